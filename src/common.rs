@@ -1,17 +1,24 @@
 //! Types and utilities shared between the glow and wgpu renderers.
 
-/// Shadow offset in component coordinate space.
+/// Default shadow offset in component coordinate space.
 pub(crate) const SHADOW_OFFSET: f32 = 0.05;
 
+/// Most texels sampled on each side of a pixel by the text shadow blur.
+/// Limits the blur's standard deviation to a third of this.
+pub(crate) const MAX_SHADOW_BLUR_TAPS: u32 = 64;
+
 /// Blur sigma scale factor applied to the larger image dimension.
 /// Matches livesplit-core's `BLUR_FACTOR`.
 pub(crate) const BLUR_FACTOR: f32 = 0.05;
 
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::time::{Duration, Instant};
 
 use bytemuck::{Pod, Zeroable};
-use livesplit_core::rendering::SharedOwnership;
-use lyon::path::Path as LyonPath;
+use livesplit_core::rendering::{
+    Background, Entity, FillShader, Handle, SharedOwnership, Transform,
+};
+use lyon::path::{Path as LyonPath, PathEvent};
 
 /// A vertex in a tessellated path, ready for the GPU.
 ///
@@ -22,6 +29,27 @@ use lyon::path::Path as LyonPath;
 pub struct Vertex {
     /// X and Y position in local (pre-transform) coordinate space.
     pub position: [f32; 2],
+    /// Outward direction of the path edge at this vertex, in local space,
+    /// scaled to the corner's miter length. Zero for vertices inside the
+    /// shape.
+    ///
+    /// Only used for [`Antialiasing::Analytic`], where edge vertices are
+    /// moved along it to form the coverage fringe.
+    pub normal: [f32; 2],
+    /// Fraction of the pixel covered by the shape at this vertex: `1.0`
+    /// for the shape itself, `0.0` for the outer edge of its fringe.
+    pub coverage: f32,
+}
+
+impl Vertex {
+    /// A fully covered vertex at `position`, not on the shape's edge.
+    pub const fn new(position: [f32; 2]) -> Self {
+        Self {
+            position,
+            normal: [0.0, 0.0],
+            coverage: 1.0,
+        }
+    }
 }
 
 /// A tessellated path stored as indexed triangle data.
@@ -39,32 +67,104 @@ pub struct Path {
     pub indices: Arc<Vec<u32>>,
     /// The original lyon path, retained for stroke tessellation.
     pub lyon_path: Arc<LyonPath>,
-    /// Cached stroke tessellation, keyed by stroke width.
-    stroke_cache: RwLock<Option<StrokeCache>>,
+    /// Axis-aligned bounds of [`vertices`](Self::vertices), `[min, max]`
+    /// in local coordinates, computed when the path is created. Zero for a
+    /// path without vertices. Used for gradients and culling, so it must be
+    /// kept in sync if the vertices are replaced.
+    pub bounds: [[f32; 2]; 2],
+    /// Cached stroke tessellations, shared with clones of this path.
+    stroke_cache: Arc<Mutex<StrokeCache>>,
+    /// Fill tessellations at other curve tolerances, oldest first (see
+    /// [`fill_at_tolerance`]). Shared with clones of this path.
+    fill_levels: Arc<RwLock<Vec<FillLevel>>>,
+    /// The geometry with its coverage fringe, built the first time it is
+    /// needed. Shared with clones of this path.
+    fringe: Arc<OnceLock<Fringe>>,
+}
+
+/// A path's geometry with the coverage fringe around its edges (see
+/// [`Antialiasing::Analytic`]).
+pub(crate) struct Fringe {
+    /// The path's vertices with their edge normals set, followed by a
+    /// zero-coverage copy of each outline vertex.
+    vertices: Vec<Vertex>,
+    /// The path's own triangle indices, followed by the fringe's.
+    indices: Vec<u32>,
 }
 
-/// Shared vertex and index buffers for a tessellated path.
-type StrokeGeometry = (Arc<Vec<Vertex>>, Arc<Vec<u32>>);
+/// Number of stroke tessellations kept per path.
+const MAX_STROKE_ENTRIES: usize = 8;
 
-/// Cached stroke tessellation data for a specific line width.
+/// Mantissa bits of a stroke width kept by [`quantize_stroke_width`].
+const STROKE_WIDTH_BITS: u32 = 10;
+
+/// Round a stroke width to [`STROKE_WIDTH_BITS`] significant bits (about
+/// 0.1%), so widths that differ only by rounding noise share a stroke
+/// tessellation. Non-finite widths are returned unchanged, and finite ones
+/// never round up to infinity.
+pub(crate) fn quantize_stroke_width(width: f32) -> f32 {
+    if !width.is_finite() {
+        return width;
+    }
+    let dropped = f32::MANTISSA_DIGITS - 1 - STROKE_WIDTH_BITS;
+    let half = 1 << (dropped - 1);
+    let quantized = f32::from_bits(((width.to_bits() + half) >> dropped) << dropped);
+    if quantized.is_finite() {
+        quantized
+    } else {
+        f32::MAX.copysign(width)
+    }
+}
+
+/// Stroke tessellations of a path at different widths and tolerances,
+/// least recently used first.
+#[derive(Default)]
 struct StrokeCache {
-    /// The stroke width this cache was tessellated for.
-    width: f32,
-    /// Stroke triangle vertices.
-    vertices: Arc<Vec<Vertex>>,
-    /// Stroke triangle indices.
-    indices: Arc<Vec<u32>>,
+    /// The cached strokes. At most [`MAX_STROKE_ENTRIES`].
+    entries: Vec<StrokeEntry>,
+}
+
+/// Cached stroke tessellation for a specific line width.
+struct StrokeEntry {
+    /// What this entry was tessellated for.
+    key: StrokeKey,
+    /// The stroke geometry, handed out as clones.
+    stroke: Path,
+}
+
+/// The stroke cache key: a stroke's width, tolerance, and style.
+#[derive(PartialEq)]
+struct StrokeKey {
+    /// Bits of the quantized stroke width.
+    width: u32,
+    /// Bits of the curve tolerance.
+    tolerance: u32,
+    /// Joins, caps, and dashes.
+    style: StrokeStyle,
+}
+
+impl StrokeKey {
+    fn new(width: f32, tolerance: f32, style: &StrokeStyle) -> Self {
+        Self {
+            width: quantize_stroke_width(width).to_bits(),
+            tolerance: tolerance.to_bits(),
+            style: style.clone(),
+        }
+    }
+}
+
+/// A fill tessellation of a path's outline at one curve tolerance.
+struct FillLevel {
+    /// The curve tolerance this level was tessellated with.
+    tolerance: f32,
+    /// The fill geometry, handed out as clones.
+    fill: Path,
 }
 
 impl Path {
     /// Create a new `Path` from tessellated geometry and the original path.
     pub fn new(vertices: Vec<Vertex>, indices: Vec<u32>, lyon_path: Arc<LyonPath>) -> Self {
-        Self {
-            vertices: Arc::new(vertices),
-            indices: Arc::new(indices),
-            lyon_path,
-            stroke_cache: RwLock::new(None),
-        }
+        Self::from_arcs(Arc::new(vertices), Arc::new(indices), lyon_path)
     }
 
     /// Create a `Path` from pre-shared vertex and index buffers.
@@ -73,40 +173,97 @@ impl Path {
         indices: Arc<Vec<u32>>,
         lyon_path: Arc<LyonPath>,
     ) -> Self {
+        let [min_x, max_x] = vertex_bounds(&vertices, 0);
+        let [min_y, max_y] = vertex_bounds(&vertices, 1);
         Self {
             vertices,
             indices,
             lyon_path,
-            stroke_cache: RwLock::new(None),
+            bounds: [[min_x, min_y], [max_x, max_y]],
+            stroke_cache: Arc::default(),
+            fill_levels: Arc::default(),
+            fringe: Arc::default(),
         }
     }
 
-    /// Get the cached stroke tessellation for a given width, or `None` if
-    /// the cache is empty or was tessellated for a different width.
-    pub fn cached_stroke(&self, width: f32) -> Option<StrokeGeometry> {
-        let cache = self
-            .stroke_cache
-            .read()
-            .expect("stroke cache RwLock poisoned");
-        cache.as_ref().and_then(|c| {
-            if (c.width - width).abs() < f32::EPSILON {
-                Some((Arc::clone(&c.vertices), Arc::clone(&c.indices)))
-            } else {
-                None
-            }
+    /// A path drawing the same geometry, with caches of its own. Unlike a
+    /// clone it can be stored in this path's caches without keeping them
+    /// alive.
+    fn share_geometry(&self) -> Self {
+        Self {
+            vertices: Arc::clone(&self.vertices),
+            indices: Arc::clone(&self.indices),
+            lyon_path: Arc::clone(&self.lyon_path),
+            bounds: self.bounds,
+            stroke_cache: Arc::default(),
+            fill_levels: Arc::default(),
+            fringe: Arc::clone(&self.fringe),
+        }
+    }
+
+    /// The geometry with its coverage fringe, built on first use.
+    pub(crate) fn fringed(&self) -> &Fringe {
+        self.fringe.get_or_init(|| {
+            let mut vertices = self.vertices.to_vec();
+            let mut indices = self.indices.to_vec();
+            let fringe = add_coverage_fringe(&mut vertices, &indices);
+            indices.extend(fringe);
+            Fringe { vertices, indices }
         })
     }
 
-    /// Store a stroke tessellation in the cache for a given width.
-    pub fn set_stroke_cache(&self, width: f32, vertices: Arc<Vec<Vertex>>, indices: Arc<Vec<u32>>) {
+    /// The vertices and indices to draw the path with. Draws with a
+    /// `fringe`, or whose edges are moved because they are `dilated`, use
+    /// the [fringed](Self::fringed) geometry for its edge normals, but only
+    /// the former draw the fringe triangles.
+    pub(crate) fn draw_geometry(&self, fringe: bool, dilated: bool) -> (&[Vertex], &[u32]) {
+        if !fringe && !dilated {
+            return (&self.vertices, &self.indices);
+        }
+        let fringed = self.fringed();
+        let indices = if fringe {
+            &fringed.indices[..]
+        } else {
+            &fringed.indices[..self.indices.len()]
+        };
+        (&fringed.vertices, indices)
+    }
+
+    /// Get the cached stroke tessellation for a given width, curve
+    /// tolerance, and style, or `None` if it is not cached. Widths are
+    /// compared after [`quantize_stroke_width`]. The returned path shares
+    /// the cached buffers.
+    ///
+    /// A hit makes the entry the most recently used.
+    pub fn cached_stroke(&self, width: f32, tolerance: f32, style: &StrokeStyle) -> Option<Path> {
+        let key = StrokeKey::new(width, tolerance, style);
         let mut cache = self
             .stroke_cache
-            .write()
-            .expect("stroke cache RwLock poisoned");
-        *cache = Some(StrokeCache {
-            width,
-            vertices,
-            indices,
+            .lock()
+            .expect("stroke cache Mutex poisoned");
+        let index = cache.entries.iter().position(|entry| entry.key == key)?;
+        let entry = cache.entries.remove(index);
+        let stroke = entry.stroke.clone();
+        cache.entries.push(entry);
+        Some(stroke)
+    }
+
+    /// Store a stroke tessellation in the cache for a given width, curve
+    /// tolerance, and style, evicting the least recently used entry if the
+    /// cache is full.
+    pub fn set_stroke_cache(&self, width: f32, tolerance: f32, style: &StrokeStyle, stroke: &Path) {
+        let key = StrokeKey::new(width, tolerance, style);
+        let mut cache = self
+            .stroke_cache
+            .lock()
+            .expect("stroke cache Mutex poisoned");
+        cache.entries.retain(|entry| entry.key != key);
+        if cache.entries.len() == MAX_STROKE_ENTRIES {
+            cache.entries.remove(0);
+        }
+        cache.entries.push(StrokeEntry {
+            key,
+            stroke: stroke.clone(),
         });
     }
 }
@@ -117,8 +274,12 @@ impl Clone for Path {
             vertices: Arc::clone(&self.vertices),
             indices: Arc::clone(&self.indices),
             lyon_path: Arc::clone(&self.lyon_path),
-            // Start with an empty cache — it will be populated on first stroke draw.
-            stroke_cache: RwLock::new(None),
+            bounds: self.bounds,
+            // Clones draw the same outline, so they share the caches: a
+            // path shared every frame does not re-tessellate.
+            stroke_cache: Arc::clone(&self.stroke_cache),
+            fill_levels: Arc::clone(&self.fill_levels),
+            fringe: Arc::clone(&self.fringe),
         }
     }
 }
@@ -128,6 +289,8 @@ impl std::fmt::Debug for Path {
         f.debug_struct("Path")
             .field("vertices", &self.vertices.len())
             .field("indices", &self.indices.len())
+            .field("fringed", &self.fringe.get().is_some())
+            .field("bounds", &self.bounds)
             .finish_non_exhaustive()
     }
 }
@@ -156,8 +319,8 @@ pub type LockedLabel = livesplit_core::rendering::default_text_engine::LockedLab
 
 /// Compute the min/max of a single axis across all path vertices.
 ///
-/// Used to determine the interpolation range for gradient shaders.
-/// `axis` is the index into `Vertex::position` (0 = X, 1 = Y).
+/// Used to compute [`Path::bounds`] when a path is created. `axis` is the
+/// index into `Vertex::position` (0 = X, 1 = Y).
 ///
 /// Returns `[0.0, 0.0]` for an empty vertex slice.
 pub fn vertex_bounds(vertices: &[Vertex], axis: usize) -> [f32; 2] {
@@ -175,236 +338,2757 @@ pub fn vertex_bounds(vertices: &[Vertex], axis: usize) -> [f32; 2] {
     }
 }
 
-/// Lyon-backed path builder that produces a [`Path`] on `finish()`.
+/// Axis-aligned bounds, `[min, max]` in viewport pixels, of a path's
+/// vertices drawn with `transform` followed by the output transform.
 ///
-/// Implements livesplit-core's [`PathBuilder`](livesplit_core::rendering::PathBuilder) trait,
-/// converting path commands (move/line/quad/curve/close) into a lyon path and
-/// then tessellating it into an indexed triangle mesh.
-pub struct CommonPathBuilder {
-    /// The underlying lyon path builder.
-    builder: lyon::path::path::Builder,
-    /// Whether a sub-path has been opened (via `begin`) but not yet closed.
-    /// Lyon panics if `build()` is called while a sub-path is still open.
-    sub_path_open: bool,
+/// Returns `None` for a path without vertices.
+pub(crate) fn pixel_bounds(
+    path: &Path,
+    transform: &Transform,
+    output: &OutputTransform,
+) -> Option<[[f32; 2]; 2]> {
+    if path.vertices.is_empty() {
+        return None;
+    }
+    Some(transform_bounds(path.bounds, transform, output))
 }
 
-impl CommonPathBuilder {
-    /// Create a new path builder.
-    pub fn new() -> Self {
-        Self {
-            builder: LyonPath::builder(),
-            sub_path_open: false,
+/// Axis-aligned bounds, `[min, max]` in viewport pixels, of the local
+/// rectangle `[min_x, min_y], [max_x, max_y]` drawn with `transform`
+/// followed by the output transform.
+pub(crate) fn transform_bounds(
+    [[min_x, min_y], [max_x, max_y]]: [[f32; 2]; 2],
+    transform: &Transform,
+    output: &OutputTransform,
+) -> [[f32; 2]; 2] {
+    let mut min = [f32::INFINITY; 2];
+    let mut max = [f32::NEG_INFINITY; 2];
+    for [x, y] in [
+        [min_x, min_y],
+        [max_x, min_y],
+        [min_x, max_y],
+        [max_x, max_y],
+    ] {
+        let point = output.apply([
+            transform.x + transform.scale_x * x,
+            transform.y + transform.scale_y * y,
+        ]);
+        for axis in 0..2 {
+            min[axis] = min[axis].min(point[axis]);
+            max[axis] = max[axis].max(point[axis]);
         }
     }
+    [min, max]
 }
 
-impl livesplit_core::rendering::PathBuilder for CommonPathBuilder {
-    type Path = Option<Path>;
+/// Axis-aligned bounds, `[min, max]` in viewport pixels, of the glyphs of
+/// a label drawn with `transform` followed by the output transform.
+///
+/// Returns `None` for a label without visible glyphs.
+pub(crate) fn label_pixel_bounds(
+    label: &LockedLabel,
+    transform: &Transform,
+    output: &OutputTransform,
+) -> Option<[[f32; 2]; 2]> {
+    label
+        .glyphs()
+        .iter()
+        .filter_map(|glyph| {
+            let path = glyph.path.as_ref()?;
+            let transform = transform
+                .pre_translate(glyph.x, glyph.y)
+                .pre_scale(glyph.scale, glyph.scale);
+            pixel_bounds(path, &transform, output)
+        })
+        .reduce(|[min, max], [glyph_min, glyph_max]| {
+            [
+                [min[0].min(glyph_min[0]), min[1].min(glyph_min[1])],
+                [max[0].max(glyph_max[0]), max[1].max(glyph_max[1])],
+            ]
+        })
+}
 
-    fn move_to(&mut self, x: f32, y: f32) {
-        if self.sub_path_open {
-            self.builder.end(false);
+/// Pixels beyond an entity's bounds that it may still touch: antialiased
+/// edges, analytic coverage fringes, and rasterized glyphs snapped to the
+/// pixel grid.
+const CULL_MARGIN: f32 = 2.0;
+
+/// Entity and stroke cache counts of the last frame a renderer drew.
+///
+/// Only the layers drawn that frame are counted: the bottom layer is
+/// counted on frames where it is re-rendered, not when its cached texture
+/// is reused.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct FrameStats {
+    /// Entities drawn.
+    pub drawn_entities: u32,
+    /// Entities skipped because they lie entirely outside the viewport or
+    /// have no geometry.
+    pub culled_entities: u32,
+    /// Strokes, including text outlines, whose tessellation was reused
+    /// from their path's stroke cache.
+    pub stroke_cache_hits: u32,
+    /// Strokes that missed the stroke cache and were tessellated.
+    pub stroke_cache_misses: u32,
+}
+
+impl FrameStats {
+    /// Count an entity as drawn or culled.
+    pub(crate) fn count(&mut self, drawn: bool) {
+        if drawn {
+            self.drawn_entities += 1;
+        } else {
+            self.culled_entities += 1;
         }
-        self.builder.begin(lyon::math::point(x, y));
-        self.sub_path_open = true;
     }
 
-    fn line_to(&mut self, x: f32, y: f32) {
-        self.builder.line_to(lyon::math::point(x, y));
+    /// Add the counts of another layer drawn in the same frame.
+    pub(crate) fn add(&mut self, other: Self) {
+        self.drawn_entities += other.drawn_entities;
+        self.culled_entities += other.culled_entities;
+        self.stroke_cache_hits += other.stroke_cache_hits;
+        self.stroke_cache_misses += other.stroke_cache_misses;
     }
+}
 
-    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
-        self.builder
-            .quadratic_bezier_to(lyon::math::point(x1, y1), lyon::math::point(x, y));
-    }
+/// What an entity may draw beyond its geometry, for culling entities that
+/// lie entirely outside the viewport.
+///
+/// Bounds are conservative: an entity is only culled if nothing it draws,
+/// including strokes, shadows, outlines, and glows, can reach the viewport.
+pub(crate) struct Culling<'a> {
+    /// Size of the viewport, in pixels.
+    resolution: [f32; 2],
+    /// The transform from layout units to viewport pixels.
+    output: &'a OutputTransform,
+    /// How strokes are joined and capped.
+    stroke_style: &'a StrokeStyle,
+    /// Offset of label shadows, as a fraction of the font size.
+    shadow_offset: [f32; 2],
+    /// Pixels label shadows spread and blur beyond their glyphs.
+    shadow_margin: f32,
+    /// Width of label outlines, as a fraction of the font size.
+    outline_width: f32,
+    /// Pixels label glyphs draw beyond their outlines, for distance field
+    /// outlines and glows.
+    glyph_margin: f32,
+}
 
-    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
-        self.builder.cubic_bezier_to(
-            lyon::math::point(x1, y1),
-            lyon::math::point(x2, y2),
-            lyon::math::point(x, y),
-        );
+impl<'a> Culling<'a> {
+    /// Culling for a viewport of `resolution` pixels drawn with the given
+    /// renderer settings.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        resolution: [f32; 2],
+        output: &'a OutputTransform,
+        scale_factor: f32,
+        stroke_style: &'a StrokeStyle,
+        text_shadow: &TextShadow,
+        text_outline: Option<TextOutline>,
+        text_rendering: TextRendering,
+    ) -> Self {
+        let sigma = text_shadow.blur_sigma(scale_factor);
+        // Precision loss is acceptable: the tap count is at most 64.
+        #[expect(clippy::cast_precision_loss)]
+        let blur = if sigma > 0.0 {
+            shadow_blur_taps(sigma) as f32
+        } else {
+            0.0
+        };
+        let glyph_margin = match text_rendering {
+            TextRendering::DistanceField(effects) => {
+                effects.outline_width.max(0.0) + effects.glow_radius.max(0.0)
+            }
+            TextRendering::Geometry | TextRendering::Rasterized => 0.0,
+        };
+        Self {
+            resolution,
+            output,
+            stroke_style,
+            shadow_offset: text_shadow.offset,
+            shadow_margin: blur + text_shadow.spread_pixels(scale_factor),
+            outline_width: text_outline.map_or(0.0, |outline| outline.width.max(0.0)),
+            glyph_margin,
+        }
     }
 
-    fn close(&mut self) {
-        self.builder.close();
-        self.sub_path_open = false;
+    /// Whether `entity` may draw anything inside the viewport.
+    pub(crate) fn is_visible<I>(&self, entity: &Entity<Option<Path>, I, Label>) -> bool {
+        let (bounds, margin) = match entity {
+            Entity::FillPath(path, _, transform) => (
+                path.as_ref()
+                    .and_then(|path| pixel_bounds(path, transform, self.output)),
+                0.0,
+            ),
+            Entity::StrokePath(path, width, _, transform) => (
+                path.as_ref()
+                    .filter(|path| !path.vertices.is_empty())
+                    .map(|path| {
+                        let extent = self.stroke_style.max_extent(*width);
+                        let [min, max] = path.bounds;
+                        transform_bounds(
+                            [min.map(|min| min - extent), max.map(|max| max + extent)],
+                            transform,
+                            self.output,
+                        )
+                    }),
+                0.0,
+            ),
+            Entity::Image(_, transform) => (
+                Some(transform_bounds(
+                    [[0.0; 2], [1.0; 2]],
+                    transform,
+                    self.output,
+                )),
+                0.0,
+            ),
+            Entity::Label(label, _, shadow, transform) => {
+                let label = label.read().expect("label RwLock poisoned");
+                let shadow_offset = shadow.map(|_| self.shadow_offset);
+                (
+                    self.label_bounds(&label, shadow_offset, transform),
+                    self.glyph_margin + shadow.map_or(0.0, |_| self.shadow_margin),
+                )
+            }
+        };
+        let margin = margin + CULL_MARGIN;
+        bounds.is_some_and(|[min, max]| {
+            (0..2).all(|axis| max[axis] >= -margin && min[axis] <= self.resolution[axis] + margin)
+        })
     }
 
-    fn finish(mut self) -> Self::Path {
-        if self.sub_path_open {
-            self.builder.end(false);
+    /// Bounds, in viewport pixels, of a label's glyphs grown by the
+    /// outline, together with their shadow if it has one.
+    ///
+    /// Returns `None` for a label without visible glyphs.
+    fn label_bounds(
+        &self,
+        label: &LockedLabel,
+        shadow_offset: Option<[f32; 2]>,
+        transform: &Transform,
+    ) -> Option<[[f32; 2]; 2]> {
+        // Glyph bounds in the label's own units, where the font size is 1.
+        let [mut min, mut max] = label
+            .glyphs()
+            .iter()
+            .filter_map(|glyph| {
+                let path = glyph
+                    .path
+                    .as_ref()
+                    .filter(|path| !path.vertices.is_empty())?;
+                let [min, max] = path.bounds.map(|corner| {
+                    [
+                        glyph.x + glyph.scale * corner[0],
+                        glyph.y + glyph.scale * corner[1],
+                    ]
+                });
+                Some([
+                    [min[0].min(max[0]), min[1].min(max[1])],
+                    [min[0].max(max[0]), min[1].max(max[1])],
+                ])
+            })
+            .reduce(|[min, max], [glyph_min, glyph_max]| {
+                [
+                    [min[0].min(glyph_min[0]), min[1].min(glyph_min[1])],
+                    [max[0].max(glyph_max[0]), max[1].max(glyph_max[1])],
+                ]
+            })?;
+        for axis in 0..2 {
+            min[axis] -= self.outline_width;
+            max[axis] += self.outline_width;
+            if let Some(offset) = shadow_offset {
+                min[axis] = min[axis].min(min[axis] + offset[axis]);
+                max[axis] = max[axis].max(max[axis] + offset[axis]);
+            }
         }
-        let path = self.builder.build();
-        tessellate_path(&path)
+        Some(transform_bounds([min, max], transform, self.output))
     }
 }
 
-/// Tessellate a lyon path into an indexed triangle mesh (fill).
+/// The rectangle `[x, y, width, height]`, in texels of a target of the
+/// given `size` (y pointing down), that a blurred text shadow whose
+/// outlines lie within `bounds` is computed in: the bounds grown by
+/// `margin` pixels on each side, rounded outwards, and clipped to the
+/// target.
 ///
-/// Uses a fill tessellator with the non-zero fill rule and a tolerance of
-/// 0.01 (suitable for the small coordinate spaces livesplit-core uses).
+/// Returns `None` if nothing of it lies within the target.
+#[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub(crate) fn shadow_region(
+    [min, max]: [[f32; 2]; 2],
+    margin: f32,
+    size: [u32; 2],
+) -> Option<[u32; 4]> {
+    let mut region = [0; 4];
+    for axis in 0..2 {
+        // Precision loss is acceptable: target dimensions are small
+        // relative to f32 mantissa range.
+        #[expect(clippy::cast_precision_loss)]
+        let limit = size[axis] as f32;
+        let start = (min[axis] - margin).floor().clamp(0.0, limit);
+        let end = (max[axis] + margin).ceil().clamp(0.0, limit);
+        if end <= start {
+            return None;
+        }
+        region[axis] = start as u32;
+        region[axis + 2] = (end - start) as u32;
+    }
+    Some(region)
+}
+
+/// A [`FillShader`] resolved for drawing one path: its colors converted to
+/// the target's color encoding, and a gradient's extent along its axis.
 ///
-/// Returns `None` if tessellation fails or produces no vertices.
-pub fn tessellate_path(path: &LyonPath) -> Option<Path> {
-    use lyon::tessellation::{
-        BuffersBuilder, FillOptions, FillRule, FillTessellator, FillVertex, VertexBuffers,
-    };
+/// Matches the `shader_type`, `color_a`, `color_b`, and `bounds` inputs of
+/// the path and text shaders.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) struct Fill {
+    /// `0` for a solid color, `1` for a vertical and `2` for a horizontal
+    /// gradient.
+    pub(crate) shader_type: i32,
+    /// The solid color, or the gradient's start and end colors.
+    pub(crate) colors: [[f32; 4]; 2],
+    /// Start and end of the gradient along its axis, in the path's local
+    /// units.
+    pub(crate) bounds: [f32; 2],
+}
 
-    let mut geometry: VertexBuffers<Vertex, u32> = VertexBuffers::new();
-    let mut tessellator = FillTessellator::new();
+impl Fill {
+    /// Opaque white, for coverage masks.
+    pub(crate) const MASK: Self = Self::solid([1.0; 4]);
 
-    let result = tessellator.tessellate_path(
-        path,
-        &FillOptions::tolerance(0.01).with_fill_rule(FillRule::NonZero),
-        &mut BuffersBuilder::new(&mut geometry, |vertex: FillVertex| Vertex {
-            position: vertex.position().to_array(),
-        }),
-    );
+    /// A solid fill of an already resolved color.
+    pub(crate) const fn solid(color: [f32; 4]) -> Self {
+        Self {
+            shader_type: 0,
+            colors: [color, [0.0; 4]],
+            bounds: [0.0; 2],
+        }
+    }
+}
 
-    match result {
-        Ok(()) if !geometry.vertices.is_empty() => Some(Path::new(
-            geometry.vertices,
-            geometry.indices,
-            Arc::new(path.clone()),
-        )),
-        _ => None,
+/// The renderer settings a [`DisplayList`] is built with.
+pub(crate) struct DisplaySettings<'a> {
+    /// Size of the viewport, in pixels.
+    pub(crate) resolution: [f32; 2],
+    /// Size the layout was laid out at, in layout units.
+    pub(crate) layout_resolution: [f32; 2],
+    /// The transform from layout units to viewport pixels.
+    pub(crate) layout_transform: &'a OutputTransform,
+    /// Ratio of physical pixels to logical layout units.
+    pub(crate) scale_factor: f32,
+    /// How stroked paths are joined, capped, and dashed.
+    pub(crate) stroke_style: &'a StrokeStyle,
+    /// Shape of label shadows.
+    pub(crate) text_shadow: &'a TextShadow,
+    /// Outline drawn around label glyphs, if any.
+    pub(crate) text_outline: Option<TextOutline>,
+    /// How labels are drawn.
+    pub(crate) text_rendering: TextRendering,
+    /// How colors are written to the target.
+    pub(crate) encoding: ColorEncoding,
+}
+
+/// A label shadow that is blurred off-screen before it is drawn.
+pub(crate) struct ShadowItem {
+    /// The shadow glyphs, tessellated for their transforms, whose coverage
+    /// is drawn in [`Fill::MASK`] grown by [`spread`](Self::spread).
+    pub(crate) masks: Vec<(Path, Transform)>,
+    /// Pixels the masks are grown by along their edge normals.
+    pub(crate) spread: f32,
+    /// Standard deviation of the blur, in pixels.
+    pub(crate) sigma: f32,
+    /// Texels the blur samples on each side of a pixel.
+    pub(crate) taps: u32,
+    /// Bounds of the masks before they are grown, `[min, max]` in viewport
+    /// pixels.
+    pub(crate) bounds: [[f32; 2]; 2],
+    /// Resolved, straight-alpha color the blurred coverage is drawn in.
+    pub(crate) color: [f32; 4],
+}
+
+impl ShadowItem {
+    /// Pixels beyond [`bounds`](Self::bounds) the blurred shadow may reach.
+    pub(crate) fn margin(&self) -> f32 {
+        // Precision loss is acceptable: the tap count is at most 64.
+        #[expect(clippy::cast_precision_loss)]
+        let taps = self.taps as f32;
+        taps + self.spread + 1.0
     }
 }
 
-/// Tessellate a path outline (stroke) into an indexed triangle mesh.
-///
-/// Uses lyon's stroke tessellator with the given `stroke_width` and a
-/// tolerance of 0.01. Results are cached inside the [`Path`]'s stroke
-/// cache so that repeated draws at the same width do not re-tessellate.
+/// An image stretched over the unit square.
+pub(crate) struct ImageItem<'a, I> {
+    /// The image to sample.
+    pub(crate) image: &'a Handle<I>,
+    /// Maps the unit square to layout units.
+    pub(crate) transform: Transform,
+    /// Multiplier of the image's color channels in the target's encoding.
+    pub(crate) brightness: f32,
+    /// Multiplier of the image's alpha.
+    pub(crate) opacity: f32,
+    /// Whether to sample the renderer's blurred copy of the background
+    /// image instead (see [`blurred_background`]).
+    pub(crate) blurred: bool,
+}
+
+/// One draw of a [`DisplayList`].
+pub(crate) enum DisplayItem<'a, I> {
+    /// The cached bottom layer, drawn 1:1 over the viewport.
+    CachedLayer,
+    /// A tessellated path, filled and grown by `dilation` pixels along its
+    /// edge normals.
+    Path {
+        path: Path,
+        fill: Fill,
+        transform: Transform,
+        dilation: f32,
+    },
+    /// A label glyph drawn from the glyph atlas: as a distance field with
+    /// `effects` (whose colors are resolved like fills), or as rasterized
+    /// coverage if there are none. Glyphs that do not fit the atlas are
+    /// drawn as `path` filled at the tolerance of their size instead.
+    Glyph {
+        path: Path,
+        fill: Fill,
+        transform: Transform,
+        effects: Option<TextEffects>,
+    },
+    /// A label shadow blurred off-screen.
+    Shadow(ShadowItem),
+    /// An image or background image.
+    Image(ImageItem<'a, I>),
+}
+
+/// The draws of one layer of a scene, in order, with transforms, colors,
+/// and tessellations resolved for the renderer's settings.
 ///
-/// Returns `None` if tessellation fails or produces no geometry.
-pub fn tessellate_stroke(path: &Path, stroke_width: f32) -> Option<Path> {
-    use lyon::tessellation::{
-        BuffersBuilder, StrokeOptions, StrokeTessellator, StrokeVertex, VertexBuffers,
-    };
+/// Built once per layer from the scene by the code shared between the
+/// renderers, which only execute the items: entities outside the viewport
+/// are culled, strokes and fills are tessellated for their size on screen,
+/// and labels are broken up into their shadow, outline, and glyph draws.
+pub(crate) struct DisplayList<'a, I> {
+    /// The draws, in order.
+    items: Vec<DisplayItem<'a, I>>,
+    /// The entities drawn and culled.
+    stats: FrameStats,
+}
 
-    // Check the cache first.
-    if let Some((verts, idxs)) = path.cached_stroke(stroke_width) {
-        return Some(Path::from_arcs(verts, idxs, Arc::clone(&path.lyon_path)));
+impl<'a, I> DisplayList<'a, I> {
+    /// The cached bottom layer, drawn at full opacity: the `background`, if
+    /// any, filling the scene's unit `rectangle` stretched over the layout,
+    /// and the `entities` over it.
+    pub(crate) fn bottom_layer(
+        settings: &DisplaySettings<'_>,
+        background: Option<&'a Background<I>>,
+        rectangle: Option<&Path>,
+        entities: &'a [Entity<Option<Path>, I, Label>],
+    ) -> Self {
+        let mut builder = DisplayListBuilder::new(settings);
+        if let Some(background) = background {
+            builder.push_background(background, rectangle);
+        }
+        for entity in entities {
+            builder.push_entity(entity);
+        }
+        builder.list
     }
 
-    // Cache miss — tessellate the stroke.
-    let mut geometry: VertexBuffers<Vertex, u32> = VertexBuffers::new();
-    let mut tessellator = StrokeTessellator::new();
+    /// The chroma `key` filling the scene's unit `rectangle` stretched over
+    /// the layout, for drawing the layout over it where the key cannot be
+    /// applied to the finished frame.
+    pub(crate) fn key_layer(
+        settings: &DisplaySettings<'_>,
+        key: &ChromaKey,
+        rectangle: Option<&Path>,
+    ) -> Self {
+        let mut builder = DisplayListBuilder::new(settings);
+        let [r, g, b] = key.color;
+        builder.push_layout_rectangle(&FillShader::SolidColor([r, g, b, 1.0]), rectangle);
+        builder.list
+    }
 
-    let result = tessellator.tessellate_path(
-        &*path.lyon_path,
-        &StrokeOptions::tolerance(0.01).with_line_width(stroke_width),
-        &mut BuffersBuilder::new(&mut geometry, |vertex: StrokeVertex| Vertex {
-            position: vertex.position().to_array(),
-        }),
-    );
+    /// The composited frame: the cached bottom layer and the top layer's
+    /// `entities` over it, at full opacity. The layout's opacity fades the
+    /// finished frame as a whole in the final pass, so that overlapping
+    /// entities do not show through each other.
+    pub(crate) fn top_layer(
+        settings: &DisplaySettings<'_>,
+        entities: &'a [Entity<Option<Path>, I, Label>],
+    ) -> Self {
+        let mut builder = DisplayListBuilder::new(settings);
+        builder.list.items.push(DisplayItem::CachedLayer);
+        for entity in entities {
+            builder.push_entity(entity);
+        }
+        builder.list
+    }
 
-    match result {
-        Ok(()) if !geometry.vertices.is_empty() => {
-            let verts = Arc::new(geometry.vertices);
-            let idxs = Arc::new(geometry.indices);
+    /// The draws, in order.
+    pub(crate) fn items(&self) -> &[DisplayItem<'a, I>] {
+        &self.items
+    }
 
-            // Populate the cache for next time.
-            path.set_stroke_cache(stroke_width, Arc::clone(&verts), Arc::clone(&idxs));
+    /// How many entities were drawn and culled.
+    pub(crate) fn stats(&self) -> FrameStats {
+        self.stats
+    }
+}
+
+/// Builds a [`DisplayList`] for one layer.
+struct DisplayListBuilder<'s, 'a, I> {
+    settings: &'s DisplaySettings<'s>,
+    culling: Culling<'s>,
+    list: DisplayList<'a, I>,
+}
+
+impl<'s, 'a, I> DisplayListBuilder<'s, 'a, I> {
+    fn new(settings: &'s DisplaySettings<'s>) -> Self {
+        Self {
+            settings,
+            culling: Culling::new(
+                settings.resolution,
+                settings.layout_transform,
+                settings.scale_factor,
+                settings.stroke_style,
+                settings.text_shadow,
+                settings.text_outline,
+                settings.text_rendering,
+            ),
+            list: DisplayList {
+                items: Vec::new(),
+                stats: FrameStats::default(),
+            },
+        }
+    }
+
+    /// Convert an sRGB color from livesplit-core to the target's encoding.
+    fn color(&self, c: &[f32; 4]) -> [f32; 4] {
+        self.settings.encoding.encode(c)
+    }
+
+    /// Resolve `shader` for filling `path`, whose bounds span a gradient.
+    fn fill(&self, shader: &FillShader, path: &Path) -> Fill {
+        let (shader_type, [a, b], axis) = match shader {
+            FillShader::SolidColor(color) => return Fill::solid(self.color(color)),
+            FillShader::VerticalGradient(top, bottom) => (1, [top, bottom], 1),
+            FillShader::HorizontalGradient(left, right) => (2, [left, right], 0),
+        };
+        Fill {
+            shader_type,
+            colors: [self.color(a), self.color(b)],
+            bounds: path.bounds.map(|corner| corner[axis]),
+        }
+    }
+
+    /// Curve tolerance for paths drawn with `transform`.
+    fn tolerance(&self, transform: &Transform) -> f32 {
+        curve_tolerance(transform, self.settings.layout_transform)
+    }
+
+    fn push_background(&mut self, background: &'a Background<I>, rectangle: Option<&Path>) {
+        match background {
+            Background::Shader(shader) => self.push_layout_rectangle(shader, rectangle),
+            Background::Image(image, transform) => {
+                self.list.items.push(DisplayItem::Image(ImageItem {
+                    image: &image.image,
+                    transform: *transform,
+                    brightness: image.brightness * self.settings.encoding.brightness(),
+                    opacity: image.opacity,
+                    blurred: image.blur > 0.0,
+                }));
+            }
+        }
+    }
+
+    fn push_entity(&mut self, entity: &'a Entity<Option<Path>, I, Label>) {
+        let visible = self.culling.is_visible(entity);
+        self.list.stats.count(visible);
+        if !visible {
+            return;
+        }
+
+        match entity {
+            Entity::FillPath(path, shader, transform) => {
+                if let Some(path) = path.as_ref() {
+                    let path = fill_at_tolerance(path, self.tolerance(transform));
+                    let fill = self.fill(shader, &path);
+                    self.push_path(path, fill, *transform, 0.0);
+                }
+            }
+            Entity::StrokePath(path, width, color, transform) => {
+                let stroke = path.as_ref().and_then(|path| {
+                    tessellate_stroke(
+                        path,
+                        *width,
+                        self.tolerance(transform),
+                        self.settings.stroke_style,
+                        &mut self.list.stats,
+                    )
+                });
+                if let Some(stroke) = stroke {
+                    let fill = Fill::solid(self.color(color));
+                    self.push_path(stroke, fill, *transform, 0.0);
+                }
+            }
+            Entity::Image(image, transform) => {
+                self.list.items.push(DisplayItem::Image(ImageItem {
+                    image,
+                    transform: *transform,
+                    brightness: self.settings.encoding.brightness(),
+                    opacity: 1.0,
+                    blurred: false,
+                }));
+            }
+            Entity::Label(label, shader, shadow, transform) => {
+                let label = label.read().expect("label RwLock poisoned");
+                self.push_label(&label, shader, shadow.as_ref(), transform);
+            }
+        }
+    }
+
+    /// Push the scene's unit `rectangle`, if any, stretched over the whole
+    /// layout and filled with `shader`.
+    fn push_layout_rectangle(&mut self, shader: &FillShader, rectangle: Option<&Path>) {
+        let Some(rectangle) = rectangle else {
+            return;
+        };
+        let [width, height] = self.settings.layout_resolution;
+        let transform = Transform {
+            scale_x: width,
+            scale_y: height,
+            x: 0.0,
+            y: 0.0,
+        };
+        let fill = self.fill(shader, rectangle);
+        self.push_path(rectangle.clone(), fill, transform, 0.0);
+    }
+
+    fn push_path(&mut self, path: Path, fill: Fill, transform: Transform, dilation: f32) {
+        if path.vertices.is_empty() || path.indices.is_empty() {
+            return;
+        }
+        self.list.items.push(DisplayItem::Path {
+            path,
+            fill,
+            transform,
+            dilation,
+        });
+    }
+
+    /// Push a label's shadow, if it has one, then its outline, if enabled,
+    /// and then its glyphs. The shadow and outline colors are modulated by
+    /// the label's alpha.
+    fn push_label(
+        &mut self,
+        label: &LockedLabel,
+        shader: &FillShader,
+        shadow: Option<&[f32; 4]>,
+        transform: &Transform,
+    ) {
+        let alpha = match shader {
+            FillShader::SolidColor([.., a]) => *a,
+            FillShader::VerticalGradient([.., a1], [.., a2])
+            | FillShader::HorizontalGradient([.., a1], [.., a2]) => 0.5 * (a1 + a2),
+        };
+
+        if let Some(&[r, g, b, a]) = shadow {
+            self.push_label_shadow(label, [r, g, b, a * alpha], transform);
+        }
+
+        if let Some(outline) = self
+            .settings
+            .text_outline
+            .filter(|outline| outline.width > 0.0)
+        {
+            let mut color = outline.color;
+            color[3] *= alpha;
+            let fill = Fill::solid(self.color(&color));
+            let style = StrokeStyle::default();
+            for (path, glyph_transform, scale) in glyphs(label, transform) {
+                let width = outline.stroke_width(scale);
+                let tolerance = self.tolerance(&glyph_transform);
+                let stats = &mut self.list.stats;
+                if let Some(stroke) = tessellate_stroke(path, width, tolerance, &style, stats) {
+                    self.push_path(stroke, fill, glyph_transform, 0.0);
+                }
+            }
+        }
+
+        let effects = match self.settings.text_rendering {
+            TextRendering::Geometry | TextRendering::Rasterized => TextEffects::default(),
+            TextRendering::DistanceField(effects) => effects,
+        };
+        for glyph in label.glyphs() {
+            if let Some(path) = &glyph.path {
+                let glyph_transform = transform
+                    .pre_translate(glyph.x, glyph.y)
+                    .pre_scale(glyph.scale, glyph.scale);
+                let shader = glyph.color.map_or(*shader, FillShader::SolidColor);
+                let fill = self.fill(&shader, path);
+                self.push_glyph(path, fill, glyph_transform, &effects);
+            }
+        }
+    }
+
+    /// Push a label's shadow in `color`, shaped by the [`TextShadow`]
+    /// setting: blurred off-screen, or a hard-edged copy of the glyphs.
+    fn push_label_shadow(&mut self, label: &LockedLabel, color: [f32; 4], transform: &Transform) {
+        let text_shadow = self.settings.text_shadow;
+        let [offset_x, offset_y] = text_shadow.offset;
+        let transform = transform.pre_translate(offset_x, offset_y);
+        let sigma = text_shadow.blur_sigma(self.settings.scale_factor);
+        let spread = text_shadow.spread_pixels(self.settings.scale_factor);
+
+        if sigma > 0.0 {
+            let Some(bounds) =
+                label_pixel_bounds(label, &transform, self.settings.layout_transform)
+            else {
+                return;
+            };
+            let masks = glyphs(label, &transform)
+                .map(|(path, transform, _)| {
+                    (
+                        fill_at_tolerance(path, self.tolerance(&transform)),
+                        transform,
+                    )
+                })
+                .collect();
+            self.list.items.push(DisplayItem::Shadow(ShadowItem {
+                masks,
+                spread,
+                sigma,
+                taps: shadow_blur_taps(sigma),
+                bounds,
+                color: self.color(&color),
+            }));
+            return;
+        }
 
-            Some(Path::from_arcs(verts, idxs, Arc::clone(&path.lyon_path)))
+        let fill = Fill::solid(self.color(&color));
+        for (path, glyph_transform, _) in glyphs(label, &transform) {
+            if spread > 0.0 {
+                let path = fill_at_tolerance(path, self.tolerance(&glyph_transform));
+                self.push_path(path, fill, glyph_transform, spread);
+            } else {
+                self.push_glyph(path, fill, glyph_transform, &TextEffects::default());
+            }
         }
+    }
+
+    /// Push a glyph, drawn as geometry or from the glyph atlas depending on
+    /// the [`TextRendering`] mode.
+    fn push_glyph(&mut self, path: &Path, fill: Fill, transform: Transform, effects: &TextEffects) {
+        let effects = match self.settings.text_rendering {
+            TextRendering::Geometry => {
+                let path = fill_at_tolerance(path, self.tolerance(&transform));
+                self.push_path(path, fill, transform, 0.0);
+                return;
+            }
+            TextRendering::DistanceField(_) => Some(TextEffects {
+                outline_color: self.color(&effects.outline_color),
+                glow_color: self.color(&effects.glow_color),
+                ..*effects
+            }),
+            TextRendering::Rasterized => None,
+        };
+        self.list.items.push(DisplayItem::Glyph {
+            path: path.clone(),
+            fill,
+            transform,
+            effects,
+        });
+    }
+}
+
+/// The glyphs of a label that have a path, with their transforms for a
+/// label drawn with `transform` and their scales.
+fn glyphs<'l>(
+    label: &'l LockedLabel,
+    transform: &Transform,
+) -> impl Iterator<Item = (&'l Path, Transform, f32)> {
+    let transform = *transform;
+    label.glyphs().iter().filter_map(move |glyph| {
+        let path = glyph.path.as_ref()?;
+        let transform = transform
+            .pre_translate(glyph.x, glyph.y)
+            .pre_scale(glyph.scale, glyph.scale);
+        Some((path, transform, glyph.scale))
+    })
+}
+
+/// The image and blur radius of `background`, if it is a blurred image,
+/// whose blurred copy the renderers compute on the CPU and cache before
+/// drawing the bottom layer.
+pub(crate) fn blurred_background<I>(
+    background: Option<&Background<I>>,
+) -> Option<(&Handle<I>, f32)> {
+    match background? {
+        Background::Image(image, _) if image.blur > 0.0 => Some((&image.image, image.blur)),
         _ => None,
     }
 }
 
-#[cfg(test)]
-#[allow(clippy::unwrap_used)]
-pub(crate) mod tests {
-    use super::*;
-    use livesplit_core::rendering::SharedOwnership;
+/// The rectangle of the render target that the layout is drawn into, in
+/// pixels.
+///
+/// The layout is laid out at the viewport's size and drawn at its origin.
+/// Pixels of the target outside the viewport are left untouched. The origin
+/// is the top-left corner of the rectangle for both renderers (as for
+/// `RenderPass::set_viewport`); [`GlowRenderer`](crate::GlowRenderer) flips
+/// it into GL's bottom-left window coordinates itself.
+///
+/// A plain `[width, height]` converts into a viewport at the origin, so
+/// existing callers that render to the whole target keep working.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Viewport {
+    /// Horizontal offset of the viewport in pixels.
+    pub x: u32,
+    /// Vertical offset of the viewport in pixels.
+    pub y: u32,
+    /// Width of the viewport in pixels.
+    pub width: u32,
+    /// Height of the viewport in pixels.
+    pub height: u32,
+}
 
-    /// Helper to compare `[f32; 2]` with tolerance.
-    pub(crate) fn assert_bounds_eq(actual: [f32; 2], expected: [f32; 2]) {
-        assert!(
-            (actual[0] - expected[0]).abs() < f32::EPSILON
-                && (actual[1] - expected[1]).abs() < f32::EPSILON,
-            "expected {expected:?}, got {actual:?}",
-        );
+impl Viewport {
+    /// Create a viewport from its origin and size.
+    #[must_use]
+    pub const fn new(x: u32, y: u32, width: u32, height: u32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
     }
 
-    #[test]
-    fn vertex_bounds_y_known_values() {
-        let vertices = [
-            Vertex {
-                position: [0.0, 1.0],
-            },
-            Vertex {
-                position: [1.0, 3.0],
-            },
-            Vertex {
-                position: [2.0, 2.0],
-            },
-        ];
-        assert_bounds_eq(vertex_bounds(&vertices, 1), [1.0, 3.0]);
+    /// The viewport's `[width, height]`.
+    #[must_use]
+    pub const fn size(&self) -> [u32; 2] {
+        [self.width, self.height]
     }
 
-    #[test]
-    fn vertex_bounds_x_known_values() {
-        let vertices = [
-            Vertex {
-                position: [5.0, 0.0],
-            },
-            Vertex {
-                position: [2.0, 0.0],
-            },
-            Vertex {
-                position: [8.0, 0.0],
+    /// Whether the viewport has zero area.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    /// Whether the viewport covers exactly a target of the given size.
+    #[must_use]
+    pub const fn covers(&self, [width, height]: [u32; 2]) -> bool {
+        self.x == 0 && self.y == 0 && self.width == width && self.height == height
+    }
+}
+
+impl From<[u32; 2]> for Viewport {
+    fn from([width, height]: [u32; 2]) -> Self {
+        Self::new(0, 0, width, height)
+    }
+}
+
+/// How the finished layout is combined with the existing contents of the
+/// render target inside the [`Viewport`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum CompositeMode {
+    /// Overwrite the target's pixels with the layout, including its
+    /// transparent areas.
+    #[default]
+    Replace,
+    /// Alpha-blend the premultiplied layout over whatever the target
+    /// already contains, e.g. a game capture or a background drawn by the
+    /// host application.
+    Blend,
+}
+
+/// How the alpha of the finished layout is stored in the render target.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum AlphaMode {
+    /// Colors are multiplied by their alpha (default), as expected by
+    /// `wgpu::CompositeAlphaMode::PreMultiplied` surfaces and most
+    /// compositors.
+    #[default]
+    Premultiplied,
+    /// Colors are divided by their alpha in the final pass (straight, or
+    /// unassociated, alpha), as expected by
+    /// `wgpu::CompositeAlphaMode::PostMultiplied` surfaces, some capture
+    /// tools, and PNG encoders. Fully transparent pixels are black.
+    ///
+    /// With [`CompositeMode::Blend`], the straight colors are blended with
+    /// the usual source-alpha blend function.
+    Straight,
+}
+
+/// An animation of the layout's opacity from one value to another, eased
+/// in and out over `duration`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Fade {
+    /// Opacity at the start of the fade.
+    pub from: f32,
+    /// Opacity at the end of the fade.
+    pub to: f32,
+    /// How long the fade takes.
+    pub duration: Duration,
+}
+
+impl Fade {
+    /// The opacity `elapsed` after the fade started, clamped to `0..=1`.
+    #[must_use]
+    pub fn opacity_at(&self, elapsed: Duration) -> f32 {
+        let opacity = if self.is_finished(elapsed) {
+            self.to
+        } else {
+            let t = elapsed.as_secs_f32() / self.duration.as_secs_f32();
+            let eased = t * t * (3.0 - 2.0 * t);
+            self.from + (self.to - self.from) * eased
+        };
+        opacity.clamp(0.0, 1.0)
+    }
+
+    /// Whether the fade has reached its final opacity `elapsed` after it
+    /// started.
+    #[must_use]
+    pub fn is_finished(&self, elapsed: Duration) -> bool {
+        elapsed >= self.duration
+    }
+}
+
+/// The opacity the renderers apply to the whole layout, and the fade
+/// animating it, if any.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) struct LayoutOpacity {
+    /// Opacity as of the last [`advance`](Self::advance).
+    value: f32,
+    /// The running fade and when it started.
+    fade: Option<(Fade, Instant)>,
+}
+
+impl LayoutOpacity {
+    /// A fully opaque layout.
+    pub(crate) const OPAQUE: Self = Self {
+        value: 1.0,
+        fade: None,
+    };
+
+    /// The current opacity.
+    pub(crate) fn get(&self) -> f32 {
+        self.value
+    }
+
+    /// Set the opacity, clamped to `0..=1`, and stop any fade.
+    pub(crate) fn set(&mut self, opacity: f32) {
+        self.value = opacity.clamp(0.0, 1.0);
+        self.fade = None;
+    }
+
+    /// Start fading from the current opacity to `opacity` at `now`. A fade
+    /// that is still running is first moved on to `now`, so the new one
+    /// starts where it left off.
+    pub(crate) fn fade_to(&mut self, opacity: f32, duration: Duration, now: Instant) {
+        self.advance(now);
+        let fade = Fade {
+            from: self.value,
+            to: opacity,
+            duration,
+        };
+        self.fade = Some((fade, now));
+    }
+
+    /// Move the running fade, if any, on to `now`, and stop it once it is
+    /// finished.
+    pub(crate) fn advance(&mut self, now: Instant) {
+        if let Some((fade, start)) = self.fade {
+            let elapsed = now.saturating_duration_since(start);
+            self.value = fade.opacity_at(elapsed);
+            if fade.is_finished(elapsed) {
+                self.fade = None;
+            }
+        }
+    }
+
+    /// Whether a fade is still running, so the layout needs redrawing.
+    pub(crate) fn is_fading(&self) -> bool {
+        self.fade.is_some()
+    }
+}
+
+/// A 4×5 matrix applied to the layout's colors as it is composited into the
+/// output, e.g. for grayscale, inverted, or colorblind-friendly output.
+///
+/// Each row computes one channel of the result from the straight (not
+/// premultiplied) color and a constant offset:
+///
+/// ```text
+/// r' = rows[0][0] * r + rows[0][1] * g + rows[0][2] * b + rows[0][3] * a + rows[0][4]
+/// g' = rows[1][0] * r + ...
+/// b' = rows[2][0] * r + ...
+/// a' = rows[3][0] * r + ...
+/// ```
+///
+/// Channels run from `0.0` to `1.0` (the layout's white), in the output's
+/// color encoding, and the result is clamped to that range. The matrix is
+/// applied to the whole finished frame, so it affects every layout without
+/// editing it.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ColorMatrix {
+    rows: [[f32; 5]; 4],
+}
+
+impl Default for ColorMatrix {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// Rec. 709 luma weights.
+const LUMA: [f32; 3] = [0.2126, 0.7152, 0.0722];
+
+impl ColorMatrix {
+    /// The matrix that leaves colors as they are.
+    pub const IDENTITY: Self = Self::from_rows([
+        [1.0, 0.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0, 0.0],
+    ]);
+
+    /// Replace each color by its luma.
+    pub const GRAYSCALE: Self = Self::from_rows([
+        [LUMA[0], LUMA[1], LUMA[2], 0.0, 0.0],
+        [LUMA[0], LUMA[1], LUMA[2], 0.0, 0.0],
+        [LUMA[0], LUMA[1], LUMA[2], 0.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0, 0.0],
+    ]);
+
+    /// Invert the colors, keeping alpha.
+    pub const INVERT: Self = Self::from_rows([
+        [-1.0, 0.0, 0.0, 0.0, 1.0],
+        [0.0, -1.0, 0.0, 0.0, 1.0],
+        [0.0, 0.0, -1.0, 0.0, 1.0],
+        [0.0, 0.0, 0.0, 1.0, 0.0],
+    ]);
+
+    /// Double the contrast around mid-gray ([`contrast(2.0)`](Self::contrast)).
+    pub const HIGH_CONTRAST: Self = Self::contrast(2.0);
+
+    /// Create a matrix from its four rows.
+    #[must_use]
+    pub const fn from_rows(rows: [[f32; 5]; 4]) -> Self {
+        Self { rows }
+    }
+
+    /// The four rows of the matrix.
+    #[must_use]
+    pub const fn rows(&self) -> [[f32; 5]; 4] {
+        self.rows
+    }
+
+    /// Scale the colors' distance from mid-gray by `amount`: `1.0` leaves
+    /// them as they are, larger values increase contrast.
+    #[must_use]
+    pub const fn contrast(amount: f32) -> Self {
+        let offset = 0.5 - 0.5 * amount;
+        Self::from_rows([
+            [amount, 0.0, 0.0, 0.0, offset],
+            [0.0, amount, 0.0, 0.0, offset],
+            [0.0, 0.0, amount, 0.0, offset],
+            [0.0, 0.0, 0.0, 1.0, 0.0],
+        ])
+    }
+
+    /// Daltonize the colors for a color vision deficiency: the difference
+    /// between each color and how it appears with the deficiency is moved
+    /// into channels that are still told apart, so that e.g. red and green
+    /// split deltas differ in brightness and blue for deuteranopes.
+    #[must_use]
+    pub fn daltonize(deficiency: ColorDeficiency) -> Self {
+        // Viénot, Brettel & Mollon (1999): RGB to LMS cone space,
+        // and the projection onto the colors a dichromat can see.
+        const RGB_TO_LMS: [[f32; 3]; 3] = [
+            [17.8824, 43.5161, 4.11935],
+            [3.45565, 27.1554, 3.86714],
+            [0.029_956_6, 0.184_309, 1.46709],
+        ];
+        const LMS_TO_RGB: [[f32; 3]; 3] = [
+            [0.080_944_45, -0.130_504_41, 0.116_721_07],
+            [-0.010_248_533, 0.054_019_33, -0.113_614_71],
+            [-0.000_365_296_94, -0.004_121_614_7, 0.693_511_4],
+        ];
+        let (simulation, shift) = match deficiency {
+            ColorDeficiency::Protanopia => (
+                [[0.0, 2.02344, -2.52581], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+                [[0.0, 0.0, 0.0], [0.7, 1.0, 0.0], [0.7, 0.0, 1.0]],
+            ),
+            ColorDeficiency::Deuteranopia => (
+                [[1.0, 0.0, 0.0], [0.494_207, 0.0, 1.24827], [0.0, 0.0, 1.0]],
+                [[0.0, 0.0, 0.0], [0.7, 1.0, 0.0], [0.7, 0.0, 1.0]],
+            ),
+            ColorDeficiency::Tritanopia => (
+                [
+                    [1.0, 0.0, 0.0],
+                    [0.0, 1.0, 0.0],
+                    [-0.395_913, 0.801_109, 0.0],
+                ],
+                [[1.0, 0.0, 0.7], [0.0, 1.0, 0.7], [0.0, 0.0, 0.0]],
+            ),
+        };
+        let simulated = mul3(&LMS_TO_RGB, &mul3(&simulation, &RGB_TO_LMS));
+        // color + shift * (color - simulated color)
+        let lost = std::array::from_fn(|i| {
+            std::array::from_fn(|j| if i == j { 1.0 } else { 0.0 } - simulated[i][j])
+        });
+        let correction = mul3(&shift, &lost);
+        let mut rows = Self::IDENTITY.rows;
+        for (row, correction) in rows.iter_mut().zip(correction) {
+            for (value, correction) in row.iter_mut().zip(correction) {
+                *value += correction;
+            }
+        }
+        Self::from_rows(rows)
+    }
+
+    /// The matrix that applies `self` first and then `next`.
+    #[must_use]
+    pub fn then(&self, next: &Self) -> Self {
+        let rows = next.rows.map(|next_row| {
+            let mut row = [0.0; 5];
+            for (k, first_row) in self.rows.iter().enumerate() {
+                for (value, first) in row.iter_mut().zip(first_row) {
+                    *value += next_row[k] * first;
+                }
+            }
+            row[4] += next_row[4];
+            row
+        });
+        Self::from_rows(rows)
+    }
+
+    /// Apply the matrix to a straight RGBA color, clamping the result to
+    /// `0..=1`.
+    #[must_use]
+    pub fn apply(&self, color: [f32; 4]) -> [f32; 4] {
+        self.rows.map(|row| {
+            let sum: f32 = row.iter().zip(&color).map(|(m, c)| m * c).sum();
+            (sum + row[4]).clamp(0.0, 1.0)
+        })
+    }
+
+    /// Whether this is the identity matrix.
+    #[must_use]
+    pub fn is_identity(&self) -> bool {
+        *self == Self::IDENTITY
+    }
+
+    /// The 4×4 part of the matrix as column-major columns, and the offsets,
+    /// as uploaded to the shaders.
+    pub(crate) fn columns_and_offset(&self) -> ([[f32; 4]; 4], [f32; 4]) {
+        let columns = std::array::from_fn(|j| self.rows.map(|row| row[j]));
+        (columns, self.rows.map(|row| row[4]))
+    }
+}
+
+/// 3×3 matrix product `a * b`.
+fn mul3(a: &[[f32; 3]; 3], b: &[[f32; 3]; 3]) -> [[f32; 3]; 3] {
+    std::array::from_fn(|i| std::array::from_fn(|j| (0..3).map(|k| a[i][k] * b[k][j]).sum()))
+}
+
+/// A dichromatic color vision deficiency, for
+/// [`ColorMatrix::daltonize`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ColorDeficiency {
+    /// No working red (long-wavelength) cones.
+    Protanopia,
+    /// No working green (medium-wavelength) cones, the most common form of
+    /// red-green color blindness.
+    Deuteranopia,
+    /// No working blue (short-wavelength) cones.
+    Tritanopia,
+}
+
+/// A rounded-rectangle mask and border applied to the finished layout as it
+/// is composited, e.g. for borderless transparent splits windows and stream
+/// overlays.
+///
+/// The mask covers the whole [`Viewport`]; everything outside its rounded
+/// corners is transparent, with antialiased edges. The border is drawn
+/// along the inside of the mask's edge, over the layout. Lengths are in
+/// logical units, scaled to pixels by the renderer's scale factor.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct OutputMask {
+    /// Radius of the corners. Clamped to half the viewport's smaller side.
+    pub corner_radius: f32,
+    /// Width of the border, `0.0` for none.
+    pub border_width: f32,
+    /// Color of the border, with straight alpha, in sRGB.
+    pub border_color: [f32; 4],
+}
+
+impl Default for OutputMask {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+impl OutputMask {
+    /// No mask and no border (default).
+    pub const NONE: Self = Self {
+        corner_radius: 0.0,
+        border_width: 0.0,
+        border_color: [0.0; 4],
+    };
+
+    /// Round the corners by `radius`, without a border.
+    #[must_use]
+    pub const fn rounded(radius: f32) -> Self {
+        Self {
+            corner_radius: radius,
+            ..Self::NONE
+        }
+    }
+
+    /// The same mask with a border of the given width and color.
+    #[must_use]
+    pub const fn with_border(self, width: f32, color: [f32; 4]) -> Self {
+        Self {
+            border_width: width,
+            border_color: color,
+            ..self
+        }
+    }
+
+    /// Whether the mask leaves the layout unchanged.
+    #[must_use]
+    pub fn is_none(&self) -> bool {
+        self.corner_radius <= 0.0 && (self.border_width <= 0.0 || self.border_color[3] <= 0.0)
+    }
+
+    /// The corner radius and border width in pixels, for a viewport of
+    /// `size` pixels at `scale_factor`, each clamped to half the viewport's
+    /// smaller side.
+    pub(crate) fn pixel_extents(&self, scale_factor: f32, [width, height]: [f32; 2]) -> [f32; 2] {
+        let max = 0.5 * width.min(height);
+        [self.corner_radius, self.border_width]
+            .map(|length| (length * scale_factor).clamp(0.0, max))
+    }
+
+    /// The border color in `encoding`, premultiplied.
+    pub(crate) fn border_color(&self, encoding: ColorEncoding) -> [f32; 4] {
+        let [r, g, b, a] = encoding.encode(&self.border_color);
+        [r * a, g * a, b * a, a]
+    }
+}
+
+/// An opaque key color that replaces the layout's background, for capture
+/// setups that remove the background with a chroma key filter because they
+/// cannot capture alpha, e.g. window capture in OBS.
+///
+/// The layout is drawn without the background from its settings, over the
+/// key color filling the [`Viewport`]. By default partially covered pixels
+/// mix the layout with the key (`color + key × (1 − alpha)`), so keyers
+/// that pull a soft matte keep the layout's antialiased edges.
+///
+/// Keyers that only cut a hard matte leave fringes of key color around
+/// text and shapes instead. For them, a `threshold` fills every pixel the
+/// layout covers less than the threshold with exactly the key color (not
+/// blended, dithered, or antialiased) and shows every other pixel in the
+/// layout's own color at full opacity, unmixed with the key. Edges then
+/// look aliased against the key but antialiased against whatever the keyed
+/// capture is placed over.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ChromaKey {
+    /// The key color, in sRGB.
+    pub color: [f32; 3],
+    /// Coverage from which a pixel shows the layout instead of the key,
+    /// clamped to `1/255..=1`, or `None` to mix partially covered pixels
+    /// with the key (the default).
+    pub threshold: Option<f32>,
+}
+
+impl Default for ChromaKey {
+    fn default() -> Self {
+        Self::GREEN
+    }
+}
+
+impl ChromaKey {
+    /// Pure green, the usual key color (default).
+    pub const GREEN: Self = Self::new([0.0, 1.0, 0.0]);
+    /// Pure blue, for layouts with green text.
+    pub const BLUE: Self = Self::new([0.0, 0.0, 1.0]);
+    /// Pure magenta, for layouts using both green and blue.
+    pub const MAGENTA: Self = Self::new([1.0, 0.0, 1.0]);
+
+    /// Key with `color`, mixed with partially covered pixels.
+    #[must_use]
+    pub const fn new(color: [f32; 3]) -> Self {
+        Self {
+            color,
+            threshold: None,
+        }
+    }
+
+    /// The same key, cut at a threshold for keyers that only pull a hard
+    /// matte. A threshold of `0.5` keeps text at its weight; lower
+    /// thresholds make it bolder, higher ones thinner.
+    #[must_use]
+    pub const fn with_threshold(self, threshold: f32) -> Self {
+        Self {
+            threshold: Some(threshold),
+            ..self
+        }
+    }
+
+    /// The opaque key color in `encoding`.
+    pub(crate) fn encoded_color(&self, encoding: ColorEncoding) -> [f32; 4] {
+        let [r, g, b] = self.color;
+        encoding.encode(&[r, g, b, 1.0])
+    }
+
+    /// The threshold, if any, clamped so that uncovered pixels are always
+    /// keyed and fully covered ones never are.
+    pub(crate) fn clamped_threshold(&self) -> Option<f32> {
+        self.threshold
+            .map(|threshold| threshold.clamp(1.0 / 255.0, 1.0))
+    }
+}
+
+/// How the renderers antialias path and image edges.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Antialiasing {
+    /// Render into a 4x multisampled target and resolve it (default).
+    #[default]
+    Msaa,
+    /// Render at one sample per pixel and smooth path edges with a
+    /// one-pixel coverage fringe, built the first time a path is drawn
+    /// in this mode.
+    ///
+    /// The cheapest mode, for GPUs where multisampling is too slow. Text
+    /// and shapes stay smooth, but image edges are not antialiased.
+    Analytic,
+    /// Render into a single-sample target `factor` times the viewport size
+    /// in each dimension and downsample it into the output with `filter`.
+    ///
+    /// Works on drivers with poor multisampling support and gives smoother
+    /// thin lines than 4x MSAA, at the cost of `factor²` times the fill
+    /// rate and memory. A `factor` of `0` is treated as `1`, which disables
+    /// antialiasing. The factor is lowered for viewports where the enlarged
+    /// target would exceed the device's maximum texture size (for
+    /// [`GlowRenderer`](crate::GlowRenderer), the smaller of
+    /// `GL_MAX_TEXTURE_SIZE` and `GL_MAX_RENDERBUFFER_SIZE`).
+    Supersample {
+        /// Scale of the off-screen target relative to the viewport.
+        factor: u32,
+        /// Filter used to downsample the off-screen target.
+        filter: DownsampleFilter,
+    },
+}
+
+/// Filter used to downsample a supersampled frame
+/// (see [`Antialiasing::Supersample`]).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DownsampleFilter {
+    /// Average each `factor`×`factor` block of samples. Cheap and never
+    /// rings.
+    #[default]
+    Box,
+    /// Two-lobe Lanczos filter. Sharper than [`Box`](Self::Box), at the
+    /// cost of `16 × factor²` samples per output pixel.
+    Lanczos,
+}
+
+/// Noise added to colors before they are stored in the target, to break up
+/// the banding that slow gradients and blurred backgrounds show on 8-bit
+/// targets.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Dithering {
+    /// No dithering (default).
+    #[default]
+    None,
+    /// An 8×8 ordered (Bayer) dither of one step of the output format,
+    /// applied to paths, images, and the supersampling filter. The pattern
+    /// depends only on the pixel position, so the same frame always
+    /// produces the same pixels.
+    Ordered,
+}
+
+impl Dithering {
+    /// Amplitude of the dither in color units (`1.0` = full range) for
+    /// targets of the given format, or `0.0` if disabled.
+    pub(crate) fn amplitude(self, format: OutputFormat) -> f32 {
+        match self {
+            Self::None => 0.0,
+            Self::Ordered => format.quantization_step(),
+        }
+    }
+}
+
+/// Luminance of scRGB's reference white, `1.0`, in nits.
+pub const SCRGB_WHITE_NITS: f32 = 80.0;
+
+/// Pixel format of the renderer's color targets.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum OutputFormat {
+    /// 8 bits per channel holding sRGB-encoded colors (default).
+    #[default]
+    Rgba8,
+    /// 10 bits per color channel and 2 bits of alpha, holding sRGB-encoded
+    /// colors. Shows less banding in gradients, but alpha only has four
+    /// levels, so it suits opaque layouts.
+    Rgb10A2,
+    /// 16-bit floats holding linear scRGB colors, as used by HDR
+    /// swapchains: `1.0` is [`SCRGB_WHITE_NITS`] and larger values are
+    /// brighter.
+    Rgba16Float,
+}
+
+impl OutputFormat {
+    /// Smallest step between two stored color values, or `0.0` for float
+    /// formats, whose precision is fine enough not to band.
+    pub(crate) fn quantization_step(self) -> f32 {
+        match self {
+            Self::Rgba8 => 1.0 / 255.0,
+            Self::Rgb10A2 => 1.0 / 1023.0,
+            Self::Rgba16Float => 0.0,
+        }
+    }
+
+    /// How colors are written to targets of this format, with the layout's
+    /// white shown at `sdr_white_level` nits on HDR targets.
+    pub(crate) fn color_encoding(self, sdr_white_level: f32) -> ColorEncoding {
+        match self {
+            Self::Rgba8 | Self::Rgb10A2 => ColorEncoding::Srgb,
+            Self::Rgba16Float => ColorEncoding::Linear {
+                brightness: sdr_white_level / SCRGB_WHITE_NITS,
             },
+        }
+    }
+}
+
+/// How the sRGB colors from livesplit-core are written to a render target.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) enum ColorEncoding {
+    /// The target stores sRGB-encoded values, so colors are written as is
+    /// and blended in sRGB space.
+    Srgb,
+    /// The target stores linear values (an sRGB format that encodes on
+    /// write, or a float format), so colors are linearized and then scaled
+    /// by `brightness`.
+    Linear {
+        /// Multiplier of the linear color channels (`1.0` = SDR white).
+        brightness: f32,
+    },
+}
+
+impl ColorEncoding {
+    /// Whether images must be sampled as linear values.
+    pub(crate) fn is_linear(self) -> bool {
+        matches!(self, Self::Linear { .. })
+    }
+
+    /// Multiplier of the color channels of everything drawn.
+    pub(crate) fn brightness(self) -> f32 {
+        match self {
+            Self::Srgb => 1.0,
+            Self::Linear { brightness } => brightness,
+        }
+    }
+
+    /// Convert a straight-alpha sRGB color to this encoding.
+    pub(crate) fn encode(self, c: &[f32; 4]) -> [f32; 4] {
+        match self {
+            Self::Srgb => *c,
+            Self::Linear { brightness } => [
+                srgb_channel_to_linear(c[0]) * brightness,
+                srgb_channel_to_linear(c[1]) * brightness,
+                srgb_channel_to_linear(c[2]) * brightness,
+                c[3], // alpha is always linear
+            ],
+        }
+    }
+}
+
+/// Convert a single sRGB channel value to linear.
+///
+/// livesplit-core provides colors in sRGB space. Targets that store linear
+/// values need them converted first; otherwise they would be
+/// double-encoded (washed out, with reduced contrast between dark shades).
+pub(crate) fn srgb_channel_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// How the renderers draw text labels.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum TextRendering {
+    /// Draw every glyph as its tessellated outline (default).
+    #[default]
+    Geometry,
+    /// Draw every glyph as a quad sampling a signed distance field of its
+    /// outline from a glyph atlas.
+    ///
+    /// Each glyph's field is built once, on the CPU, the first time it is
+    /// drawn, so long labels cost one quad per glyph instead of its whole
+    /// mesh. Edges stay sharp at any scale, and the field makes outlines
+    /// and glows (see [`TextEffects`]) nearly free. Glyphs that do not fit
+    /// in the atlas are drawn as geometry.
+    DistanceField(TextEffects),
+    /// Draw every glyph as a quad from a glyph atlas holding its coverage,
+    /// rasterized on the CPU at the size it appears on screen.
+    ///
+    /// Glyphs are moved onto the pixel grid, horizontally in quarter-pixel
+    /// steps, so small text looks as crisp as hinted text, and each label
+    /// costs one quad per glyph. Each glyph is rasterized once per size, so
+    /// this suits layouts drawn at a fixed size, like a stream overlay.
+    /// Glyphs larger than 128 pixels, or that do not fit in the atlas, are
+    /// drawn as geometry.
+    Rasterized,
+}
+
+/// Outline and glow drawn around distance field text
+/// (see [`TextRendering::DistanceField`]).
+///
+/// Widths are in output pixels. Both effects are limited to the few pixels
+/// around each glyph that its distance field covers, so very wide effects
+/// on small text are cut short. Colors are straight (not premultiplied)
+/// RGBA, like the layout's own colors.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct TextEffects {
+    /// Width of the outline around each glyph. `0.0` disables it.
+    pub outline_width: f32,
+    /// Color of the outline.
+    pub outline_color: [f32; 4],
+    /// Distance over which the glow fades out beyond the outline. `0.0`
+    /// disables it.
+    pub glow_radius: f32,
+    /// Color of the glow at the glyph's (outlined) edge.
+    pub glow_color: [f32; 4],
+}
+
+/// Soft drop shadow drawn under labels that have a shadow color.
+///
+/// livesplit-core decides which labels have a shadow and its color; this
+/// controls its shape. With a `blur_radius` the shadow glyphs are drawn into
+/// an off-screen coverage target and blurred before they are composited
+/// under the text, which costs a mask pass and two blur passes over each
+/// shadowed label's area. Without one, the shadow is a hard-edged copy of
+/// the glyphs.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TextShadow {
+    /// Offset of the shadow from the text, as a fraction of the font size.
+    /// Positive values move it right and down.
+    pub offset: [f32; 2],
+    /// Blur radius in logical pixels. As with CSS shadows, the shadow is
+    /// blurred with a gaussian whose standard deviation is half the radius,
+    /// limited to about 21 physical pixels. `0.0` disables the blur.
+    pub blur_radius: f32,
+    /// Distance, in logical pixels, that the shadow grows beyond the
+    /// glyph outlines before it is blurred.
+    pub spread: f32,
+}
+
+impl Default for TextShadow {
+    /// The hard-edged shadow livesplit-core draws itself: offset by 5% of
+    /// the font size, without blur or spread.
+    fn default() -> Self {
+        Self {
+            offset: [SHADOW_OFFSET, SHADOW_OFFSET],
+            blur_radius: 0.0,
+            spread: 0.0,
+        }
+    }
+}
+
+impl TextShadow {
+    /// Standard deviation of the blur in viewport pixels, or `0.0` for a
+    /// hard-edged shadow.
+    pub(crate) fn blur_sigma(&self, scale_factor: f32) -> f32 {
+        #[expect(clippy::cast_precision_loss)]
+        let max_sigma = MAX_SHADOW_BLUR_TAPS as f32 / 3.0;
+        (0.5 * self.blur_radius * scale_factor).clamp(0.0, max_sigma)
+    }
+
+    /// Spread in viewport pixels.
+    pub(crate) fn spread_pixels(&self, scale_factor: f32) -> f32 {
+        self.spread.max(0.0) * scale_factor
+    }
+}
+
+/// Outline drawn around every label glyph, beneath its fill.
+///
+/// The outline is the glyph's outline stroked as geometry, so unlike the
+/// distance field outline of [`TextEffects`] it works with every
+/// [`TextRendering`] mode and is not limited in width. Glyph strokes are
+/// tessellated once and cached, like those of stroked paths.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TextOutline {
+    /// Width of the outline outside the glyph, as a fraction of the font
+    /// size.
+    pub width: f32,
+    /// Color of the outline, straight (not premultiplied) RGBA. Its alpha
+    /// is multiplied by the label's.
+    pub color: [f32; 4],
+}
+
+impl TextOutline {
+    /// Stroke width, in the glyph path's own units, for a glyph drawn at
+    /// `glyph_scale`. The stroke is centered on the glyph's outline and its
+    /// inner half is hidden under the fill, so it is twice the outline's
+    /// visible width.
+    pub(crate) fn stroke_width(&self, glyph_scale: f32) -> f32 {
+        2.0 * self.width / glyph_scale
+    }
+}
+
+/// Shape drawn where two stroked segments meet.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum LineJoin {
+    /// Extend the outer edges to a point, or fall back to a bevel past the
+    /// [miter limit](StrokeStyle::miter_limit).
+    #[default]
+    Miter,
+    /// Round the corner off with a circular arc.
+    Round,
+    /// Cut the corner off with a straight line.
+    Bevel,
+}
+
+/// Shape drawn at the open ends of stroked lines and dashes.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum LineCap {
+    /// End flat at the end point.
+    #[default]
+    Butt,
+    /// End flat half a stroke width past the end point.
+    Square,
+    /// End in a half circle around the end point.
+    Round,
+}
+
+/// How the paths of the layout's [`Entity::StrokePath`] entities are
+/// stroked.
+///
+/// The layout only gives each stroke a width; joins, caps, and dashing are
+/// up to the renderer and apply to every stroke it draws. The default
+/// matches what the renderers drew before the style was configurable:
+/// mitered joins, butt caps, and solid lines.
+///
+/// [`Entity::StrokePath`]: livesplit_core::rendering::Entity::StrokePath
+#[derive(Clone, Debug, PartialEq)]
+pub struct StrokeStyle {
+    /// Corner shape.
+    pub join: LineJoin,
+    /// End shape of lines and dashes.
+    pub cap: LineCap,
+    /// Longest a miter may be, in stroke widths, before it is beveled.
+    /// Values below `1.0` are treated as `1.0`.
+    pub miter_limit: f32,
+    /// Alternating dash and gap lengths, in stroke widths, starting with a
+    /// dash. An odd-length pattern is repeated to make it even, as in SVG.
+    /// Empty, or with a negative length or no positive one, the line is
+    /// solid.
+    pub dashes: Vec<f32>,
+    /// Distance, in stroke widths, into the dash pattern at which each
+    /// subpath starts.
+    pub dash_offset: f32,
+}
+
+impl Default for StrokeStyle {
+    fn default() -> Self {
+        Self {
+            join: LineJoin::Miter,
+            cap: LineCap::Butt,
+            miter_limit: 4.0,
+            dashes: Vec::new(),
+            dash_offset: 0.0,
+        }
+    }
+}
+
+impl StrokeStyle {
+    /// lyon's stroke options for this style.
+    fn stroke_options(&self, width: f32, tolerance: f32) -> lyon::tessellation::StrokeOptions {
+        use lyon::tessellation::{LineCap as Cap, LineJoin as Join, StrokeOptions};
+        let join = match self.join {
+            LineJoin::Miter => Join::Miter,
+            LineJoin::Round => Join::Round,
+            LineJoin::Bevel => Join::Bevel,
+        };
+        let cap = match self.cap {
+            LineCap::Butt => Cap::Butt,
+            LineCap::Square => Cap::Square,
+            LineCap::Round => Cap::Round,
+        };
+        StrokeOptions::tolerance(tolerance)
+            .with_line_width(width)
+            .with_line_join(join)
+            .with_line_cap(cap)
+            .with_miter_limit(self.miter_limit.max(StrokeOptions::MINIMUM_MITER_LIMIT))
+    }
+
+    /// Furthest a stroke `width` wide reaches from the path, in the path's
+    /// units: half the width, or more at miters and square caps.
+    pub(crate) fn max_extent(&self, width: f32) -> f32 {
+        let reach = match (self.join, self.cap) {
+            (LineJoin::Miter, _) => self
+                .miter_limit
+                .max(lyon::tessellation::StrokeOptions::MINIMUM_MITER_LIMIT)
+                .max(std::f32::consts::SQRT_2),
+            (_, LineCap::Square) => std::f32::consts::SQRT_2,
+            _ => 1.0,
+        };
+        0.5 * width.abs() * reach
+    }
+
+    /// The dash pattern scaled to a stroke `width`, made even, or `None`
+    /// for a solid line.
+    fn dash_pattern(&self, width: f32) -> Option<Vec<f32>> {
+        let valid = self
+            .dashes
+            .iter()
+            .all(|&dash| dash >= 0.0 && dash.is_finite())
+            && self.dashes.iter().any(|&dash| dash > 0.0);
+        if !valid {
+            return None;
+        }
+        let repeats = if self.dashes.len().is_multiple_of(2) {
+            1
+        } else {
+            2
+        };
+        Some(
+            self.dashes
+                .iter()
+                .cycle()
+                .take(self.dashes.len() * repeats)
+                .map(|dash| dash * width)
+                .collect(),
+        )
+    }
+}
+
+/// Texels sampled on each side of a pixel by the shadow blur: three
+/// standard deviations, rounded up.
+#[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub(crate) fn shadow_blur_taps(sigma: f32) -> u32 {
+    ((3.0 * sigma).ceil() as u32).min(MAX_SHADOW_BLUR_TAPS)
+}
+
+impl Antialiasing {
+    /// The supersampling factor, or `1` when not supersampling.
+    pub(crate) fn supersample_factor(self) -> u32 {
+        match self {
+            Self::Msaa | Self::Analytic => 1,
+            Self::Supersample { factor, .. } => factor.max(1),
+        }
+    }
+
+    /// Width of the coverage fringe around path edges, in output pixels.
+    /// Zero unless the fringe is drawn.
+    pub(crate) fn fringe_width(self) -> f32 {
+        match self {
+            Self::Analytic => 1.0,
+            Self::Msaa | Self::Supersample { .. } => 0.0,
+        }
+    }
+
+    /// The supersampling factor for a viewport of the given size: the
+    /// configured factor, lowered until the enlarged target fits within
+    /// `max_dimension` pixels in each dimension (but never below `1`).
+    pub(crate) fn fitted_supersample_factor(
+        self,
+        [width, height]: [u32; 2],
+        max_dimension: u32,
+    ) -> u32 {
+        let largest = width.max(height).max(1);
+        self.supersample_factor()
+            .min(max_dimension / largest)
+            .max(1)
+    }
+
+    /// The size of the off-screen render targets for a viewport of the
+    /// given size, on a device whose textures are at most `max_dimension`
+    /// pixels wide and high.
+    pub(crate) fn target_size(self, size: [u32; 2], max_dimension: u32) -> [u32; 2] {
+        let factor = self.fitted_supersample_factor(size, max_dimension);
+        let [width, height] = size;
+        [width.saturating_mul(factor), height.saturating_mul(factor)]
+    }
+}
+
+/// A 2D affine transform applied to the whole layout on output.
+///
+/// Maps layout coordinates (pixels, y pointing down) to viewport
+/// coordinates:
+///
+/// ```text
+/// x' = rows[0][0] * x + rows[0][1] * y + rows[0][2]
+/// y' = rows[1][0] * x + rows[1][1] * y + rows[1][2]
+/// ```
+///
+/// The transform is applied in the vertex stage of every draw, so the
+/// layout stays sharp under rotation, skew, and flips. Anything mapped
+/// outside the viewport is clipped.
+///
+/// The layout is laid out at the viewport's size, with width and height
+/// swapped if the transform is closer to a quarter turn than to an
+/// upright placement (see [`layout_size`](Self::layout_size)).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct OutputTransform {
+    rows: [[f32; 3]; 2],
+}
+
+impl Default for OutputTransform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl OutputTransform {
+    /// The transform that leaves the layout where it is.
+    pub const IDENTITY: Self = Self::from_rows([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]);
+
+    /// Create a transform from the two rows of its matrix.
+    #[must_use]
+    pub const fn from_rows(rows: [[f32; 3]; 2]) -> Self {
+        Self { rows }
+    }
+
+    /// The two rows of the transform's matrix.
+    #[must_use]
+    pub const fn rows(&self) -> [[f32; 3]; 2] {
+        self.rows
+    }
+
+    /// A translation by `(x, y)` pixels.
+    #[must_use]
+    pub const fn translation(x: f32, y: f32) -> Self {
+        Self::from_rows([[1.0, 0.0, x], [0.0, 1.0, y]])
+    }
+
+    /// A scale about the origin. Negative factors flip the layout.
+    #[must_use]
+    pub const fn scale(x: f32, y: f32) -> Self {
+        Self::from_rows([[x, 0.0, 0.0], [0.0, y, 0.0]])
+    }
+
+    /// A rotation about the origin by `radians`, clockwise on screen.
+    #[must_use]
+    pub fn rotation(radians: f32) -> Self {
+        let (sin, cos) = radians.sin_cos();
+        Self::from_rows([[cos, -sin, 0.0], [sin, cos, 0.0]])
+    }
+
+    /// A skew about the origin: `x_radians` leans vertical edges, and
+    /// `y_radians` leans horizontal edges.
+    #[must_use]
+    pub fn skew(x_radians: f32, y_radians: f32) -> Self {
+        Self::from_rows([[1.0, x_radians.tan(), 0.0], [y_radians.tan(), 1.0, 0.0]])
+    }
+
+    /// Rotate the layout a quarter turn clockwise so that it fills a
+    /// viewport of the given size, e.g. for a monitor mounted on its side.
+    #[must_use]
+    pub const fn quarter_turn_clockwise([width, _]: [f32; 2]) -> Self {
+        Self::from_rows([[0.0, -1.0, width], [1.0, 0.0, 0.0]])
+    }
+
+    /// Rotate the layout a quarter turn counterclockwise so that it fills a
+    /// viewport of the given size.
+    #[must_use]
+    pub const fn quarter_turn_counterclockwise([_, height]: [f32; 2]) -> Self {
+        Self::from_rows([[0.0, 1.0, 0.0], [-1.0, 0.0, height]])
+    }
+
+    /// Mirror the layout left to right within a viewport `width` pixels
+    /// wide.
+    #[must_use]
+    pub const fn flip_horizontal(width: f32) -> Self {
+        Self::from_rows([[-1.0, 0.0, width], [0.0, 1.0, 0.0]])
+    }
+
+    /// Mirror the layout top to bottom within a viewport `height` pixels
+    /// tall.
+    #[must_use]
+    pub const fn flip_vertical(height: f32) -> Self {
+        Self::from_rows([[1.0, 0.0, 0.0], [0.0, -1.0, height]])
+    }
+
+    /// The transform that applies `self` first and then `next`.
+    #[must_use]
+    pub fn then(&self, next: &Self) -> Self {
+        let [first_x, first_y] = self.rows;
+        let row = |[dx, dy, offset]: [f32; 3]| {
+            [
+                dx * first_x[0] + dy * first_y[0],
+                dx * first_x[1] + dy * first_y[1],
+                dx * first_x[2] + dy * first_y[2] + offset,
+            ]
+        };
+        Self::from_rows([row(next.rows[0]), row(next.rows[1])])
+    }
+
+    /// The same transform, but pivoting around `center` instead of the
+    /// origin. Useful for rotating or scaling the layout about the middle
+    /// of the viewport.
+    #[must_use]
+    pub fn about(&self, [x, y]: [f32; 2]) -> Self {
+        Self::translation(-x, -y)
+            .then(self)
+            .then(&Self::translation(x, y))
+    }
+
+    /// Map a point from layout to viewport coordinates.
+    #[must_use]
+    pub fn apply(&self, [x, y]: [f32; 2]) -> [f32; 2] {
+        let [row_x, row_y] = self.rows;
+        [
+            row_x[0] * x + row_x[1] * y + row_x[2],
+            row_y[0] * x + row_y[1] * y + row_y[2],
+        ]
+    }
+
+    /// Whether this is the identity transform.
+    #[must_use]
+    pub fn is_identity(&self) -> bool {
+        *self == Self::IDENTITY
+    }
+
+    /// Whether the transform maps the layout's x axis closer to the
+    /// viewport's y axis than to its x axis (e.g. a quarter turn).
+    #[must_use]
+    pub fn swaps_axes(&self) -> bool {
+        let [row_x, row_y] = self.rows;
+        row_x[1].abs() + row_y[0].abs() > row_x[0].abs() + row_y[1].abs()
+    }
+
+    /// The size the layout is laid out at for a viewport of the given
+    /// size: the viewport's size, with width and height swapped if
+    /// [`swaps_axes`](Self::swaps_axes).
+    #[must_use]
+    pub fn layout_size(&self, [width, height]: [u32; 2]) -> [u32; 2] {
+        if self.swaps_axes() {
+            [height, width]
+        } else {
+            [width, height]
+        }
+    }
+}
+
+/// The transform from logical layout units to viewport pixels: the high-DPI
+/// `scale_factor` followed by the user's output transform.
+pub(crate) fn layout_transform(
+    output_transform: &OutputTransform,
+    scale_factor: f32,
+) -> OutputTransform {
+    OutputTransform::scale(scale_factor, scale_factor).then(output_transform)
+}
+
+/// The size, in logical units, that the layout is laid out at for a viewport
+/// of the given physical size.
+pub(crate) fn layout_resolution(
+    viewport_size: [u32; 2],
+    output_transform: &OutputTransform,
+    scale_factor: f32,
+) -> [f32; 2] {
+    let [width, height] = output_transform.layout_size(viewport_size);
+    // Precision loss is acceptable: viewport dimensions are small relative
+    // to f32 mantissa range.
+    #[expect(clippy::cast_precision_loss)]
+    let physical = [width as f32, height as f32];
+    [physical[0] / scale_factor, physical[1] / scale_factor]
+}
+
+/// Lyon-backed path builder that produces a [`Path`] on `finish()`.
+///
+/// Implements livesplit-core's [`PathBuilder`](livesplit_core::rendering::PathBuilder) trait,
+/// converting path commands (move/line/quad/curve/close) into a lyon path and
+/// then tessellating it into an indexed triangle mesh.
+pub struct CommonPathBuilder {
+    /// The underlying lyon path builder.
+    builder: lyon::path::path::Builder,
+    /// Whether a sub-path has been opened (via `begin`) but not yet closed.
+    /// Lyon panics if `build()` is called while a sub-path is still open.
+    sub_path_open: bool,
+}
+
+impl CommonPathBuilder {
+    /// Create a new path builder.
+    pub fn new() -> Self {
+        Self {
+            builder: LyonPath::builder(),
+            sub_path_open: false,
+        }
+    }
+}
+
+impl livesplit_core::rendering::PathBuilder for CommonPathBuilder {
+    type Path = Option<Path>;
+
+    fn move_to(&mut self, x: f32, y: f32) {
+        if self.sub_path_open {
+            self.builder.end(false);
+        }
+        self.builder.begin(lyon::math::point(x, y));
+        self.sub_path_open = true;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.builder.line_to(lyon::math::point(x, y));
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.builder
+            .quadratic_bezier_to(lyon::math::point(x1, y1), lyon::math::point(x, y));
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.builder.cubic_bezier_to(
+            lyon::math::point(x1, y1),
+            lyon::math::point(x2, y2),
+            lyon::math::point(x, y),
+        );
+    }
+
+    fn close(&mut self) {
+        self.builder.close();
+        self.sub_path_open = false;
+    }
+
+    fn finish(mut self) -> Self::Path {
+        if self.sub_path_open {
+            self.builder.end(false);
+        }
+        let path = self.builder.build();
+        tessellate_path(&path)
+    }
+}
+
+/// Curve tolerance, in the path's own units, of the tessellation done when
+/// a path is created.
+pub(crate) const DEFAULT_TOLERANCE: f32 = 0.01;
+
+/// Largest distance, in viewport pixels, that a tessellated curve drawn
+/// with the tolerance from [`curve_tolerance`] strays from the true curve.
+const SCREEN_TOLERANCE: f32 = 0.1;
+
+/// Range of the power-of-two exponents [`curve_tolerance`] picks from. Keeps
+/// degenerate transforms from asking for enormous or empty meshes.
+const TOLERANCE_EXPONENTS: [f32; 2] = [-16.0, 16.0];
+
+/// Number of fill tessellations at different tolerances kept per path, in
+/// addition to the one made when it was created.
+const MAX_FILL_LEVELS: usize = 4;
+
+/// Curve tolerance, in the path's own units, that keeps a path drawn with
+/// `transform` followed by `output` within a tenth of a pixel of its true
+/// outline.
+///
+/// The tolerance is rounded down to a power of two, so paths drawn at
+/// similar sizes share a tessellation and small changes in size don't cause
+/// a new one.
+pub(crate) fn curve_tolerance(transform: &Transform, output: &OutputTransform) -> f32 {
+    let [[xx, xy, _], [yx, yy, _]] = output.rows();
+    let pixels_per_unit =
+        (transform.scale_x.abs() * xx.hypot(yx)).max(transform.scale_y.abs() * xy.hypot(yy));
+    if !pixels_per_unit.is_normal() {
+        return DEFAULT_TOLERANCE;
+    }
+    let [min, max] = TOLERANCE_EXPONENTS;
+    (SCREEN_TOLERANCE / pixels_per_unit)
+        .log2()
+        .floor()
+        .clamp(min, max)
+        .exp2()
+}
+
+/// Whether a path has curved segments, whose tessellation depends on the
+/// tolerance.
+fn has_curves(path: &LyonPath) -> bool {
+    path.iter()
+        .any(|event| matches!(event, PathEvent::Quadratic { .. } | PathEvent::Cubic { .. }))
+}
+
+/// Fill-tessellate a lyon path with the given curve tolerance, returning
+/// the vertices and indices.
+fn fill_geometry(path: &LyonPath, tolerance: f32) -> Option<(Vec<Vertex>, Vec<u32>)> {
+    use lyon::tessellation::{
+        BuffersBuilder, FillOptions, FillRule, FillTessellator, FillVertex, VertexBuffers,
+    };
+
+    let mut geometry: VertexBuffers<Vertex, u32> = VertexBuffers::new();
+    let mut tessellator = FillTessellator::new();
+
+    let result = tessellator.tessellate_path(
+        path,
+        &FillOptions::tolerance(tolerance).with_fill_rule(FillRule::NonZero),
+        &mut BuffersBuilder::new(&mut geometry, |vertex: FillVertex| {
+            Vertex::new(vertex.position().to_array())
+        }),
+    );
+
+    match result {
+        Ok(()) if !geometry.vertices.is_empty() => Some((geometry.vertices, geometry.indices)),
+        _ => None,
+    }
+}
+
+/// Tessellate a lyon path into an indexed triangle mesh (fill).
+///
+/// Uses a fill tessellator with the non-zero fill rule and a tolerance of
+/// 0.01 (suitable for the small coordinate spaces livesplit-core uses).
+/// [`fill_at_tolerance`] re-tessellates it for the size it is drawn at.
+///
+/// Returns `None` if tessellation fails or produces no vertices.
+pub fn tessellate_path(path: &LyonPath) -> Option<Path> {
+    let (vertices, indices) = fill_geometry(path, DEFAULT_TOLERANCE)?;
+    Some(Path::new(vertices, indices, Arc::new(path.clone())))
+}
+
+/// The fill of `path` tessellated with the given curve tolerance (see
+/// [`curve_tolerance`]).
+///
+/// Tessellations are cached inside the [`Path`], which keeps the most
+/// recently added few. Paths without curves, and paths whose
+/// re-tessellation fails, reuse the tessellation made when they were
+/// created. The returned path shares the cached buffers.
+pub(crate) fn fill_at_tolerance(path: &Path, tolerance: f32) -> Path {
+    // Straight edges tessellate the same at every tolerance.
+    if !has_curves(&path.lyon_path) {
+        return path.clone();
+    }
+
+    {
+        let levels = path.fill_levels.read().expect("fill level RwLock poisoned");
+        if let Some(level) = levels
+            .iter()
+            .find(|level| level.tolerance.to_bits() == tolerance.to_bits())
+        {
+            return level.fill.clone();
+        }
+    }
+
+    let level = match fill_geometry(&path.lyon_path, tolerance) {
+        Some((vertices, indices)) => FillLevel {
+            tolerance,
+            fill: Path::new(vertices, indices, Arc::clone(&path.lyon_path)),
+        },
+        _ => FillLevel {
+            tolerance,
+            fill: path.share_geometry(),
+        },
+    };
+    let fill = level.fill.clone();
+
+    let mut levels = path
+        .fill_levels
+        .write()
+        .expect("fill level RwLock poisoned");
+    if levels.len() == MAX_FILL_LEVELS {
+        levels.remove(0);
+    }
+    levels.push(level);
+    fill
+}
+
+/// Tessellate a path outline (stroke) into an indexed triangle mesh.
+///
+/// Uses lyon's stroke tessellator with the given `stroke_width`, curve
+/// `tolerance` (see [`curve_tolerance`]), and `style`, dashing the path
+/// first if the style has dashes. The width is first rounded with
+/// [`quantize_stroke_width`]. Results are cached in the [`Path`]'s stroke
+/// cache, which keeps several widths and styles and is shared with the
+/// path's clones, so repeated draws of the same stroke do not
+/// re-tessellate. The lookup is counted as a hit or a miss in `stats`.
+///
+/// Returns `None` if tessellation fails or produces no geometry.
+pub fn tessellate_stroke(
+    path: &Path,
+    stroke_width: f32,
+    tolerance: f32,
+    style: &StrokeStyle,
+    stats: &mut FrameStats,
+) -> Option<Path> {
+    use lyon::tessellation::{BuffersBuilder, StrokeTessellator, StrokeVertex, VertexBuffers};
+
+    // Check the cache first. Strokes are tessellated at the quantized
+    // width, so a cached stroke is the same whichever width first missed.
+    let stroke_width = quantize_stroke_width(stroke_width);
+    if let Some(stroke) = path.cached_stroke(stroke_width, tolerance, style) {
+        stats.stroke_cache_hits += 1;
+        return Some(stroke);
+    }
+
+    // Cache miss — tessellate the stroke.
+    stats.stroke_cache_misses += 1;
+    let dashed = style.dash_pattern(stroke_width).and_then(|pattern| {
+        dash_path(
+            &path.lyon_path,
+            &pattern,
+            style.dash_offset * stroke_width,
+            tolerance,
+        )
+    });
+    let mut geometry: VertexBuffers<Vertex, u32> = VertexBuffers::new();
+    let mut tessellator = StrokeTessellator::new();
+
+    let result = tessellator.tessellate_path(
+        dashed.as_ref().unwrap_or(&path.lyon_path),
+        &style.stroke_options(stroke_width, tolerance),
+        &mut BuffersBuilder::new(&mut geometry, |vertex: StrokeVertex| {
+            Vertex::new(vertex.position().to_array())
+        }),
+    );
+
+    match result {
+        Ok(()) if !geometry.vertices.is_empty() => {
+            let stroke = Path::new(
+                geometry.vertices,
+                geometry.indices,
+                Arc::clone(&path.lyon_path),
+            );
+
+            // Populate the cache for next time.
+            path.set_stroke_cache(stroke_width, tolerance, style, &stroke);
+
+            Some(stroke)
+        }
+        _ => None,
+    }
+}
+
+/// Most dashes [`dash_path`] emits for one path. Patterns much finer than
+/// the path is long are drawn solid instead.
+const MAX_DASHES: usize = 4096;
+
+/// Split `path` into one open subpath per dash of `pattern` (alternating
+/// dash and gap lengths, of even count), starting `offset` into the pattern
+/// at the start of each subpath. Curves are flattened with `tolerance`
+/// first.
+///
+/// Returns `None` if the path would need more than [`MAX_DASHES`] dashes.
+fn dash_path(path: &LyonPath, pattern: &[f32], offset: f32, tolerance: f32) -> Option<LyonPath> {
+    use lyon::path::iterator::PathIterator;
+
+    let period: f32 = pattern.iter().sum();
+    let mut dasher = Dasher {
+        builder: LyonPath::builder(),
+        pattern,
+        start: offset.rem_euclid(period),
+        index: 0,
+        remaining: 0.0,
+        drawing: false,
+        dashes: 0,
+    };
+    for event in path.iter().flattened(tolerance) {
+        match event {
+            PathEvent::Begin { at } => dasher.begin(at),
+            PathEvent::Line { from, to } => dasher.line(from, to),
+            PathEvent::End { last, first, close } => {
+                if close {
+                    dasher.line(last, first);
+                }
+                dasher.end();
+            }
+            PathEvent::Quadratic { .. } | PathEvent::Cubic { .. } => {
+                unreachable!("flattened paths only contain lines")
+            }
+        }
+        if dasher.dashes > MAX_DASHES {
+            return None;
+        }
+    }
+    Some(dasher.builder.build())
+}
+
+/// State of [`dash_path`] while it walks a flattened path.
+struct Dasher<'a> {
+    /// The dashed path so far.
+    builder: lyon::path::path::Builder,
+    /// Dash and gap lengths.
+    pattern: &'a [f32],
+    /// Distance into the pattern at which subpaths start.
+    start: f32,
+    /// The current pattern element. Even elements are dashes.
+    index: usize,
+    /// Length left of the current element.
+    remaining: f32,
+    /// Whether a dash is open in `builder`.
+    drawing: bool,
+    /// Dashes begun so far.
+    dashes: usize,
+}
+
+impl Dasher<'_> {
+    /// Start a subpath at `at`, at the start of the pattern.
+    fn begin(&mut self, at: lyon::math::Point) {
+        self.index = 0;
+        let mut skipped = self.start;
+        while skipped >= self.pattern[self.index] {
+            skipped -= self.pattern[self.index];
+            self.index = (self.index + 1) % self.pattern.len();
+        }
+        self.remaining = self.pattern[self.index] - skipped;
+        self.begin_dash(at);
+    }
+
+    /// Follow a line of the subpath, toggling between dashes and gaps as
+    /// their lengths run out.
+    fn line(&mut self, from: lyon::math::Point, to: lyon::math::Point) {
+        let mut length = (to - from).length();
+        let direction = (to - from) / length.max(f32::MIN_POSITIVE);
+        let mut position = from;
+        while length > 0.0 && self.dashes <= MAX_DASHES {
+            // Elements that end exactly at the end of a line are only
+            // switched from once the path goes on, so no empty dash is left
+            // at the end.
+            if self.remaining <= 0.0 {
+                self.end();
+                self.index = (self.index + 1) % self.pattern.len();
+                self.remaining = self.pattern[self.index];
+                self.begin_dash(position);
+            }
+            let step = self.remaining.min(length);
+            position += direction * step;
+            self.remaining -= step;
+            length -= step;
+            if self.drawing && step > 0.0 {
+                self.builder.line_to(position);
+            }
+        }
+    }
+
+    /// Open a dash at `at` if the current element is one.
+    fn begin_dash(&mut self, at: lyon::math::Point) {
+        if self.index.is_multiple_of(2) {
+            self.builder.begin(at);
+            self.drawing = true;
+            self.dashes += 1;
+        }
+    }
+
+    /// Close the open dash, if any.
+    fn end(&mut self) {
+        if self.drawing {
+            self.builder.end(false);
+            self.drawing = false;
+        }
+    }
+}
+
+/// Longest a fringe corner may be extruded, in fringe widths. Keeps the
+/// miters of very sharp corners from spiking out of the shape.
+const MAX_FRINGE_MITER: f32 = 4.0;
+
+/// Build the coverage fringe for [`Antialiasing::Analytic`] around a
+/// triangle mesh. Also used for the edge normals that dilated draws move
+/// vertices along.
+///
+/// An edge used by exactly one triangle lies on the shape's outline. Each
+/// vertex on the outline gets its outward miter direction in
+/// [`Vertex::normal`], plus a copy with zero coverage that is appended to
+/// `vertices`. Every outline edge then gets a quad between the two,
+/// whose indices are returned. The vertex shader moves the covered side
+/// half a fringe width inwards and the uncovered side half a width
+/// outwards, so coverage ramps from 1 to 0 across the true edge.
+///
+/// Vertices are matched by position, since the tessellators can emit the
+/// same point more than once.
+fn add_coverage_fringe(vertices: &mut Vec<Vertex>, indices: &[u32]) -> Vec<u32> {
+    use std::collections::HashMap;
+
+    let position_key = |v: &Vertex| [v.position[0].to_bits(), v.position[1].to_bits()];
+
+    // The first vertex at each position stands in for all of them.
+    let mut first_at: HashMap<[u32; 2], u32> = HashMap::new();
+    let canonical: Vec<u32> = (0u32..)
+        .zip(vertices.iter())
+        .map(|(i, v)| *first_at.entry(position_key(v)).or_insert(i))
+        .collect();
+
+    let triangle_edges = |tri: &[u32]| {
+        let [a, b, c] = [0, 1, 2].map(|k| canonical[tri[k] as usize]);
+        [(a, b, c), (b, c, a), (c, a, b)]
+    };
+    let edge_key = |a: u32, b: u32| (a.min(b), a.max(b));
+
+    let mut edge_uses: HashMap<(u32, u32), u32> = HashMap::new();
+    for tri in indices.chunks_exact(3) {
+        for (a, b, _) in triangle_edges(tri) {
+            if a != b {
+                *edge_uses.entry(edge_key(a, b)).or_insert(0) += 1;
+            }
+        }
+    }
+
+    // Walk the outline in triangle order so the output is deterministic.
+    let mut outline = Vec::new();
+    let mut normal_sums: HashMap<u32, ([f32; 2], f32)> = HashMap::new();
+    for tri in indices.chunks_exact(3) {
+        for (a, b, opposite) in triangle_edges(tri) {
+            if a == b || edge_uses[&edge_key(a, b)] != 1 {
+                continue;
+            }
+            let [ax, ay] = vertices[a as usize].position;
+            let [bx, by] = vertices[b as usize].position;
+            let [ox, oy] = vertices[opposite as usize].position;
+            let length = (bx - ax).hypot(by - ay);
+            if length <= f32::EPSILON {
+                continue;
+            }
+            let mut normal = [(by - ay) / length, (ax - bx) / length];
+            // Point away from the triangle's third vertex.
+            if normal[0] * (ox - ax) + normal[1] * (oy - ay) > 0.0 {
+                normal = [-normal[0], -normal[1]];
+            }
+            for end in [a, b] {
+                let (sum, count) = normal_sums.entry(end).or_insert(([0.0, 0.0], 0.0));
+                sum[0] += normal[0];
+                sum[1] += normal[1];
+                *count += 1.0;
+            }
+            outline.push((a, b));
+        }
+    }
+
+    // For two edges meeting at a corner, the mean of their unit normals
+    // `m` points along the miter, and `m / |m|²` is the miter itself.
+    let miters: HashMap<u32, [f32; 2]> = normal_sums
+        .into_iter()
+        .map(|(vertex, (sum, count))| {
+            let mean = [sum[0] / count, sum[1] / count];
+            let length = mean[0].hypot(mean[1]);
+            let miter = if length <= f32::EPSILON {
+                [0.0, 0.0]
+            } else {
+                let scale = (1.0 / length).min(MAX_FRINGE_MITER) / length;
+                [mean[0] * scale, mean[1] * scale]
+            };
+            (vertex, miter)
+        })
+        .collect();
+
+    for (vertex, &canonical) in vertices.iter_mut().zip(&canonical) {
+        if let Some(&miter) = miters.get(&canonical) {
+            vertex.normal = miter;
+        }
+    }
+
+    // One zero-coverage copy of each outline vertex.
+    let mut fringe_vertex: HashMap<u32, u32> = HashMap::new();
+    let mut fringe = Vec::with_capacity(outline.len() * 6);
+    for (a, b) in outline {
+        let [outer_a, outer_b] = [a, b].map(|inner| {
+            *fringe_vertex.entry(inner).or_insert_with(|| {
+                let outer = Vertex {
+                    coverage: 0.0,
+                    ..vertices[inner as usize]
+                };
+                vertices.push(outer);
+                u32::try_from(vertices.len() - 1).expect("vertex count exceeds u32::MAX")
+            })
+        });
+        fringe.extend_from_slice(&[a, b, outer_b, a, outer_b, outer_a]);
+    }
+    fringe
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+pub(crate) mod tests {
+    use super::*;
+    use livesplit_core::rendering::SharedOwnership;
+
+    /// Helper to compare `[f32; 2]` with tolerance.
+    pub(crate) fn assert_bounds_eq(actual: [f32; 2], expected: [f32; 2]) {
+        assert!(
+            (actual[0] - expected[0]).abs() < f32::EPSILON
+                && (actual[1] - expected[1]).abs() < f32::EPSILON,
+            "expected {expected:?}, got {actual:?}",
+        );
+    }
+
+    #[test]
+    fn vertex_bounds_y_known_values() {
+        let vertices = [
+            Vertex::new([0.0, 1.0]),
+            Vertex::new([1.0, 3.0]),
+            Vertex::new([2.0, 2.0]),
+        ];
+        assert_bounds_eq(vertex_bounds(&vertices, 1), [1.0, 3.0]);
+    }
+
+    #[test]
+    fn vertex_bounds_x_known_values() {
+        let vertices = [
+            Vertex::new([5.0, 0.0]),
+            Vertex::new([2.0, 0.0]),
+            Vertex::new([8.0, 0.0]),
         ];
         assert_bounds_eq(vertex_bounds(&vertices, 0), [2.0, 8.0]);
     }
 
     #[test]
-    fn vertex_bounds_empty_returns_zeros() {
-        let empty: &[Vertex] = &[];
-        assert_bounds_eq(vertex_bounds(empty, 0), [0.0, 0.0]);
-        assert_bounds_eq(vertex_bounds(empty, 1), [0.0, 0.0]);
+    fn vertex_bounds_empty_returns_zeros() {
+        let empty: &[Vertex] = &[];
+        assert_bounds_eq(vertex_bounds(empty, 0), [0.0, 0.0]);
+        assert_bounds_eq(vertex_bounds(empty, 1), [0.0, 0.0]);
+    }
+
+    #[test]
+    fn vertex_bounds_single_vertex() {
+        let vertices = [Vertex::new([3.0, 7.0])];
+        let [min, max] = vertex_bounds(&vertices, 0);
+        assert!((min - max).abs() < f32::EPSILON);
+        assert!((min - 3.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn vertex_bounds_negative_coordinates() {
+        let vertices = [Vertex::new([-5.0, -10.0]), Vertex::new([5.0, 10.0])];
+        assert_bounds_eq(vertex_bounds(&vertices, 0), [-5.0, 5.0]);
+        assert_bounds_eq(vertex_bounds(&vertices, 1), [-10.0, 10.0]);
+    }
+
+    #[test]
+    fn vertex_bounds_identical_coordinates() {
+        let vertices = [Vertex::new([5.0, 5.0]), Vertex::new([5.0, 5.0])];
+        assert_bounds_eq(vertex_bounds(&vertices, 0), [5.0, 5.0]);
+        assert_bounds_eq(vertex_bounds(&vertices, 1), [5.0, 5.0]);
+    }
+
+    #[test]
+    fn viewport_from_size_is_at_origin() {
+        let viewport = Viewport::from([800, 600]);
+        assert_eq!(viewport, Viewport::new(0, 0, 800, 600));
+        assert_eq!(viewport.size(), [800, 600]);
+        assert!(viewport.covers([800, 600]));
+    }
+
+    #[test]
+    fn viewport_with_offset_does_not_cover_target() {
+        let viewport = Viewport::new(10, 20, 800, 600);
+        assert!(!viewport.covers([800, 600]));
+        assert!(!viewport.covers([810, 620]));
+    }
+
+    #[test]
+    fn viewport_empty() {
+        assert!(Viewport::new(5, 5, 0, 10).is_empty());
+        assert!(Viewport::new(5, 5, 10, 0).is_empty());
+        assert!(!Viewport::new(5, 5, 1, 1).is_empty());
+    }
+
+    fn assert_point_eq(actual: [f32; 2], expected: [f32; 2]) {
+        assert!(
+            (actual[0] - expected[0]).abs() < 1e-4 && (actual[1] - expected[1]).abs() < 1e-4,
+            "expected {expected:?}, got {actual:?}"
+        );
+    }
+
+    #[test]
+    fn output_transform_default_is_identity() {
+        let transform = OutputTransform::default();
+        assert!(transform.is_identity());
+        assert_point_eq(transform.apply([12.0, 34.0]), [12.0, 34.0]);
+    }
+
+    #[test]
+    fn output_transform_then_applies_in_order() {
+        // Scale first, then translate: the translation is not scaled.
+        let transform =
+            OutputTransform::scale(2.0, 3.0).then(&OutputTransform::translation(10.0, 20.0));
+        assert_point_eq(transform.apply([1.0, 1.0]), [12.0, 23.0]);
+
+        // Translate first, then scale: the translation is scaled.
+        let transform =
+            OutputTransform::translation(10.0, 20.0).then(&OutputTransform::scale(2.0, 3.0));
+        assert_point_eq(transform.apply([1.0, 1.0]), [22.0, 63.0]);
+    }
+
+    #[test]
+    fn output_transform_quarter_turn_fills_rotated_viewport() {
+        // A 300x100 layout turned onto a 100x300 viewport.
+        let transform = OutputTransform::quarter_turn_clockwise([100.0, 300.0]);
+        assert!(transform.swaps_axes());
+        assert_eq!(transform.layout_size([100, 300]), [300, 100]);
+        assert_point_eq(transform.apply([0.0, 0.0]), [100.0, 0.0]);
+        assert_point_eq(transform.apply([300.0, 100.0]), [0.0, 300.0]);
+
+        let transform = OutputTransform::quarter_turn_counterclockwise([100.0, 300.0]);
+        assert!(transform.swaps_axes());
+        assert_point_eq(transform.apply([0.0, 0.0]), [0.0, 300.0]);
+        assert_point_eq(transform.apply([300.0, 100.0]), [100.0, 0.0]);
+    }
+
+    #[test]
+    fn output_transform_rotation_matches_quarter_turn() {
+        let rotated = OutputTransform::rotation(std::f32::consts::FRAC_PI_2)
+            .then(&OutputTransform::translation(100.0, 0.0));
+        let turned = OutputTransform::quarter_turn_clockwise([100.0, 300.0]);
+        for point in [[0.0, 0.0], [300.0, 0.0], [120.0, 80.0]] {
+            assert_point_eq(rotated.apply(point), turned.apply(point));
+        }
+    }
+
+    #[test]
+    fn output_transform_about_keeps_center_fixed() {
+        let transform = OutputTransform::rotation(0.3).about([50.0, 40.0]);
+        assert_point_eq(transform.apply([50.0, 40.0]), [50.0, 40.0]);
+        assert!(!transform.swaps_axes());
+        assert_eq!(transform.layout_size([100, 80]), [100, 80]);
+    }
+
+    #[test]
+    fn output_transform_flip_mirrors_within_viewport() {
+        let transform = OutputTransform::flip_horizontal(200.0);
+        assert_point_eq(transform.apply([0.0, 5.0]), [200.0, 5.0]);
+        assert_point_eq(transform.apply([200.0, 5.0]), [0.0, 5.0]);
+
+        let transform = OutputTransform::flip_vertical(50.0);
+        assert_point_eq(transform.apply([5.0, 10.0]), [5.0, 40.0]);
+    }
+
+    #[test]
+    fn layout_resolution_is_logical() {
+        let resolution = layout_resolution([1600, 1200], &OutputTransform::IDENTITY, 2.0);
+        assert_point_eq(resolution, [800.0, 600.0]);
+
+        // A quarter turn swaps the logical size too.
+        let turn = OutputTransform::quarter_turn_clockwise([1200.0, 1600.0]);
+        assert_point_eq(
+            layout_resolution([1200, 1600], &turn, 1.25),
+            [1280.0, 960.0],
+        );
+    }
+
+    #[test]
+    fn layout_transform_scales_before_output_transform() {
+        let transform = layout_transform(&OutputTransform::translation(10.0, 0.0), 2.0);
+        // Logical (5, 5) -> physical (10, 10) -> translated (20, 10).
+        assert_point_eq(transform.apply([5.0, 5.0]), [20.0, 10.0]);
+    }
+
+    #[test]
+    fn pixel_bounds_follow_entity_and_output_transforms() {
+        let path = tessellate_path(&unit_square()).unwrap();
+        let transform = Transform {
+            x: 10.0,
+            y: 20.0,
+            scale_x: 4.0,
+            scale_y: 2.0,
+        };
+        let [min, max] =
+            pixel_bounds(&path, &transform, &OutputTransform::scale(2.0, 2.0)).unwrap();
+        assert_point_eq(min, [20.0, 40.0]);
+        assert_point_eq(max, [28.0, 44.0]);
+
+        // Flipped, the corners swap but the bounds stay ordered.
+        let flip = OutputTransform::flip_horizontal(100.0);
+        let [min, max] = pixel_bounds(&path, &transform, &flip).unwrap();
+        assert_point_eq(min, [86.0, 20.0]);
+        assert_point_eq(max, [90.0, 22.0]);
+    }
+
+    #[test]
+    fn shadow_region_grows_and_clips_to_target() {
+        let bounds = [[10.2, 20.5], [30.0, 25.5]];
+        assert_eq!(
+            shadow_region(bounds, 4.0, [100, 100]),
+            Some([6, 16, 28, 14])
+        );
+        // Clipped at the target's edges.
+        assert_eq!(shadow_region(bounds, 15.0, [40, 100]), Some([0, 5, 40, 36]));
+        // Entirely off-screen.
+        assert_eq!(
+            shadow_region([[-50.0, 0.0], [-20.0, 10.0]], 4.0, [100, 100]),
+            None
+        );
+    }
+
+    #[test]
+    fn shadow_blur_is_limited() {
+        let shadow = TextShadow {
+            blur_radius: 4.0,
+            ..TextShadow::default()
+        };
+        assert!((shadow.blur_sigma(1.5) - 3.0).abs() < f32::EPSILON);
+        assert_eq!(shadow_blur_taps(shadow.blur_sigma(1.5)), 9);
+        assert!(TextShadow::default().blur_sigma(2.0).abs() < f32::EPSILON);
+
+        let huge = TextShadow {
+            blur_radius: 1000.0,
+            ..TextShadow::default()
+        };
+        assert_eq!(shadow_blur_taps(huge.blur_sigma(1.0)), MAX_SHADOW_BLUR_TAPS);
+    }
+
+    #[test]
+    fn text_outline_stroke_shows_its_width_outside_the_glyph() {
+        let outline = TextOutline {
+            width: 0.25,
+            color: [0.0, 0.0, 0.0, 1.0],
+        };
+        // A glyph scaled by 0.5: a quarter of the font size is half a glyph
+        // unit, and only half of the stroke shows.
+        assert!((outline.stroke_width(0.5) - 1.0).abs() < f32::EPSILON);
     }
 
     #[test]
-    fn vertex_bounds_single_vertex() {
-        let vertices = [Vertex {
-            position: [3.0, 7.0],
-        }];
-        let [min, max] = vertex_bounds(&vertices, 0);
-        assert!((min - max).abs() < f32::EPSILON);
-        assert!((min - 3.0).abs() < f32::EPSILON);
+    fn antialiasing_target_size() {
+        const MAX: u32 = 8192;
+        assert_eq!(Antialiasing::Msaa.target_size([800, 600], MAX), [800, 600]);
+        let ssaa = Antialiasing::Supersample {
+            factor: 3,
+            filter: DownsampleFilter::Box,
+        };
+        assert_eq!(ssaa.target_size([800, 600], MAX), [2400, 1800]);
+        let degenerate = Antialiasing::Supersample {
+            factor: 0,
+            filter: DownsampleFilter::Lanczos,
+        };
+        assert_eq!(degenerate.supersample_factor(), 1);
+        assert_eq!(degenerate.target_size([800, 600], MAX), [800, 600]);
+        assert_eq!(
+            Antialiasing::Analytic.target_size([800, 600], MAX),
+            [800, 600]
+        );
     }
 
     #[test]
-    fn vertex_bounds_negative_coordinates() {
-        let vertices = [
-            Vertex {
-                position: [-5.0, -10.0],
-            },
-            Vertex {
-                position: [5.0, 10.0],
-            },
-        ];
-        assert_bounds_eq(vertex_bounds(&vertices, 0), [-5.0, 5.0]);
-        assert_bounds_eq(vertex_bounds(&vertices, 1), [-10.0, 10.0]);
+    fn supersampled_targets_fit_the_device() {
+        let huge = Antialiasing::Supersample {
+            factor: u32::MAX,
+            filter: DownsampleFilter::Box,
+        };
+        // The largest factor whose target still fits: 8192 / 1920 = 4.
+        assert_eq!(huge.fitted_supersample_factor([1920, 1080], 8192), 4);
+        assert_eq!(huge.target_size([1920, 1080], 8192), [7680, 4320]);
+        // A viewport that already fills the device is not supersampled.
+        assert_eq!(huge.target_size([8192, 100], 8192), [8192, 100]);
     }
 
     #[test]
-    fn vertex_bounds_identical_coordinates() {
-        let vertices = [
-            Vertex {
-                position: [5.0, 5.0],
-            },
-            Vertex {
-                position: [5.0, 5.0],
-            },
-        ];
-        assert_bounds_eq(vertex_bounds(&vertices, 0), [5.0, 5.0]);
-        assert_bounds_eq(vertex_bounds(&vertices, 1), [5.0, 5.0]);
+    fn vertex_layout_matches_attribute_offsets() {
+        // The GL vertex attributes and wgpu vertex buffer layout hard-code
+        // these.
+        assert_eq!(std::mem::size_of::<Vertex>(), 20);
+        assert_eq!(std::mem::offset_of!(Vertex, normal), 8);
+        assert_eq!(std::mem::offset_of!(Vertex, coverage), 16);
+    }
+
+    #[test]
+    fn only_analytic_antialiasing_has_a_fringe() {
+        assert!(Antialiasing::Analytic.fringe_width() > 0.0);
+        assert!(Antialiasing::Msaa.fringe_width().abs() < f32::EPSILON);
+        let ssaa = Antialiasing::Supersample {
+            factor: 2,
+            filter: DownsampleFilter::Box,
+        };
+        assert!(ssaa.fringe_width().abs() < f32::EPSILON);
     }
 
     #[test]
@@ -419,15 +3103,9 @@ pub(crate) mod tests {
 
         let path = Path::new(
             vec![
-                Vertex {
-                    position: [0.0, 0.0],
-                },
-                Vertex {
-                    position: [1.0, 0.0],
-                },
-                Vertex {
-                    position: [0.5, 1.0],
-                },
+                Vertex::new([0.0, 0.0]),
+                Vertex::new([1.0, 0.0]),
+                Vertex::new([0.5, 1.0]),
             ],
             vec![0, 1, 2],
             Arc::new(lyon_path),
@@ -487,6 +3165,125 @@ pub(crate) mod tests {
         );
     }
 
+    // --- coverage fringe tests ---
+
+    fn unit_square() -> LyonPath {
+        use lyon::math::point;
+        let mut builder = LyonPath::builder();
+        builder.begin(point(0.0, 0.0));
+        builder.line_to(point(1.0, 0.0));
+        builder.line_to(point(1.0, 1.0));
+        builder.line_to(point(0.0, 1.0));
+        builder.close();
+        builder.build()
+    }
+
+    #[test]
+    fn fringe_surrounds_rectangle_outline_only() {
+        let path = tessellate_path(&unit_square()).unwrap();
+        let fringed = path.fringed();
+
+        // Four outline edges, one quad each; the diagonal shared by the two
+        // fill triangles gets none.
+        assert_eq!(fringed.indices.len(), path.indices.len() + 4 * 6);
+        assert_eq!(&fringed.indices[..path.indices.len()], &path.indices[..]);
+        let fringe_vertices = fringed.vertices.iter().filter(|v| v.coverage < 1.0).count();
+        assert_eq!(fringe_vertices, 4);
+        assert!(path
+            .indices
+            .iter()
+            .all(|&i| fringed.vertices[i as usize].coverage > 0.0));
+    }
+
+    #[test]
+    fn fringe_is_built_only_when_drawn() {
+        let path = tessellate_path(&unit_square()).unwrap();
+        let (vertices, indices) = path.draw_geometry(false, false);
+        assert!(std::ptr::eq(vertices, path.vertices.as_slice()));
+        assert!(std::ptr::eq(indices, path.indices.as_slice()));
+        assert!(path.fringe.get().is_none());
+
+        // Dilated draws move the shape's edges without drawing the fringe.
+        let (vertices, indices) = path.draw_geometry(false, true);
+        assert_eq!(vertices.len(), path.vertices.len() + 4);
+        assert_eq!(indices, &path.indices[..]);
+        let clone = path.clone();
+        let (_, with_fringe) = clone.draw_geometry(true, false);
+        assert_eq!(with_fringe.len(), path.indices.len() + 4 * 6);
+        assert!(std::ptr::eq(path.fringed(), clone.fringed()));
+    }
+
+    #[test]
+    fn fringe_normals_are_outward_miters() {
+        let path = tessellate_path(&unit_square()).unwrap();
+
+        for vertex in &path.fringed().vertices {
+            let [x, y] = vertex.position;
+            // Square corners: a miter of length sqrt(2) pointing away from
+            // the center.
+            let expected = [(x - 0.5) * 2.0, (y - 0.5) * 2.0];
+            assert_point_eq(vertex.normal, expected);
+        }
+    }
+
+    #[test]
+    fn fringe_merges_duplicate_positions() {
+        // Two triangles forming a square, with the shared diagonal's
+        // vertices duplicated instead of indexed twice.
+        let mut vertices = vec![
+            Vertex::new([0.0, 0.0]),
+            Vertex::new([1.0, 0.0]),
+            Vertex::new([1.0, 1.0]),
+            Vertex::new([0.0, 0.0]),
+            Vertex::new([1.0, 1.0]),
+            Vertex::new([0.0, 1.0]),
+        ];
+        let fringe = add_coverage_fringe(&mut vertices, &[0, 1, 2, 3, 4, 5]);
+
+        assert_eq!(fringe.len(), 4 * 6);
+        assert_eq!(vertices.len(), 6 + 4);
+        assert_point_eq(vertices[3].normal, vertices[0].normal);
+    }
+
+    #[test]
+    fn fringe_miter_is_clamped() {
+        // A very thin sliver: its sharp tip would otherwise extrude far.
+        let mut vertices = vec![
+            Vertex::new([0.0, 0.0]),
+            Vertex::new([100.0, 1.0]),
+            Vertex::new([100.0, -1.0]),
+        ];
+        let _ = add_coverage_fringe(&mut vertices, &[0, 1, 2]);
+
+        let [nx, ny] = vertices[0].normal;
+        assert!(nx < 0.0);
+        assert!((nx.hypot(ny) - MAX_FRINGE_MITER).abs() < 1e-4);
+    }
+
+    #[test]
+    fn stroke_has_fringe_and_caches_it() {
+        let path = tessellate_path(&unit_square()).unwrap();
+        let first = tessellate_stroke(
+            &path,
+            0.1,
+            DEFAULT_TOLERANCE,
+            &StrokeStyle::default(),
+            &mut FrameStats::default(),
+        )
+        .unwrap();
+        assert!(first.fringed().indices.len() > first.indices.len());
+
+        let second = tessellate_stroke(
+            &path,
+            0.1,
+            DEFAULT_TOLERANCE,
+            &StrokeStyle::default(),
+            &mut FrameStats::default(),
+        )
+        .unwrap();
+        assert!(std::ptr::eq(first.fringed(), second.fringed()));
+    }
+
     // --- CommonPathBuilder tests ---
 
     #[test]
@@ -562,7 +3359,13 @@ pub(crate) mod tests {
         pb.close();
         let path = pb.finish().unwrap();
 
-        let stroked = tessellate_stroke(&path, 0.1);
+        let stroked = tessellate_stroke(
+            &path,
+            0.1,
+            DEFAULT_TOLERANCE,
+            &StrokeStyle::default(),
+            &mut FrameStats::default(),
+        );
         assert!(
             stroked.is_some(),
             "rectangle stroke should produce geometry"
@@ -585,7 +3388,13 @@ pub(crate) mod tests {
         let fill = tessellate_path(&lyon_path);
         let path = fill.unwrap_or_else(|| Path::new(vec![], vec![], Arc::new(lyon_path)));
 
-        let stroked = tessellate_stroke(&path, 0.1);
+        let stroked = tessellate_stroke(
+            &path,
+            0.1,
+            DEFAULT_TOLERANCE,
+            &StrokeStyle::default(),
+            &mut FrameStats::default(),
+        );
         assert!(
             stroked.is_some(),
             "open line stroke should produce geometry"
@@ -603,21 +3412,34 @@ pub(crate) mod tests {
         let path = pb.finish().unwrap();
 
         // First call populates the cache.
-        let first = tessellate_stroke(&path, 0.1);
+        let first = tessellate_stroke(
+            &path,
+            0.1,
+            DEFAULT_TOLERANCE,
+            &StrokeStyle::default(),
+            &mut FrameStats::default(),
+        );
         assert!(first.is_some());
 
         // Cache should now be populated.
-        let cached = path.cached_stroke(0.1);
+        let cached = path.cached_stroke(0.1, DEFAULT_TOLERANCE, &StrokeStyle::default());
         assert!(
             cached.is_some(),
             "cache should be populated after first stroke"
         );
 
         // Second call should return geometry from cache with same Arc pointers.
-        let second = tessellate_stroke(&path, 0.1).unwrap();
-        let (cached_verts, cached_idxs) = cached.unwrap();
-        assert!(Arc::ptr_eq(&second.vertices, &cached_verts));
-        assert!(Arc::ptr_eq(&second.indices, &cached_idxs));
+        let second = tessellate_stroke(
+            &path,
+            0.1,
+            DEFAULT_TOLERANCE,
+            &StrokeStyle::default(),
+            &mut FrameStats::default(),
+        )
+        .unwrap();
+        let cached = cached.unwrap();
+        assert!(Arc::ptr_eq(&second.vertices, &cached.vertices));
+        assert!(Arc::ptr_eq(&second.indices, &cached.indices));
     }
 
     #[test]
@@ -630,15 +3452,21 @@ pub(crate) mod tests {
         pb.close();
         let path = pb.finish().unwrap();
 
-        let _ = tessellate_stroke(&path, 0.1);
+        let _ = tessellate_stroke(
+            &path,
+            0.1,
+            DEFAULT_TOLERANCE,
+            &StrokeStyle::default(),
+            &mut FrameStats::default(),
+        );
 
         // Different width should miss the cache.
-        let cached = path.cached_stroke(0.2);
+        let cached = path.cached_stroke(0.2, DEFAULT_TOLERANCE, &StrokeStyle::default());
         assert!(cached.is_none(), "cache should miss for different width");
     }
 
     #[test]
-    fn tessellate_stroke_different_width_evicts_cache() {
+    fn tessellate_stroke_keeps_several_widths() {
         use livesplit_core::rendering::PathBuilder;
         let mut pb = CommonPathBuilder::new();
         pb.move_to(0.0, 0.0);
@@ -647,22 +3475,305 @@ pub(crate) mod tests {
         pb.close();
         let path = pb.finish().unwrap();
 
-        // Populate cache with width 0.1
-        let first = tessellate_stroke(&path, 0.1).unwrap();
+        let first = tessellate_stroke(
+            &path,
+            0.1,
+            DEFAULT_TOLERANCE,
+            &StrokeStyle::default(),
+            &mut FrameStats::default(),
+        )
+        .unwrap();
+        let second = tessellate_stroke(
+            &path,
+            0.2,
+            DEFAULT_TOLERANCE,
+            &StrokeStyle::default(),
+            &mut FrameStats::default(),
+        )
+        .unwrap();
+        assert!(!Arc::ptr_eq(&first.vertices, &second.vertices));
 
-        // Tessellate with different width — should evict old cache entry.
-        let second = tessellate_stroke(&path, 0.2).unwrap();
+        // Both widths stay cached.
+        let again = tessellate_stroke(
+            &path,
+            0.1,
+            DEFAULT_TOLERANCE,
+            &StrokeStyle::default(),
+            &mut FrameStats::default(),
+        )
+        .unwrap();
+        assert!(Arc::ptr_eq(&first.vertices, &again.vertices));
+        let again = tessellate_stroke(
+            &path,
+            0.2,
+            DEFAULT_TOLERANCE,
+            &StrokeStyle::default(),
+            &mut FrameStats::default(),
+        )
+        .unwrap();
+        assert!(Arc::ptr_eq(&second.vertices, &again.vertices));
+    }
 
-        // The new cache should be for width 0.2, not 0.1.
-        assert!(path.cached_stroke(0.2).is_some());
-        assert!(path.cached_stroke(0.1).is_none());
+    #[test]
+    fn stroke_cache_evicts_least_recently_used() {
+        let path = tessellate_path(&unit_square()).unwrap();
+        #[expect(clippy::cast_precision_loss)]
+        let width = |i: usize| 0.1 * (i + 1) as f32;
 
-        // Arcs should differ since they're from different tessellations.
-        assert!(!Arc::ptr_eq(&first.vertices, &second.vertices));
+        let oldest = tessellate_stroke(
+            &path,
+            width(0),
+            DEFAULT_TOLERANCE,
+            &StrokeStyle::default(),
+            &mut FrameStats::default(),
+        )
+        .unwrap();
+        let used = tessellate_stroke(
+            &path,
+            width(1),
+            DEFAULT_TOLERANCE,
+            &StrokeStyle::default(),
+            &mut FrameStats::default(),
+        )
+        .unwrap();
+        for i in 2..MAX_STROKE_ENTRIES {
+            let _ = tessellate_stroke(
+                &path,
+                width(i),
+                DEFAULT_TOLERANCE,
+                &StrokeStyle::default(),
+                &mut FrameStats::default(),
+            );
+        }
+        // Touch the second entry, then overflow the cache by one.
+        assert!(path
+            .cached_stroke(width(1), DEFAULT_TOLERANCE, &StrokeStyle::default())
+            .is_some());
+        let _ = tessellate_stroke(
+            &path,
+            width(MAX_STROKE_ENTRIES),
+            DEFAULT_TOLERANCE,
+            &StrokeStyle::default(),
+            &mut FrameStats::default(),
+        );
+
+        assert!(path
+            .cached_stroke(width(0), DEFAULT_TOLERANCE, &StrokeStyle::default())
+            .is_none());
+        let kept = path
+            .cached_stroke(width(1), DEFAULT_TOLERANCE, &StrokeStyle::default())
+            .unwrap();
+        assert!(Arc::ptr_eq(&used.vertices, &kept.vertices));
+        assert!(!Arc::ptr_eq(&oldest.vertices, &kept.vertices));
+    }
+
+    #[test]
+    fn stroke_cache_is_shared_by_clones() {
+        let path = tessellate_path(&unit_square()).unwrap();
+        let stroke = tessellate_stroke(
+            &path,
+            0.1,
+            DEFAULT_TOLERANCE,
+            &StrokeStyle::default(),
+            &mut FrameStats::default(),
+        )
+        .unwrap();
+
+        let shared = path.share();
+        let cached = tessellate_stroke(
+            &shared,
+            0.1,
+            DEFAULT_TOLERANCE,
+            &StrokeStyle::default(),
+            &mut FrameStats::default(),
+        )
+        .unwrap();
+        assert!(Arc::ptr_eq(&stroke.vertices, &cached.vertices));
+
+        let circle = tessellate_path(&circle()).unwrap();
+        let level = fill_at_tolerance(&circle, 1.0);
+        let cached = fill_at_tolerance(&circle.clone(), 1.0);
+        assert!(Arc::ptr_eq(&level.vertices, &cached.vertices));
+    }
+
+    #[test]
+    fn stroke_cache_counts_hits_and_misses() {
+        let path = tessellate_path(&unit_square()).unwrap();
+        let style = StrokeStyle::default();
+        let mut stats = FrameStats::default();
+
+        let _ = tessellate_stroke(&path, 0.1, DEFAULT_TOLERANCE, &style, &mut stats);
+        let _ = tessellate_stroke(&path, 0.1, DEFAULT_TOLERANCE, &style, &mut stats);
+        let _ = tessellate_stroke(&path.clone(), 0.1, DEFAULT_TOLERANCE, &style, &mut stats);
+        assert_eq!(
+            stats,
+            FrameStats {
+                stroke_cache_hits: 2,
+                stroke_cache_misses: 1,
+                ..FrameStats::default()
+            }
+        );
+    }
+
+    #[test]
+    fn non_finite_stroke_widths_are_not_quantized() {
+        for width in [f32::from_bits(0xFFFF_F000), f32::NAN, f32::INFINITY] {
+            assert_eq!(quantize_stroke_width(width).to_bits(), width.to_bits());
+        }
+    }
+
+    #[test]
+    fn huge_stroke_widths_saturate() {
+        assert_eq!(
+            quantize_stroke_width(f32::MAX).to_bits(),
+            f32::MAX.to_bits()
+        );
+        assert_eq!(
+            quantize_stroke_width(-f32::MAX).to_bits(),
+            (-f32::MAX).to_bits()
+        );
+    }
+
+    /// A circle of radius 10 made of quadratic curves.
+    fn circle() -> LyonPath {
+        use lyon::math::point;
+        let mut builder = LyonPath::builder();
+        builder.begin(point(10.0, 0.0));
+        builder.quadratic_bezier_to(point(10.0, 10.0), point(0.0, 10.0));
+        builder.quadratic_bezier_to(point(-10.0, 10.0), point(-10.0, 0.0));
+        builder.quadratic_bezier_to(point(-10.0, -10.0), point(0.0, -10.0));
+        builder.quadratic_bezier_to(point(10.0, -10.0), point(10.0, 0.0));
+        builder.close();
+        builder.build()
+    }
+
+    #[test]
+    fn curve_tolerance_follows_screen_scale() {
+        let transform = Transform {
+            x: 0.0,
+            y: 0.0,
+            scale_x: 100.0,
+            scale_y: 50.0,
+        };
+        let tolerance = curve_tolerance(&transform, &OutputTransform::IDENTITY);
+        // Within a tenth of a pixel, but no more than twice as fine.
+        assert!(tolerance * 100.0 <= SCREEN_TOLERANCE);
+        assert!(tolerance * 100.0 > 0.5 * SCREEN_TOLERANCE);
+
+        // Twice the output scale halves the tolerance, exactly.
+        let doubled = curve_tolerance(&transform, &OutputTransform::scale(2.0, 2.0));
+        assert_eq!(doubled.to_bits(), (0.5 * tolerance).to_bits());
+
+        // A degenerate transform keeps the tolerance paths are created with.
+        let flat = Transform {
+            scale_y: 0.0,
+            scale_x: 0.0,
+            ..transform
+        };
+        assert_eq!(
+            curve_tolerance(&flat, &OutputTransform::IDENTITY).to_bits(),
+            DEFAULT_TOLERANCE.to_bits()
+        );
+    }
+
+    #[test]
+    fn fill_at_tolerance_caches_levels() {
+        let path = tessellate_path(&circle()).unwrap();
+
+        let coarse = fill_at_tolerance(&path, 1.0);
+        let fine = fill_at_tolerance(&path, 1.0 / 1024.0);
+        assert!(coarse.vertices.len() < fine.vertices.len());
+
+        let again = fill_at_tolerance(&path, 1.0);
+        assert!(Arc::ptr_eq(&coarse.vertices, &again.vertices));
+        assert!(std::ptr::eq(coarse.fringed(), again.fringed()));
+
+        // Only the most recent levels are kept.
+        for exponent in 1..=MAX_FILL_LEVELS {
+            #[expect(clippy::cast_precision_loss)]
+            let _ = fill_at_tolerance(&path, (exponent as f32).exp2());
+        }
+        let evicted = fill_at_tolerance(&path, 1.0);
+        assert!(!Arc::ptr_eq(&coarse.vertices, &evicted.vertices));
+    }
+
+    #[test]
+    fn bounds_are_computed_at_creation() {
+        let path = tessellate_path(&circle()).unwrap();
+        let [min, max] = path.bounds;
+        for axis in 0..2 {
+            assert_bounds_eq([min[axis], max[axis]], vertex_bounds(&path.vertices, axis));
+        }
+        assert_bounds_eq([min[0], max[0]], [-10.0, 10.0]);
+
+        // Cached tessellations carry their own bounds.
+        let stroke = tessellate_stroke(
+            &path,
+            2.0,
+            DEFAULT_TOLERANCE,
+            &StrokeStyle::default(),
+            &mut FrameStats::default(),
+        )
+        .unwrap();
+        assert!(stroke.bounds[1][0] > 10.5);
+        let cached = path
+            .cached_stroke(2.0, DEFAULT_TOLERANCE, &StrokeStyle::default())
+            .unwrap();
+        assert_eq!(cached.bounds, stroke.bounds);
+        let fill = fill_at_tolerance(&path, 1.0);
+        assert_eq!(fill_at_tolerance(&path, 1.0).bounds, fill.bounds);
+
+        let empty = Path::new(vec![], vec![], Arc::new(circle()));
+        assert_eq!(empty.bounds, [[0.0; 2]; 2]);
+    }
+
+    #[test]
+    fn fill_without_curves_reuses_creation_tessellation() {
+        let path = tessellate_path(&unit_square()).unwrap();
+        let fill = fill_at_tolerance(&path, 1.0 / 1024.0);
+        assert!(Arc::ptr_eq(&path.vertices, &fill.vertices));
+        assert!(Arc::ptr_eq(&path.indices, &fill.indices));
+        // Nothing was tessellated, so no level is cached.
+        assert!(path.fill_levels.read().unwrap().is_empty());
+    }
+
+    #[test]
+    fn stroke_cache_is_keyed_by_tolerance() {
+        let path = tessellate_path(&circle()).unwrap();
+        let coarse = tessellate_stroke(
+            &path,
+            1.0,
+            1.0,
+            &StrokeStyle::default(),
+            &mut FrameStats::default(),
+        )
+        .unwrap();
+        assert!(path
+            .cached_stroke(1.0, DEFAULT_TOLERANCE, &StrokeStyle::default())
+            .is_none());
+
+        let fine = tessellate_stroke(
+            &path,
+            1.0,
+            1.0 / 64.0,
+            &StrokeStyle::default(),
+            &mut FrameStats::default(),
+        )
+        .unwrap();
+        assert!(coarse.vertices.len() < fine.vertices.len());
+        let again = tessellate_stroke(
+            &path,
+            1.0,
+            1.0 / 64.0,
+            &StrokeStyle::default(),
+            &mut FrameStats::default(),
+        )
+        .unwrap();
+        assert!(Arc::ptr_eq(&fine.vertices, &again.vertices));
     }
 
     #[test]
-    fn stroke_cache_epsilon_boundary() {
+    fn stroke_cache_quantizes_widths() {
         use livesplit_core::rendering::PathBuilder;
         let mut pb = CommonPathBuilder::new();
         pb.move_to(0.0, 0.0);
@@ -671,11 +3782,614 @@ pub(crate) mod tests {
         pb.close();
         let path = pb.finish().unwrap();
 
-        let _ = tessellate_stroke(&path, 1.0);
+        let _ = tessellate_stroke(
+            &path,
+            1.0,
+            DEFAULT_TOLERANCE,
+            &StrokeStyle::default(),
+            &mut FrameStats::default(),
+        );
+
+        // Rounding noise shares the entry.
+        assert!(path
+            .cached_stroke(
+                1.0 + f32::EPSILON,
+                DEFAULT_TOLERANCE,
+                &StrokeStyle::default()
+            )
+            .is_some());
+        assert!(path
+            .cached_stroke(
+                1.0 - f32::EPSILON,
+                DEFAULT_TOLERANCE,
+                &StrokeStyle::default()
+            )
+            .is_some());
+        // A visibly different width does not.
+        assert!(path
+            .cached_stroke(1.01, DEFAULT_TOLERANCE, &StrokeStyle::default())
+            .is_none());
+        assert_eq!(quantize_stroke_width(1.0).to_bits(), 1.0f32.to_bits());
+    }
+
+    /// An open horizontal line from the origin to `(length, 0)`.
+    fn line(length: f32) -> LyonPath {
+        use lyon::math::point;
+        let mut builder = LyonPath::builder();
+        builder.begin(point(0.0, 0.0));
+        builder.line_to(point(length, 0.0));
+        builder.end(false);
+        builder.build()
+    }
+
+    /// Start and end x of each subpath of a horizontal path.
+    fn spans(path: &LyonPath) -> Vec<[f32; 2]> {
+        path.iter()
+            .filter_map(|event| match event {
+                PathEvent::End { last, first, .. } => Some([first.x, last.x]),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn dash_path_splits_lines_into_dashes() {
+        let dashed = dash_path(&line(10.0), &[2.0, 1.0], 0.0, DEFAULT_TOLERANCE).unwrap();
+        assert_eq!(
+            spans(&dashed),
+            [[0.0, 2.0], [3.0, 5.0], [6.0, 8.0], [9.0, 10.0]]
+        );
+
+        // The offset shifts the pattern back along the line.
+        let dashed = dash_path(&line(5.0), &[2.0, 1.0], 1.0, DEFAULT_TOLERANCE).unwrap();
+        assert_eq!(spans(&dashed), [[0.0, 1.0], [2.0, 4.0]]);
+
+        // A pattern too fine for the path is drawn solid.
+        assert!(dash_path(&line(1000.0), &[0.1, 0.1], 0.0, DEFAULT_TOLERANCE).is_none());
+    }
+
+    #[test]
+    fn dash_pattern_is_scaled_and_made_even() {
+        let style = StrokeStyle {
+            dashes: vec![1.0, 2.0, 3.0],
+            ..StrokeStyle::default()
+        };
+        assert_eq!(
+            style.dash_pattern(2.0).unwrap(),
+            [2.0, 4.0, 6.0, 2.0, 4.0, 6.0]
+        );
+
+        for dashes in [vec![], vec![0.0, 0.0], vec![1.0, -1.0]] {
+            let style = StrokeStyle {
+                dashes,
+                ..StrokeStyle::default()
+            };
+            assert!(style.dash_pattern(1.0).is_none());
+        }
+    }
+
+    #[test]
+    fn stroke_style_shapes_and_keys_the_stroke() {
+        let path = tessellate_path(&unit_square()).unwrap();
+        let miter = tessellate_stroke(
+            &path,
+            0.1,
+            DEFAULT_TOLERANCE,
+            &StrokeStyle::default(),
+            &mut FrameStats::default(),
+        )
+        .unwrap();
+        let round = StrokeStyle {
+            join: LineJoin::Round,
+            ..StrokeStyle::default()
+        };
+        assert!(path.cached_stroke(0.1, DEFAULT_TOLERANCE, &round).is_none());
+        let rounded = tessellate_stroke(
+            &path,
+            0.1,
+            DEFAULT_TOLERANCE,
+            &round,
+            &mut FrameStats::default(),
+        )
+        .unwrap();
+        assert!(rounded.vertices.len() > miter.vertices.len());
+
+        let dashed = StrokeStyle {
+            dashes: vec![1.0, 1.0],
+            ..StrokeStyle::default()
+        };
+        let dashed = tessellate_stroke(
+            &path,
+            0.1,
+            DEFAULT_TOLERANCE,
+            &dashed,
+            &mut FrameStats::default(),
+        )
+        .unwrap();
+        assert!(dashed.vertices.len() > miter.vertices.len());
+
+        // Each style keeps its own entry.
+        let again = tessellate_stroke(
+            &path,
+            0.1,
+            DEFAULT_TOLERANCE,
+            &StrokeStyle::default(),
+            &mut FrameStats::default(),
+        )
+        .unwrap();
+        assert!(Arc::ptr_eq(&miter.vertices, &again.vertices));
+    }
+
+    #[test]
+    fn culling_skips_entities_outside_the_viewport() {
+        use livesplit_core::rendering::{FillShader, Handle};
+        let style = StrokeStyle::default();
+        let culling = Culling::new(
+            [100.0, 100.0],
+            &OutputTransform::IDENTITY,
+            1.0,
+            &style,
+            &TextShadow::default(),
+            None,
+            TextRendering::Geometry,
+        );
+        let at = |x: f32| Transform {
+            x,
+            y: 10.0,
+            scale_x: 10.0,
+            scale_y: 10.0,
+        };
+        let fill = |x: f32| -> Entity<Option<Path>, (), Label> {
+            Entity::FillPath(
+                Handle::new(0, tessellate_path(&unit_square())),
+                FillShader::SolidColor([1.0; 4]),
+                at(x),
+            )
+        };
+        assert!(culling.is_visible(&fill(50.0)));
+        // Partly inside, and within the antialiasing margin.
+        assert!(culling.is_visible(&fill(95.0)));
+        assert!(culling.is_visible(&fill(101.0)));
+        assert!(!culling.is_visible(&fill(110.0)));
+        assert!(!culling.is_visible(&fill(-20.0)));
+
+        // Strokes reach beyond their path by the miter.
+        let stroke: Entity<Option<Path>, (), Label> = Entity::StrokePath(
+            Handle::new(0, tessellate_path(&unit_square())),
+            1.0,
+            [1.0; 4],
+            at(105.0),
+        );
+        assert!(culling.is_visible(&stroke));
+
+        let image = |x: f32| -> Entity<Option<Path>, (), Label> {
+            Entity::Image(Handle::new(0, ()), at(x))
+        };
+        assert!(culling.is_visible(&image(-5.0)));
+        assert!(!culling.is_visible(&image(-20.0)));
+    }
+
+    #[test]
+    fn stroke_extent_covers_miters_and_caps() {
+        let style = StrokeStyle::default();
+        assert!((style.max_extent(2.0) - 4.0).abs() < f32::EPSILON);
+        let round = StrokeStyle {
+            join: LineJoin::Round,
+            ..StrokeStyle::default()
+        };
+        assert!((round.max_extent(2.0) - 1.0).abs() < f32::EPSILON);
+        let square = StrokeStyle {
+            cap: LineCap::Square,
+            ..round
+        };
+        assert!((square.max_extent(2.0) - std::f32::consts::SQRT_2).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn frame_stats_count_drawn_and_culled() {
+        let mut stats = FrameStats::default();
+        stats.count(true);
+        stats.count(false);
+        stats.count(true);
+        assert_eq!(
+            stats,
+            FrameStats {
+                drawn_entities: 2,
+                culled_entities: 1,
+                ..FrameStats::default()
+            }
+        );
+    }
+
+    #[test]
+    fn dithering_is_one_step_of_the_output_format() {
+        let format = OutputFormat::Rgba8;
+        assert!(Dithering::default().amplitude(format).abs() < f32::EPSILON);
+        assert!((Dithering::Ordered.amplitude(format) * 255.0 - 1.0).abs() < f32::EPSILON);
+        let ten_bit = Dithering::Ordered.amplitude(OutputFormat::Rgb10A2);
+        assert!((ten_bit * 1023.0 - 1.0).abs() < f32::EPSILON);
+        assert!(
+            Dithering::Ordered
+                .amplitude(OutputFormat::Rgba16Float)
+                .abs()
+                < f32::EPSILON
+        );
+    }
+
+    #[test]
+    fn fade_eases_between_opacities() {
+        let fade = Fade {
+            from: 1.0,
+            to: 0.0,
+            duration: Duration::from_millis(400),
+        };
+        assert!((fade.opacity_at(Duration::ZERO) - 1.0).abs() < f32::EPSILON);
+        assert!((fade.opacity_at(Duration::from_millis(200)) - 0.5).abs() < 1e-6);
+        // Eased: slower than linear near the ends.
+        assert!(fade.opacity_at(Duration::from_millis(40)) > 0.9);
+        assert!(!fade.is_finished(Duration::from_millis(399)));
+        assert!(fade.opacity_at(Duration::from_secs(1)).abs() < f32::EPSILON);
+        assert!(fade.is_finished(Duration::from_millis(400)));
+
+        let instant = Fade {
+            from: 0.0,
+            to: 1.0,
+            duration: Duration::ZERO,
+        };
+        assert!((instant.opacity_at(Duration::ZERO) - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn layout_opacity_follows_its_fade_until_it_finishes() {
+        let start = Instant::now();
+        let mut opacity = LayoutOpacity::OPAQUE;
+        opacity.set(0.25);
+        opacity.fade_to(0.75, Duration::from_secs(1), start);
+        assert!(opacity.is_fading());
+        assert!((opacity.get() - 0.25).abs() < f32::EPSILON);
+
+        opacity.advance(start + Duration::from_millis(500));
+        assert!((opacity.get() - 0.5).abs() < 1e-6);
+        assert!(opacity.is_fading());
+
+        opacity.advance(start + Duration::from_secs(2));
+        assert!((opacity.get() - 0.75).abs() < f32::EPSILON);
+        assert!(!opacity.is_fading());
+
+        // A fade started during another one starts where that one is, even
+        // if no frame was drawn since.
+        opacity.fade_to(0.25, Duration::from_secs(1), start);
+        let restart = start + Duration::from_millis(500);
+        opacity.fade_to(1.0, Duration::from_secs(1), restart);
+        assert!((opacity.get() - 0.5).abs() < 1e-6);
+        opacity.advance(restart + Duration::from_millis(500));
+        assert!((opacity.get() - 0.75).abs() < 1e-6);
+
+        // Setting the opacity stops a fade.
+        opacity.fade_to(0.0, Duration::from_secs(1), start);
+        opacity.set(2.0);
+        assert!(!opacity.is_fading());
+        assert!((opacity.get() - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn hdr_colors_are_linear_and_scaled_to_the_sdr_white_level() {
+        let color = [1.0, 0.5, 0.0, 0.25];
+        let sdr = OutputFormat::Rgb10A2.color_encoding(203.0);
+        assert_eq!(sdr, ColorEncoding::Srgb);
+        assert!(!sdr.is_linear());
+
+        let hdr = OutputFormat::Rgba16Float.color_encoding(2.0 * SCRGB_WHITE_NITS);
+        assert!(hdr.is_linear());
+        assert!((hdr.brightness() - 2.0).abs() < f32::EPSILON);
+        let [r, g, b, a] = hdr.encode(&color);
+        assert!((r - 2.0).abs() < 1e-6);
+        assert!((g - 2.0 * srgb_channel_to_linear(0.5)).abs() < 1e-6);
+        assert!(b.abs() < f32::EPSILON);
+        assert!((a - 0.25).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn color_matrices_compose_and_keep_grays() {
+        let color = [0.8, 0.3, 0.1, 0.5];
+        let gray = ColorMatrix::GRAYSCALE.apply(color);
+        assert!(
+            (gray[0] - gray[1]).abs() < f32::EPSILON && (gray[1] - gray[2]).abs() < f32::EPSILON
+        );
+        assert!((gray[3] - 0.5).abs() < f32::EPSILON);
+
+        let twice = ColorMatrix::INVERT.then(&ColorMatrix::INVERT);
+        assert!(twice.is_identity());
+        let composed = ColorMatrix::INVERT.then(&ColorMatrix::HIGH_CONTRAST);
+        let stepwise = ColorMatrix::HIGH_CONTRAST.apply(ColorMatrix::INVERT.apply(color));
+        for (a, b) in composed.apply(color).iter().zip(stepwise) {
+            assert!((a - b).abs() < 1e-6);
+        }
+
+        let (columns, offset) = ColorMatrix::INVERT.columns_and_offset();
+        assert!((columns[0][0] + 1.0).abs() < f32::EPSILON);
+        assert!((columns[3][3] - 1.0).abs() < f32::EPSILON);
+        assert!((offset[2] - 1.0).abs() < f32::EPSILON);
+
+        for deficiency in [
+            ColorDeficiency::Protanopia,
+            ColorDeficiency::Deuteranopia,
+            ColorDeficiency::Tritanopia,
+        ] {
+            let matrix = ColorMatrix::daltonize(deficiency);
+            for level in [0.0, 0.5, 1.0] {
+                for channel in &matrix.apply([level, level, level, 1.0])[..3] {
+                    assert!((channel - level).abs() < 0.02, "{deficiency:?}: {channel}");
+                }
+            }
+        }
+        // Red and green become distinguishable by their blue channel.
+        let deuteranopia = ColorMatrix::daltonize(ColorDeficiency::Deuteranopia);
+        let red = deuteranopia.apply([1.0, 0.0, 0.0, 1.0]);
+        let green = deuteranopia.apply([0.0, 1.0, 0.0, 1.0]);
+        assert!(red[2] > green[2] + 0.2);
+    }
+
+    #[test]
+    fn output_masks_fit_the_viewport() {
+        assert!(OutputMask::NONE.is_none());
+        assert!(OutputMask::NONE
+            .with_border(2.0, [1.0, 1.0, 1.0, 0.0])
+            .is_none());
+        let mask = OutputMask::rounded(12.0).with_border(2.0, [1.0, 0.5, 0.0, 0.5]);
+        assert!(!mask.is_none());
+
+        assert_bounds_eq(mask.pixel_extents(2.0, [400.0, 300.0]), [24.0, 4.0]);
+        assert_bounds_eq(mask.pixel_extents(2.0, [400.0, 20.0]), [10.0, 4.0]);
+
+        let [r, g, b, a] = mask.border_color(ColorEncoding::Srgb);
+        assert!((a - 0.5).abs() < f32::EPSILON);
+        assert!((r - 0.5).abs() < f32::EPSILON && (g - 0.25).abs() < f32::EPSILON);
+        assert!(b.abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn chroma_keys_are_opaque_and_always_key_empty_pixels() {
+        assert_eq!(ChromaKey::default(), ChromaKey::GREEN);
+        let linear = ColorEncoding::Linear { brightness: 2.0 };
+        for (actual, expected) in [
+            (
+                ChromaKey::MAGENTA.encoded_color(linear),
+                [2.0, 0.0, 2.0, 1.0],
+            ),
+            (
+                ChromaKey::BLUE.encoded_color(ColorEncoding::Srgb),
+                [0.0, 0.0, 1.0, 1.0],
+            ),
+        ] {
+            assert!(actual
+                .iter()
+                .zip(expected)
+                .all(|(a, e)| (a - e).abs() < f32::EPSILON));
+        }
+
+        assert_eq!(ChromaKey::GREEN.clamped_threshold(), None);
+        assert!(ChromaKey::GREEN
+            .with_threshold(0.0)
+            .clamped_threshold()
+            .is_some_and(|threshold| threshold > 0.0));
+        assert_eq!(
+            ChromaKey::GREEN.with_threshold(2.0).clamped_threshold(),
+            Some(1.0)
+        );
+    }
+
+    fn display_settings<'a>(
+        layout_transform: &'a OutputTransform,
+        stroke_style: &'a StrokeStyle,
+        text_shadow: &'a TextShadow,
+        encoding: ColorEncoding,
+    ) -> DisplaySettings<'a> {
+        DisplaySettings {
+            resolution: [100.0, 100.0],
+            layout_resolution: [200.0, 100.0],
+            layout_transform,
+            scale_factor: 1.0,
+            stroke_style,
+            text_shadow,
+            text_outline: None,
+            text_rendering: TextRendering::Geometry,
+            encoding,
+        }
+    }
+
+    fn assert_color_eq(actual: [f32; 4], expected: [f32; 4]) {
+        assert!(
+            actual
+                .iter()
+                .zip(expected)
+                .all(|(a, e)| (a - e).abs() < f32::EPSILON),
+            "expected {expected:?}, got {actual:?}",
+        );
+    }
+
+    #[test]
+    fn top_layer_resolves_colors_and_culls() {
+        use livesplit_core::rendering::Handle;
+        let (style, shadow) = (StrokeStyle::default(), TextShadow::default());
+        let settings = display_settings(
+            &OutputTransform::IDENTITY,
+            &style,
+            &shadow,
+            ColorEncoding::Linear { brightness: 2.0 },
+        );
+        let at = |x: f32| Transform {
+            x,
+            y: 10.0,
+            scale_x: 10.0,
+            scale_y: 10.0,
+        };
+        let square = || Handle::new(0, tessellate_path(&unit_square()));
+        let entities: Vec<Entity<Option<Path>, (), Label>> = vec![
+            Entity::FillPath(square(), FillShader::SolidColor([1.0; 4]), at(50.0)),
+            Entity::FillPath(square(), FillShader::SolidColor([1.0; 4]), at(110.0)),
+            Entity::StrokePath(square(), 1.0, [0.0, 0.0, 0.0, 0.5], at(20.0)),
+            Entity::Image(Handle::new(0, ()), at(30.0)),
+        ];
+
+        let list = DisplayList::top_layer(&settings, &entities);
+        assert_eq!(
+            list.stats(),
+            FrameStats {
+                drawn_entities: 3,
+                culled_entities: 1,
+                stroke_cache_hits: 0,
+                stroke_cache_misses: 1,
+            }
+        );
+        let [cached, fill, stroke, image] = list.items() else {
+            panic!("expected four items");
+        };
+        assert!(matches!(cached, DisplayItem::CachedLayer));
+        let DisplayItem::Path { fill, dilation, .. } = fill else {
+            panic!("expected the fill's path");
+        };
+        assert_eq!(fill.shader_type, 0);
+        assert_color_eq(fill.colors[0], [2.0, 2.0, 2.0, 1.0]);
+        assert!(dilation.abs() < f32::EPSILON);
+        let DisplayItem::Path { fill, .. } = stroke else {
+            panic!("expected the stroke's path");
+        };
+        assert_color_eq(fill.colors[0], [0.0, 0.0, 0.0, 0.5]);
+        let DisplayItem::Image(image) = image else {
+            panic!("expected the image");
+        };
+        assert!((image.brightness - 2.0).abs() < f32::EPSILON);
+        assert!((image.opacity - 1.0).abs() < f32::EPSILON);
+        assert!(!image.blurred);
+    }
+
+    #[test]
+    fn layout_opacity_fades_the_top_layer_as_a_group() {
+        use livesplit_core::rendering::Handle;
+        let (style, shadow) = (StrokeStyle::default(), TextShadow::default());
+        let settings = display_settings(
+            &OutputTransform::IDENTITY,
+            &style,
+            &shadow,
+            ColorEncoding::Srgb,
+        );
+        let square = Handle::new(0, tessellate_path(&unit_square()));
+        let transform = Transform {
+            x: 10.0,
+            y: 10.0,
+            scale_x: 10.0,
+            scale_y: 10.0,
+        };
+        let top = [1.0, 0.0, 0.0, 0.5];
+        let entities: Vec<Entity<Option<Path>, (), Label>> = vec![Entity::FillPath(
+            square,
+            FillShader::SolidColor(top),
+            transform,
+        )];
+        let list = DisplayList::top_layer(&settings, &entities);
+
+        // Composite the list over an opaque bottom layer pixel the way the
+        // renderers blend it, premultiplied, and fade the result in the
+        // final pass.
+        let bottom = [0.0, 0.0, 1.0, 1.0];
+        let opacity = 0.5;
+        let mut pixel = [0.0; 4];
+        for item in list.items() {
+            match item {
+                DisplayItem::CachedLayer => pixel = bottom,
+                DisplayItem::Path { fill, .. } => {
+                    let [r, g, b, a] = fill.colors[0];
+                    let src = [r * a, g * a, b * a, a];
+                    pixel = std::array::from_fn(|i| src[i] + pixel[i] * (1.0 - a));
+                }
+                _ => panic!("unexpected item"),
+            }
+        }
+        let faded = pixel.map(|channel| channel * opacity);
+
+        // α(T + B(1 − T)), with T the top entity's premultiplied color.
+        let t = [top[0] * top[3], top[1] * top[3], top[2] * top[3], top[3]];
+        let expected = std::array::from_fn(|i| opacity * (t[i] + bottom[i] * (1.0 - top[3])));
+        assert_color_eq(faded, expected);
+        // The bottom layer shows through the top entity only as much as the
+        // entity itself lets it, not more because the entity was faded too.
+        assert_color_eq(faded, [0.25, 0.0, 0.25, 0.5]);
+    }
+
+    #[test]
+    fn bottom_layer_stretches_background_over_layout() {
+        use livesplit_core::{rendering::Handle, settings::BackgroundImage};
+        let (style, shadow) = (StrokeStyle::default(), TextShadow::default());
+        let settings = display_settings(
+            &OutputTransform::IDENTITY,
+            &style,
+            &shadow,
+            ColorEncoding::Srgb,
+        );
+        let rectangle = tessellate_path(&unit_square());
+        let (top, bottom) = ([1.0, 0.0, 0.0, 1.0], [0.0, 0.0, 1.0, 1.0]);
+        let background: Background<()> =
+            Background::Shader(FillShader::VerticalGradient(top, bottom));
+
+        let list = DisplayList::bottom_layer(&settings, Some(&background), rectangle.as_ref(), &[]);
+        assert_eq!(list.stats(), FrameStats::default());
+        let [DisplayItem::Path {
+            fill, transform, ..
+        }] = list.items()
+        else {
+            panic!("expected the background's path");
+        };
+        assert_eq!(fill.shader_type, 1);
+        assert_color_eq(fill.colors[0], top);
+        assert_color_eq(fill.colors[1], bottom);
+        assert_bounds_eq(fill.bounds, [0.0, 1.0]);
+        assert_bounds_eq([transform.scale_x, transform.scale_y], [200.0, 100.0]);
+        assert!(blurred_background(Some(&background)).is_none());
+
+        let image = Background::Image(
+            BackgroundImage {
+                image: Handle::new(0, ()),
+                brightness: 0.5,
+                opacity: 1.0,
+                blur: 0.25,
+            },
+            Transform {
+                x: 0.0,
+                y: 0.0,
+                scale_x: 200.0,
+                scale_y: 100.0,
+            },
+        );
+        let list = DisplayList::bottom_layer(&settings, Some(&image), rectangle.as_ref(), &[]);
+        let [DisplayItem::Image(item)] = list.items() else {
+            panic!("expected the background image");
+        };
+        assert!(item.blurred);
+        assert!((item.brightness - 0.5).abs() < f32::EPSILON);
+        assert!(blurred_background(Some(&image)).is_some_and(|(_, blur)| blur > 0.0));
+    }
+
+    #[test]
+    fn key_layer_fills_layout_with_opaque_key() {
+        let (style, shadow) = (StrokeStyle::default(), TextShadow::default());
+        let settings = display_settings(
+            &OutputTransform::IDENTITY,
+            &style,
+            &shadow,
+            ColorEncoding::Srgb,
+        );
+        let rectangle = tessellate_path(&unit_square());
 
-        // Width differing by exactly EPSILON should miss.
-        assert!(path.cached_stroke(1.0 + f32::EPSILON).is_none());
-        // Width differing by less than EPSILON should hit.
-        assert!(path.cached_stroke(1.0 + f32::EPSILON / 2.0).is_some());
+        let list = DisplayList::<()>::key_layer(&settings, &ChromaKey::BLUE, rectangle.as_ref());
+        let [DisplayItem::Path {
+            fill, transform, ..
+        }] = list.items()
+        else {
+            panic!("expected the key's path");
+        };
+        assert_eq!(fill.shader_type, 0);
+        assert_color_eq(fill.colors[0], [0.0, 0.0, 1.0, 1.0]);
+        assert_bounds_eq([transform.scale_x, transform.scale_y], [200.0, 100.0]);
     }
 }
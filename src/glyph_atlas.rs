@@ -0,0 +1,777 @@
+//! CPU-side glyph atlas for drawing labels as textured quads.
+//!
+//! The atlas packs one single-channel image per glyph into a square
+//! texture with a shelf packer: a signed distance field of the glyph's
+//! outline for [`TextRendering::DistanceField`], or its coverage at one
+//! pixel size and subpixel offset for [`TextRendering::Rasterized`]. Glyph
+//! images are built on the CPU from the same outlines the text engine
+//! produces; the renderers upload [`pixels`](GlyphAtlas::pixels) whenever
+//! [`take_dirty`](GlyphAtlas::take_dirty) reports a change and draw each
+//! glyph as a quad placed by its [`AtlasGlyph`].
+//!
+//! Glyphs are never evicted individually. When the atlas runs out of space,
+//! further glyphs are reported as missing (the renderers fall back to
+//! drawing their geometry) and the atlas is cleared at the start of the
+//! next frame, so only the glyphs still in use are packed again.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use livesplit_core::rendering::Transform;
+use lyon::math::Point;
+use lyon::path::iterator::PathIterator;
+use lyon::path::{Path as LyonPath, PathEvent};
+
+#[cfg(doc)]
+use crate::common::TextRendering;
+use crate::common::{OutputTransform, Path};
+
+/// Side length of the atlas texture in texels.
+pub(crate) const ATLAS_SIZE: u32 = 1024;
+
+/// Texels across the larger dimension of a glyph's outline in the distance
+/// field.
+const SDF_GLYPH_TEXELS: f32 = 48.0;
+
+/// Distance, in texels, that the distance field covers on each side of the
+/// outline. Also the padding around each glyph.
+const SDF_SPREAD: f32 = 6.0;
+
+/// Empty texels left between neighboring glyphs so that filtering never
+/// reads another glyph.
+const GUTTER: u32 = 1;
+
+/// Horizontal positions within a pixel that rasterized glyphs are prepared
+/// for. Glyphs are snapped to the nearest one.
+pub(crate) const SUBPIXEL_STEPS: u8 = 4;
+
+/// Steps per pixel that rasterized glyph sizes are rounded to, so that
+/// tiny scale changes reuse the same atlas entries.
+const SIZE_STEPS_PER_PIXEL: f32 = 8.0;
+
+/// Rasterized glyphs wider or taller than this many pixels are drawn as
+/// geometry instead; they would take up much of the atlas for few quads.
+const MAX_RASTERIZED_PIXELS: f32 = 128.0;
+
+/// Rows sampled per texel when rasterizing coverage. Coverage along each
+/// row is exact.
+const COVERAGE_SAMPLES_Y: u32 = 4;
+
+/// Where a glyph was placed in the atlas.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) struct AtlasGlyph {
+    /// The quad to draw, including the padding around the outline, in the
+    /// glyph path's local coordinates: `[x, y, width, height]`.
+    pub rect: [f32; 4],
+    /// The same quad in normalized atlas coordinates: `[u, v, width,
+    /// height]`.
+    pub uv: [f32; 4],
+}
+
+/// What a packed glyph image was built from: the address of the glyph's
+/// lyon path plus, for coverage, the quantized pixel size and subpixel
+/// offset it was rasterized at.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+enum GlyphKey {
+    DistanceField(usize),
+    Coverage {
+        path: usize,
+        size: [u32; 2],
+        subpixel: u8,
+    },
+}
+
+/// Where and at which size to draw a rasterized glyph so that its texels
+/// line up with output pixels (see [`place_rasterized_glyph`]).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) struct GlyphPlacement {
+    /// Output pixels per glyph-local unit along each axis.
+    pub pixels_per_unit: [f32; 2],
+    /// Which of the [`SUBPIXEL_STEPS`] horizontal offsets to rasterize at.
+    pub subpixel: u8,
+    /// The glyph's transform, moved onto the pixel grid.
+    pub transform: Transform,
+}
+
+/// A packed glyph. The path is kept alive so that its address, used as the
+/// key, cannot be reused by another path while the entry exists.
+struct Entry {
+    _path: Arc<LyonPath>,
+    glyph: AtlasGlyph,
+}
+
+/// Single-channel glyph atlas. See the [module docs](self).
+pub(crate) struct GlyphAtlas {
+    /// `ATLAS_SIZE`² texels, row-major, top-to-bottom.
+    pixels: Vec<u8>,
+    packer: ShelfPacker,
+    /// Packed glyphs.
+    entries: HashMap<GlyphKey, Entry>,
+    /// Whether `pixels` changed since the last upload.
+    dirty: bool,
+    /// Whether a glyph did not fit since the last reset.
+    full: bool,
+}
+
+impl GlyphAtlas {
+    /// Create an empty atlas.
+    pub fn new() -> Self {
+        Self {
+            pixels: vec![0; (ATLAS_SIZE * ATLAS_SIZE) as usize],
+            packer: ShelfPacker::new(ATLAS_SIZE, ATLAS_SIZE),
+            entries: HashMap::new(),
+            dirty: true,
+            full: false,
+        }
+    }
+
+    /// The atlas texels, `ATLAS_SIZE` × `ATLAS_SIZE`, one byte each.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Whether the texels changed since the last call, i.e. whether the
+    /// GPU copy needs to be updated.
+    pub fn take_dirty(&mut self) -> bool {
+        std::mem::take(&mut self.dirty)
+    }
+
+    /// Start a new frame. Clears the atlas if it ran out of space during
+    /// the previous one.
+    pub fn begin_frame(&mut self) {
+        if self.full {
+            self.pixels.fill(0);
+            self.packer = ShelfPacker::new(ATLAS_SIZE, ATLAS_SIZE);
+            self.entries.clear();
+            self.dirty = true;
+            self.full = false;
+        }
+    }
+
+    /// Look up a glyph's signed distance field, building and packing it on
+    /// first use.
+    ///
+    /// Returns `None` if the glyph has no area or the atlas is full; the
+    /// caller should draw the glyph's geometry instead.
+    pub fn distance_field_glyph(&mut self, path: &Path) -> Option<AtlasGlyph> {
+        let key = GlyphKey::DistanceField(Arc::as_ptr(&path.lyon_path) as usize);
+        if let Some(entry) = self.entries.get(&key) {
+            return Some(entry.glyph);
+        }
+        if self.full {
+            return None;
+        }
+
+        let [[min_x, min_y], [max_x, max_y]] = path.bounds;
+        let extent = (max_x - min_x).max(max_y - min_y);
+        if extent <= 0.0 {
+            return None;
+        }
+        let texels_per_unit = SDF_GLYPH_TEXELS / extent;
+        let size = [
+            texel_count((max_x - min_x) * texels_per_unit + 2.0 * SDF_SPREAD),
+            texel_count((max_y - min_y) * texels_per_unit + 2.0 * SDF_SPREAD),
+        ];
+        let position = self.allocate(size)?;
+
+        let origin = Point::new(
+            min_x - SDF_SPREAD / texels_per_unit,
+            min_y - SDF_SPREAD / texels_per_unit,
+        );
+        let field = signed_distance_field(&path.lyon_path, origin, texels_per_unit, size);
+        Some(self.store(
+            key,
+            path,
+            position,
+            size,
+            &field,
+            origin,
+            [texels_per_unit; 2],
+        ))
+    }
+
+    /// Look up a glyph's coverage at the given size and subpixel offset,
+    /// rasterizing and packing it on first use.
+    ///
+    /// The glyph's texels line up with output pixels when it is drawn with
+    /// the transform from [`place_rasterized_glyph`]. Returns `None` if the
+    /// glyph has no area, is larger than [`MAX_RASTERIZED_PIXELS`], or the
+    /// atlas is full; the caller should draw the glyph's geometry instead.
+    pub fn coverage_glyph(
+        &mut self,
+        path: &Path,
+        placement: &GlyphPlacement,
+    ) -> Option<AtlasGlyph> {
+        // Sizes are rounded to a few steps per pixel; positive and far
+        // below `u32::MAX` for any glyph that passes the checks below.
+        #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let size_key = placement
+            .pixels_per_unit
+            .map(|pixels| (pixels * SIZE_STEPS_PER_PIXEL).round() as u32);
+        let key = GlyphKey::Coverage {
+            path: Arc::as_ptr(&path.lyon_path) as usize,
+            size: size_key,
+            subpixel: placement.subpixel,
+        };
+        if let Some(entry) = self.entries.get(&key) {
+            return Some(entry.glyph);
+        }
+        if self.full || size_key.contains(&0) {
+            return None;
+        }
+
+        // Rasterize at the rounded size; the quad is still drawn at the
+        // exact one.
+        #[expect(clippy::cast_precision_loss)]
+        let texels_per_unit = size_key.map(|steps| steps as f32 / SIZE_STEPS_PER_PIXEL);
+        let [[min_x, min_y], [max_x, max_y]] = path.bounds;
+        let extent = [
+            (max_x - min_x) * texels_per_unit[0],
+            (max_y - min_y) * texels_per_unit[1],
+        ];
+        if extent
+            .iter()
+            .any(|&e| e <= 0.0 || e > MAX_RASTERIZED_PIXELS)
+        {
+            return None;
+        }
+
+        // Put the glyph origin `subpixel` steps into its pixel, and leave a
+        // transparent texel around the outline for filtering.
+        let offset = f32::from(placement.subpixel) / f32::from(SUBPIXEL_STEPS);
+        let left = (min_x * texels_per_unit[0] + offset).floor() - 1.0;
+        let top = (min_y * texels_per_unit[1]).floor() - 1.0;
+        let size = [
+            texel_count((max_x * texels_per_unit[0] + offset).ceil() + 1.0 - left),
+            texel_count((max_y * texels_per_unit[1]).ceil() + 1.0 - top),
+        ];
+        let position = self.allocate(size)?;
+
+        let origin = Point::new(
+            (left - offset) / texels_per_unit[0],
+            top / texels_per_unit[1],
+        );
+        let coverage = rasterize_coverage(&path.lyon_path, origin, texels_per_unit, size);
+        Some(self.store(
+            key,
+            path,
+            position,
+            size,
+            &coverage,
+            origin,
+            texels_per_unit,
+        ))
+    }
+
+    /// Reserve room for a glyph image, returning its top-left texel, or
+    /// `None` (and marking the atlas full) if it does not fit.
+    fn allocate(&mut self, [width, height]: [u32; 2]) -> Option<[u32; 2]> {
+        let position = self.packer.allocate(width + GUTTER, height + GUTTER);
+        self.full |= position.is_none();
+        position
+    }
+
+    /// Copy a glyph image into its reserved spot and record it. `origin`
+    /// is the glyph-local point at the image's top-left corner.
+    #[allow(clippy::too_many_arguments)]
+    fn store(
+        &mut self,
+        key: GlyphKey,
+        path: &Path,
+        [x, y]: [u32; 2],
+        [width, height]: [u32; 2],
+        texels: &[u8],
+        origin: Point,
+        texels_per_unit: [f32; 2],
+    ) -> AtlasGlyph {
+        for (row, texels) in texels.chunks_exact(width as usize).enumerate() {
+            let start = (y as usize + row) * ATLAS_SIZE as usize + x as usize;
+            self.pixels[start..start + texels.len()].copy_from_slice(texels);
+        }
+        self.dirty = true;
+
+        // Atlas coordinates are at most `ATLAS_SIZE`, exact in `f32`.
+        #[expect(clippy::cast_precision_loss)]
+        let glyph = AtlasGlyph {
+            rect: [
+                origin.x,
+                origin.y,
+                width as f32 / texels_per_unit[0],
+                height as f32 / texels_per_unit[1],
+            ],
+            uv: [
+                x as f32 / ATLAS_SIZE as f32,
+                y as f32 / ATLAS_SIZE as f32,
+                width as f32 / ATLAS_SIZE as f32,
+                height as f32 / ATLAS_SIZE as f32,
+            ],
+        };
+        self.entries.insert(
+            key,
+            Entry {
+                _path: Arc::clone(&path.lyon_path),
+                glyph,
+            },
+        );
+        glyph
+    }
+}
+
+/// Work out the size a glyph appears at on screen and move it onto the
+/// pixel grid: vertically to a whole pixel, horizontally to the nearest of
+/// [`SUBPIXEL_STEPS`] positions within one.
+///
+/// `transform` is the glyph's entity transform and `layout` the renderer's
+/// layout-to-viewport transform. If `layout` rotates or skews the layout,
+/// no output pixel grid lines up with the glyph, so it is only sized; the
+/// same goes for a `layout` that collapses an axis.
+pub(crate) fn place_rasterized_glyph(
+    transform: &Transform,
+    layout: &OutputTransform,
+) -> GlyphPlacement {
+    let [[xx, xy, _], [yx, yy, _]] = layout.rows();
+    let pixels_per_unit = [
+        transform.scale_x.abs() * xx.hypot(yx),
+        transform.scale_y.abs() * xy.hypot(yy),
+    ];
+    if xy.abs() > f32::EPSILON
+        || yx.abs() > f32::EPSILON
+        || xx.abs() <= f32::EPSILON
+        || yy.abs() <= f32::EPSILON
+    {
+        return GlyphPlacement {
+            pixels_per_unit,
+            subpixel: 0,
+            transform: *transform,
+        };
+    }
+
+    let steps = f32::from(SUBPIXEL_STEPS);
+    let [x, y] = layout.apply([transform.x, transform.y]);
+    let snapped_x = (x * steps).round();
+    // The step count is tiny, so the remainder is too.
+    #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let step = snapped_x.rem_euclid(steps) as u8;
+    // A mirrored glyph's texels run the other way from the output's.
+    let subpixel = if xx < 0.0 {
+        (SUBPIXEL_STEPS - step) % SUBPIXEL_STEPS
+    } else {
+        step
+    };
+    GlyphPlacement {
+        pixels_per_unit,
+        subpixel,
+        transform: Transform {
+            x: transform.x + (snapped_x / steps - x) / xx,
+            y: transform.y + (y.round() - y) / yy,
+            ..*transform
+        },
+    }
+}
+
+/// Round a texel extent up to a whole number of texels.
+#[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn texel_count(extent: f32) -> u32 {
+    // Glyph extents are a few dozen texels, far inside `u32` range.
+    extent.ceil().max(1.0) as u32
+}
+
+/// Packs rectangles left to right into rows ("shelves").
+///
+/// Rectangles of similar height share a shelf; a new shelf is opened below
+/// the last one when no existing shelf fits. Used for the glyph atlas and for
+/// the wgpu renderer's text shadow slots.
+pub(crate) struct ShelfPacker {
+    width: u32,
+    height: u32,
+    /// `(y, height, used width)` of each shelf, top to bottom.
+    shelves: Vec<(u32, u32, u32)>,
+}
+
+impl ShelfPacker {
+    pub(crate) fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            shelves: Vec::new(),
+        }
+    }
+
+    /// Reserve a `w`×`h` rectangle, returning its top-left corner, or
+    /// `None` if it does not fit.
+    pub(crate) fn allocate(&mut self, w: u32, h: u32) -> Option<[u32; 2]> {
+        if w > self.width {
+            return None;
+        }
+        // Prefer the shortest shelf the rectangle fits on, so tall shelves
+        // are not filled with short glyphs.
+        let best = self
+            .shelves
+            .iter_mut()
+            .filter(|(_, shelf_h, used)| *shelf_h >= h && self.width - *used >= w)
+            .min_by_key(|(_, shelf_h, _)| *shelf_h);
+        if let Some((y, _, used)) = best {
+            let x = *used;
+            *used += w;
+            return Some([x, *y]);
+        }
+
+        let y = self
+            .shelves
+            .last()
+            .map_or(0, |(shelf_y, shelf_h, _)| shelf_y + shelf_h);
+        if self.height - y < h {
+            return None;
+        }
+        self.shelves.push((y, h, w));
+        Some([0, y])
+    }
+}
+
+/// Compute an 8-bit signed distance field of a filled outline.
+///
+/// Texel `(i, j)` samples the point `origin + (i + 0.5, j + 0.5) /
+/// texels_per_unit`. Values are `0.5` on the outline, rise towards `1.0`
+/// inside (non-zero winding), and fall towards `0.0` outside, reaching the
+/// ends [`SDF_SPREAD`] texels away from it.
+#[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn signed_distance_field(
+    path: &LyonPath,
+    origin: Point,
+    texels_per_unit: f32,
+    [width, height]: [u32; 2],
+) -> Vec<u8> {
+    // Flatten to a quarter of a texel, closing every sub-path.
+    let mut segments = Vec::new();
+    for event in path.iter().flattened(0.25 / texels_per_unit) {
+        match event {
+            PathEvent::Line { from, to } => segments.push((from, to)),
+            PathEvent::End { last, first, .. } if last != first => {
+                segments.push((last, first));
+            }
+            _ => {}
+        }
+    }
+
+    let mut field = Vec::with_capacity((width * height) as usize);
+    for j in 0..height {
+        for i in 0..width {
+            let p = Point::new(
+                origin.x + (i as f32 + 0.5) / texels_per_unit,
+                origin.y + (j as f32 + 0.5) / texels_per_unit,
+            );
+            let mut distance = f32::INFINITY;
+            let mut winding = 0i32;
+            for &(a, b) in &segments {
+                distance = distance.min(distance_to_segment(p, a, b));
+                let cross = (b - a).cross(p - a);
+                if a.y <= p.y && p.y < b.y && cross > 0.0 {
+                    winding += 1;
+                } else if b.y <= p.y && p.y < a.y && cross < 0.0 {
+                    winding -= 1;
+                }
+            }
+            let texels = distance * texels_per_unit;
+            let signed = if winding == 0 { -texels } else { texels };
+            let value = (0.5 + signed / (2.0 * SDF_SPREAD)).clamp(0.0, 1.0);
+            // `value` is in [0, 1], so this is in [0, 255].
+            field.push((value * 255.0).round() as u8);
+        }
+    }
+    field
+}
+
+/// Rasterize a filled outline (non-zero winding) into 8-bit coverage.
+///
+/// Texel `(i, j)` covers the glyph-local square from `origin + (i, j) /
+/// texels_per_unit` to `origin + (i + 1, j + 1) / texels_per_unit`.
+/// Coverage is exact horizontally and sampled at [`COVERAGE_SAMPLES_Y`]
+/// rows per texel vertically.
+#[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn rasterize_coverage(
+    path: &LyonPath,
+    origin: Point,
+    texels_per_unit: [f32; 2],
+    [width, height]: [u32; 2],
+) -> Vec<u8> {
+    // Flatten to a quarter of a texel, closing every sub-path, and move
+    // into texel space.
+    let to_texels = |p: Point| {
+        Point::new(
+            (p.x - origin.x) * texels_per_unit[0],
+            (p.y - origin.y) * texels_per_unit[1],
+        )
+    };
+    let mut segments = Vec::new();
+    let tolerance = 0.25 / texels_per_unit[0].max(texels_per_unit[1]);
+    for event in path.iter().flattened(tolerance) {
+        match event {
+            PathEvent::Line { from, to } => segments.push((to_texels(from), to_texels(to))),
+            PathEvent::End { last, first, .. } if last != first => {
+                segments.push((to_texels(last), to_texels(first)));
+            }
+            _ => {}
+        }
+    }
+
+    let weight = 1.0 / COVERAGE_SAMPLES_Y as f32;
+    let mut coverage = vec![0.0f32; (width * height) as usize];
+    let mut crossings = Vec::new();
+    for (row_index, row) in coverage.chunks_exact_mut(width as usize).enumerate() {
+        for sample in 0..COVERAGE_SAMPLES_Y {
+            let sample_y = row_index as f32 + (sample as f32 + 0.5) * weight;
+
+            // Where the sample row crosses the outline, and which way.
+            crossings.clear();
+            for &(from, to) in &segments {
+                if (from.y <= sample_y) != (to.y <= sample_y) {
+                    let t = (sample_y - from.y) / (to.y - from.y);
+                    let direction = if from.y < to.y { 1 } else { -1 };
+                    crossings.push((from.x + (to.x - from.x) * t, direction));
+                }
+            }
+            crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            let mut winding = 0;
+            for pair in crossings.windows(2) {
+                winding += pair[0].1;
+                if winding != 0 {
+                    add_span(row, pair[0].0, pair[1].0, weight);
+                }
+            }
+        }
+    }
+    coverage
+        .into_iter()
+        // Clamped to [0, 1], so this is in [0, 255].
+        .map(|value| (value.clamp(0.0, 1.0) * 255.0).round() as u8)
+        .collect()
+}
+
+/// Add `weight` times the overlap of `[start, end)` with each texel of a
+/// coverage row.
+#[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn add_span(row: &mut [f32], start: f32, end: f32, weight: f32) {
+    let start = start.max(0.0);
+    let end = end.min(row.len() as f32);
+    if start >= end {
+        return;
+    }
+    // Both are within `[0, row.len()]` here.
+    let first = start.floor() as usize;
+    let last = (end.ceil() as usize).min(row.len());
+    for (i, texel) in row.iter_mut().enumerate().take(last).skip(first) {
+        let left = i as f32;
+        *texel += (end.min(left + 1.0) - start.max(left)) * weight;
+    }
+}
+
+/// Distance from `p` to the segment `a`–`b`.
+fn distance_to_segment(p: Point, a: Point, b: Point) -> f32 {
+    let ab = b - a;
+    let length_sq = ab.square_length();
+    let t = if length_sq > 0.0 {
+        ((p - a).dot(ab) / length_sq).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (a + ab * t - p).length()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::common::tessellate_path;
+    use lyon::math::point;
+
+    fn square(size: f32) -> Path {
+        let mut builder = LyonPath::builder();
+        builder.begin(point(0.0, 0.0));
+        builder.line_to(point(size, 0.0));
+        builder.line_to(point(size, size));
+        builder.line_to(point(0.0, size));
+        builder.close();
+        tessellate_path(&builder.build()).unwrap()
+    }
+
+    #[test]
+    fn packer_places_rectangles_without_overlap() {
+        let mut packer = ShelfPacker::new(100, 100);
+        let a = packer.allocate(60, 20).unwrap();
+        let b = packer.allocate(60, 20).unwrap();
+        let c = packer.allocate(30, 10).unwrap();
+        assert_eq!(a, [0, 0]);
+        assert_eq!(b, [0, 20]);
+        // Fits next to `a` on the first shelf.
+        assert_eq!(c, [60, 0]);
+        assert!(packer.allocate(10, 70).is_none());
+        assert!(packer.allocate(101, 1).is_none());
+    }
+
+    #[test]
+    fn distance_field_is_half_on_the_outline() {
+        let path = square(1.0);
+        let field = signed_distance_field(&path.lyon_path, Point::new(-0.5, -0.5), 4.0, [8, 8]);
+        // Texel centers at -0.375, -0.125, 0.125, ... along each axis.
+        let at = |i: usize, j: usize| field[j * 8 + i];
+        assert!(at(4, 4) > 128, "center is inside");
+        assert!(at(0, 0) < 128, "corner is outside");
+        // Half a texel either side of the left edge.
+        let outside = i32::from(at(1, 4));
+        let inside = i32::from(at(2, 4));
+        assert!(outside < 128 && inside > 128);
+        assert_eq!(outside + inside, 255);
+    }
+
+    #[test]
+    fn glyphs_are_packed_once() {
+        let mut atlas = GlyphAtlas::new();
+        let path = square(1.0);
+        let first = atlas.distance_field_glyph(&path).unwrap();
+        assert!(atlas.take_dirty());
+        let second = atlas.distance_field_glyph(&path.clone()).unwrap();
+        assert_eq!(first, second);
+        assert!(
+            !atlas.take_dirty(),
+            "a cached glyph does not touch the atlas"
+        );
+    }
+
+    #[test]
+    fn glyph_quad_covers_outline_and_padding() {
+        let mut atlas = GlyphAtlas::new();
+        let glyph = atlas.distance_field_glyph(&square(2.0)).unwrap();
+        let padding = SDF_SPREAD * 2.0 / SDF_GLYPH_TEXELS;
+        let [x, y, w, h] = glyph.rect;
+        assert!((x + padding).abs() < 1e-4 && (y + padding).abs() < 1e-4);
+        assert!((w - (2.0 + 2.0 * padding)).abs() < 1e-4);
+        assert!((h - w).abs() < 1e-4);
+        #[expect(clippy::cast_precision_loss)]
+        let texels = (SDF_GLYPH_TEXELS + 2.0 * SDF_SPREAD) / ATLAS_SIZE as f32;
+        assert!((glyph.uv[2] - texels).abs() < 1e-6);
+    }
+
+    #[test]
+    fn full_atlas_falls_back_and_resets_next_frame() {
+        let mut atlas = GlyphAtlas::new();
+        let paths: Vec<Path> = (0..1000).map(|_| square(1.0)).collect();
+        let placed = paths
+            .iter()
+            .take_while(|path| atlas.distance_field_glyph(path).is_some())
+            .count();
+        assert!(placed < paths.len(), "the atlas should fill up");
+        assert!(atlas.distance_field_glyph(&paths[placed]).is_none());
+        // Already packed glyphs stay available for the rest of the frame.
+        assert!(atlas.distance_field_glyph(&paths[0]).is_some());
+
+        atlas.begin_frame();
+        assert!(atlas.distance_field_glyph(&paths[placed]).is_some());
+    }
+
+    fn placement(pixels: f32, subpixel: u8) -> GlyphPlacement {
+        GlyphPlacement {
+            pixels_per_unit: [pixels; 2],
+            subpixel,
+            transform: Transform {
+                x: 0.0,
+                y: 0.0,
+                scale_x: pixels,
+                scale_y: pixels,
+            },
+        }
+    }
+
+    #[test]
+    fn coverage_is_exact_on_whole_and_half_texels() {
+        let path = square(1.0);
+        let aligned =
+            rasterize_coverage(&path.lyon_path, Point::new(-0.25, -0.25), [4.0; 2], [6, 6]);
+        let row: Vec<u8> = aligned[6 * 3..6 * 4].to_vec();
+        assert_eq!(row, [0, 255, 255, 255, 255, 0]);
+        assert!(aligned[..6].iter().all(|&texel| texel == 0));
+
+        // Shifted by half a texel, the left and right edges half cover.
+        let shifted =
+            rasterize_coverage(&path.lyon_path, Point::new(-0.125, 0.0), [4.0; 2], [5, 4]);
+        let row: Vec<u8> = shifted[5..10].to_vec();
+        assert_eq!(row, [128, 255, 255, 255, 128]);
+    }
+
+    #[test]
+    #[expect(clippy::float_cmp)] // UVs of distinct atlas cells differ exactly
+    fn coverage_glyphs_are_keyed_by_size_and_subpixel() {
+        let mut atlas = GlyphAtlas::new();
+        let path = square(1.0);
+        let small = atlas.coverage_glyph(&path, &placement(16.0, 0)).unwrap();
+        assert_eq!(
+            atlas.coverage_glyph(&path, &placement(16.0, 0)),
+            Some(small)
+        );
+        let large = atlas.coverage_glyph(&path, &placement(24.0, 0)).unwrap();
+        let shifted = atlas.coverage_glyph(&path, &placement(16.0, 1)).unwrap();
+        assert_ne!(small.uv, large.uv);
+        assert_ne!(small.uv, shifted.uv);
+        // The quad starts a transparent texel before the outline, minus the
+        // subpixel offset.
+        assert!((small.rect[0] + 1.0 / 16.0).abs() < 1e-6);
+        assert!((shifted.rect[0] + 1.25 / 16.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn large_coverage_glyphs_fall_back_to_geometry() {
+        let mut atlas = GlyphAtlas::new();
+        assert!(atlas
+            .coverage_glyph(&square(1.0), &placement(200.0, 0))
+            .is_none());
+        assert!(!atlas.full, "an oversized glyph does not fill the atlas");
+    }
+
+    #[test]
+    fn glyphs_snap_to_subpixel_steps() {
+        let transform = Transform {
+            x: 10.3,
+            y: 5.6,
+            scale_x: 20.0,
+            scale_y: 20.0,
+        };
+        let placed = place_rasterized_glyph(&transform, &OutputTransform::scale(2.0, 2.0));
+        assert!(placed
+            .pixels_per_unit
+            .iter()
+            .all(|&pixels| (pixels - 40.0).abs() < 1e-5));
+        // 20.6 px snaps to 20.5 px, two steps into the pixel.
+        assert_eq!(placed.subpixel, 2);
+        assert!((placed.transform.x - 10.25).abs() < 1e-5);
+        assert!((placed.transform.y - 5.5).abs() < 1e-5);
+
+        // Mirrored, the glyph's texels run right to left.
+        let mirrored = place_rasterized_glyph(&transform, &OutputTransform::flip_horizontal(100.0));
+        assert_eq!(mirrored.subpixel, 1);
+        assert!((mirrored.transform.x - 10.25).abs() < 1e-5);
+
+        // Rotated, there is no pixel grid to snap to.
+        let rotated =
+            place_rasterized_glyph(&transform, &OutputTransform::rotation(0.5_f32.to_radians()));
+        assert_eq!(rotated.subpixel, 0);
+        assert_eq!(rotated.transform, transform);
+
+        // Collapsed to a line, neither is there (and snapping would divide
+        // by zero).
+        let collapsed = place_rasterized_glyph(&transform, &OutputTransform::scale(2.0, 0.0));
+        assert_eq!(collapsed.subpixel, 0);
+        assert_eq!(collapsed.transform, transform);
+    }
+}
@@ -9,9 +9,13 @@
 //!   Vulkan, Metal, DX12, and OpenGL backends.
 //!
 //! Both renderers share the same architecture: paths are tessellated via
-//! [lyon] at creation time and drawn as indexed triangle meshes. The
-//! livesplit-core scene's two-layer design is honored — the bottom layer is
-//! cached in an off-screen texture and only re-rendered when it changes.
+//! [lyon] at creation time and drawn as indexed triangle meshes,
+//! re-tessellated (and cached) for the size they are drawn at so curves
+//! stay within a tenth of a pixel. The livesplit-core scene's two-layer
+//! design is honored — the bottom layer is cached in an off-screen texture
+//! and only re-rendered when it changes. Each layer is turned into a display
+//! list of draws, with entities culled and colors resolved for the output,
+//! by code shared between the renderers, which only execute it.
 //!
 //! # Features
 //!
@@ -21,11 +25,62 @@
 //! - **Gradient fills**: solid, vertical, and horizontal gradients are
 //!   supported natively in the fragment shader.
 //! - **Text rendering** via livesplit-core's built-in text engine, with
-//!   optional text shadows.
+//!   optional text shadows that can be offset, blurred, and spread
+//!   ([`TextShadow`]) and outlines ([`TextOutline`]).
+//! - **Stroke styles**: joins, caps, miter limit, and dashes of stroked
+//!   paths are configurable ([`StrokeStyle`]).
+//! - **Culling**: entities that lie entirely outside the viewport are
+//!   skipped, and counted in [`FrameStats`].
+//! - **Layout opacity**: the whole layout can be faded as one group,
+//!   instantly or animated over a duration ([`Fade`]), without re-rendering
+//!   the cached bottom layer.
+//! - **Post-processing**: [`WgpuRenderer`] can run a chain of user WGSL
+//!   shaders ([`PostEffect`]) over the finished frame, e.g. for CRT
+//!   scanlines, chromatic aberration, or color grading.
+//! - **Straight alpha output**: the finished frame can be written with
+//!   straight instead of premultiplied alpha ([`AlphaMode`]).
+//! - **Color filters**: a [`ColorMatrix`] filters the finished frame at
+//!   composite time, with presets for grayscale, inversion, high contrast,
+//!   and daltonization ([`ColorDeficiency`]).
+//! - **Rounded corners and borders**: an [`OutputMask`] clips the finished
+//!   frame to a rounded rectangle with an optional border, e.g. for
+//!   borderless transparent windows.
+//! - **Chroma key**: a [`ChromaKey`] replaces the layout's background with
+//!   a key color, mixed with antialiased edges or cut at a threshold, for
+//!   capture tools that cannot capture alpha.
+//! - **Dithering**: an optional ordered dither ([`Dithering`]) hides
+//!   banding in gradients and blurred backgrounds.
+//! - **High bit depth and HDR**: 10-bit and half-float scRGB outputs
+//!   ([`OutputFormat`]) keep their precision through the off-screen targets,
+//!   with the layout's white mapped to the desktop's SDR white level.
 //! - **Lazy texture upload**: images are decoded on the CPU and uploaded to
 //!   the GPU only when first drawn.
 //! - **Background blur**: optional gaussian blur on background images,
 //!   computed on the CPU and cached.
+//! - **Viewports**: the layout can be drawn into a sub-rectangle of a larger
+//!   target (see [`Viewport`]), leaving the rest of the target untouched.
+//! - **Frame graph integration**: [`WgpuRenderer`] can record into a
+//!   caller-provided command encoder or render pass instead of submitting
+//!   its own work.
+//! - **Overlay compositing**: with [`CompositeMode::Blend`] the layout is
+//!   alpha-blended over the target's existing contents instead of replacing
+//!   them.
+//! - **Output transforms**: an [`OutputTransform`] rotates, scales, skews,
+//!   or mirrors the whole layout in the vertex stage, e.g. for vertical
+//!   monitors or camera-overlay setups.
+//! - **High-DPI displays**: with a scale factor set, the layout is laid out in logical
+//!   units and rasterized at the display's physical resolution.
+//! - **Supersampling**: [`Antialiasing::Supersample`] replaces MSAA with an
+//!   enlarged single-sample target filtered down with a box or Lanczos
+//!   [`DownsampleFilter`], for drivers with poor multisampling support.
+//! - **Analytic antialiasing**: [`Antialiasing::Analytic`] renders at one
+//!   sample per pixel and smooths path edges with a coverage fringe, built
+//!   when a path is first drawn in that mode, for GPUs too weak for MSAA.
+//! - **Distance field text**: [`TextRendering::DistanceField`] draws labels
+//!   as quads from a signed distance field glyph atlas, with optional
+//!   outlines and glows ([`TextEffects`]).
+//! - **Rasterized text**: [`TextRendering::Rasterized`] caches glyph
+//!   coverage per pixel size and quarter-pixel offset, for crisp small text.
 //!
 //! # Choosing a renderer
 //!
@@ -55,6 +110,7 @@
 //! [lyon]: https://docs.rs/lyon
 
 mod common;
+mod glyph_atlas;
 
 #[cfg(feature = "glow")]
 mod allocator;
@@ -70,13 +126,23 @@ mod wgpu_allocator;
 #[cfg(feature = "wgpu")]
 mod wgpu_buffer_pool;
 #[cfg(feature = "wgpu")]
+mod wgpu_post_process;
+#[cfg(feature = "wgpu")]
 mod wgpu_render;
 #[cfg(feature = "wgpu")]
 mod wgpu_shaders;
 #[cfg(feature = "wgpu")]
 mod wgpu_types;
 
+pub use common::{
+    AlphaMode, Antialiasing, ChromaKey, ColorDeficiency, ColorMatrix, CompositeMode, Dithering,
+    DownsampleFilter, Fade, FrameStats, LineCap, LineJoin, OutputFormat, OutputMask,
+    OutputTransform, StrokeStyle, TextEffects, TextOutline, TextRendering, TextShadow, Viewport,
+    SCRGB_WHITE_NITS,
+};
 #[cfg(feature = "glow")]
 pub use render::GlowRenderer;
 #[cfg(feature = "wgpu")]
+pub use wgpu_post_process::{PostEffect, PostEffectError};
+#[cfg(feature = "wgpu")]
 pub use wgpu_render::WgpuRenderer;
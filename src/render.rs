@@ -6,14 +6,24 @@
 use glow::{HasContext, PixelUnpackData};
 use livesplit_core::{
     layout::LayoutState,
-    rendering::{Background, Entity, FillShader, Handle, LabelHandle, SceneManager, Transform},
-    settings::{BackgroundImage, ImageCache},
+    rendering::{Handle, SceneManager, Transform},
+    settings::ImageCache,
 };
+use std::cell::{Cell, RefCell};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use crate::{
     allocator::GlAllocator,
-    common::{tessellate_stroke, vertex_bounds, BLUR_FACTOR, SHADOW_OFFSET},
+    common::{
+        blurred_background, curve_tolerance, fill_at_tolerance, layout_resolution,
+        layout_transform, shadow_region, AlphaMode, Antialiasing, ChromaKey, ColorEncoding,
+        ColorMatrix, CompositeMode, DisplayItem, DisplayList, DisplaySettings, Dithering,
+        DownsampleFilter, Fill, FrameStats, ImageItem, LayoutOpacity, OutputFormat, OutputMask,
+        OutputTransform, ShadowItem, StrokeStyle, TextEffects, TextOutline, TextRendering,
+        TextShadow, Viewport, BLUR_FACTOR, SCRGB_WHITE_NITS,
+    },
+    glyph_atlas::{place_rasterized_glyph, AtlasGlyph, GlyphAtlas, ATLAS_SIZE},
     shaders,
     types::{GlFont, GlImage, GlLabel, GlPath, Vertex},
 };
@@ -27,6 +37,16 @@ const MSAA_SAMPLES: i32 = 4;
 #[expect(clippy::cast_possible_wrap)]
 const RGBA8_INTERNAL_FORMAT: i32 = glow::RGBA8 as i32;
 
+/// GL internal format and pixel type of render targets in the given output
+/// format.
+const fn render_target_format(format: OutputFormat) -> (u32, u32) {
+    match format {
+        OutputFormat::Rgba8 => (glow::RGBA8, glow::UNSIGNED_BYTE),
+        OutputFormat::Rgb10A2 => (glow::RGB10_A2, glow::UNSIGNED_INT_2_10_10_10_REV),
+        OutputFormat::Rgba16Float => (glow::RGBA16F, glow::HALF_FLOAT),
+    }
+}
+
 /// Convert a `u32` to `i32` for GL API calls.
 ///
 /// # Panics
@@ -37,14 +57,52 @@ fn gl_size(value: u32) -> i32 {
     i32::try_from(value).expect("dimension exceeds i32::MAX")
 }
 
+/// Upload an output transform to a program's `u_transform_x` and
+/// `u_transform_y` uniforms. The program must be in use.
+unsafe fn set_transform_uniforms(
+    gl: &glow::Context,
+    [x, y]: [&glow::UniformLocation; 2],
+    transform: &OutputTransform,
+) {
+    let [row_x, row_y] = transform.rows();
+    unsafe {
+        gl.uniform_3_f32(Some(x), row_x[0], row_x[1], row_x[2]);
+        gl.uniform_3_f32(Some(y), row_y[0], row_y[1], row_y[2]);
+    }
+}
+
+/// Configure a program's fill shader uniforms (`u_shader_type`,
+/// `u_color_a`, `u_color_b`, `u_bounds`) from a resolved display list fill.
+/// The program must be in use.
+unsafe fn set_fill_uniforms(
+    gl: &glow::Context,
+    [shader_type, color_a, color_b, bounds]: [&glow::UniformLocation; 4],
+    fill: &Fill,
+) {
+    unsafe {
+        gl.uniform_1_i32(Some(shader_type), fill.shader_type);
+        gl.uniform_4_f32_slice(Some(color_a), &fill.colors[0]);
+        gl.uniform_4_f32_slice(Some(color_b), &fill.colors[1]);
+        gl.uniform_2_f32(Some(bounds), fill.bounds[0], fill.bounds[1]);
+    }
+}
+
 /// Cached uniform locations for the path shader program.
 struct PathUniforms {
     /// `u_scale` — entity width and height.
     scale: glow::UniformLocation,
     /// `u_offset` — entity translation.
     offset: glow::UniformLocation,
+    /// `u_transform_x` — first row of the output transform.
+    transform_x: glow::UniformLocation,
+    /// `u_transform_y` — second row of the output transform.
+    transform_y: glow::UniformLocation,
     /// `u_resolution` — viewport size in pixels.
     resolution: glow::UniformLocation,
+    /// `u_fringe_width` — antialiasing fringe width in pixels.
+    fringe_width: glow::UniformLocation,
+    /// `u_dilation` — outward shift of the path's edges in pixels.
+    dilation: glow::UniformLocation,
     /// `u_shader_type` — 0 = solid, 1 = vertical gradient, 2 = horizontal.
     shader_type: glow::UniformLocation,
     /// `u_color_a` — solid color or gradient start.
@@ -53,6 +111,8 @@ struct PathUniforms {
     color_b: glow::UniformLocation,
     /// `u_bounds` — `[min, max]` for gradient interpolation axis.
     bounds: glow::UniformLocation,
+    /// `u_dither` — dither amplitude (0 = off).
+    dither: glow::UniformLocation,
 }
 
 /// Cached uniform locations for the image shader program.
@@ -61,6 +121,10 @@ struct ImageUniforms {
     scale: glow::UniformLocation,
     /// `u_offset` — entity translation.
     offset: glow::UniformLocation,
+    /// `u_transform_x` — first row of the output transform.
+    transform_x: glow::UniformLocation,
+    /// `u_transform_y` — second row of the output transform.
+    transform_y: glow::UniformLocation,
     /// `u_resolution` — viewport size in pixels.
     resolution: glow::UniformLocation,
     /// `u_texture` — texture unit index (always 0).
@@ -74,6 +138,136 @@ struct ImageUniforms {
     /// `u_already_premultiplied` — if non-zero, skip alpha premultiplication
     /// (used when blitting FBO content that is already premultiplied).
     already_premultiplied: glow::UniformLocation,
+    /// `u_dither` — dither amplitude (0 = off).
+    dither: glow::UniformLocation,
+    /// `u_linearize` — if non-zero, convert the sampled sRGB colors to
+    /// linear (for linear targets).
+    linearize: glow::UniformLocation,
+    /// Uniforms of the final pass into the caller's framebuffer.
+    output: OutputUniforms,
+}
+
+/// Cached locations of the uniforms the image and downsample programs use
+/// when writing the finished frame to the caller's framebuffer.
+struct OutputUniforms {
+    /// `u_unpremultiply` — if non-zero, divide the result by its alpha
+    /// (straight alpha output).
+    unpremultiply: glow::UniformLocation,
+    /// `u_color_matrix` — 4×4 part of the color matrix.
+    color_matrix: glow::UniformLocation,
+    /// `u_color_offset` — offset column of the color matrix.
+    color_offset: glow::UniformLocation,
+    /// `u_peak` — color value of the layout's white.
+    peak: glow::UniformLocation,
+    /// `u_mask_size` — output mask size in pixels (0 = off).
+    mask_size: glow::UniformLocation,
+    /// `u_corner_radius` — output mask corner radius in pixels.
+    corner_radius: glow::UniformLocation,
+    /// `u_border_width` — output mask border width in pixels.
+    border_width: glow::UniformLocation,
+    /// `u_border_color` — premultiplied output mask border color.
+    border_color: glow::UniformLocation,
+    /// `u_key_color` — opaque chroma key color (0 = no key).
+    key_color: glow::UniformLocation,
+    /// `u_key_threshold` — coverage from which the layout is shown instead
+    /// of the key (0 = composite the layout over the key).
+    key_threshold: glow::UniformLocation,
+    /// `u_layout_opacity` — multiplier of the finished frame.
+    layout_opacity: glow::UniformLocation,
+}
+
+impl OutputUniforms {
+    /// Look up the output uniforms of `program`, the `shader` program.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the program lacks one of them.
+    unsafe fn new(gl: &glow::Context, program: glow::Program, shader: &str) -> Self {
+        let location = |name: &str| {
+            unsafe { gl.get_uniform_location(program, name) }
+                .unwrap_or_else(|| panic!("{name} missing from {shader} shader"))
+        };
+        Self {
+            unpremultiply: location("u_unpremultiply"),
+            color_matrix: location("u_color_matrix"),
+            color_offset: location("u_color_offset"),
+            peak: location("u_peak"),
+            mask_size: location("u_mask_size"),
+            corner_radius: location("u_corner_radius"),
+            border_width: location("u_border_width"),
+            border_color: location("u_border_color"),
+            key_color: location("u_key_color"),
+            key_threshold: location("u_key_threshold"),
+            layout_opacity: location("u_layout_opacity"),
+        }
+    }
+}
+
+/// Cached uniform locations for the atlas text program.
+struct TextUniforms {
+    /// `u_scale` — glyph scale.
+    scale: glow::UniformLocation,
+    /// `u_offset` — glyph translation.
+    offset: glow::UniformLocation,
+    /// `u_transform_x` — first row of the output transform.
+    transform_x: glow::UniformLocation,
+    /// `u_transform_y` — second row of the output transform.
+    transform_y: glow::UniformLocation,
+    /// `u_resolution` — viewport size in pixels.
+    resolution: glow::UniformLocation,
+    /// `u_glyph_rect` — quad origin and size in glyph-local space.
+    glyph_rect: glow::UniformLocation,
+    /// `u_uv_rect` — quad origin and size in the atlas.
+    uv_rect: glow::UniformLocation,
+    /// `u_atlas` — texture unit index (always 0).
+    atlas: glow::UniformLocation,
+    /// `u_shader_type` — 0 = solid, 1 = vertical gradient, 2 = horizontal.
+    shader_type: glow::UniformLocation,
+    /// `u_color_a` — solid color or gradient start.
+    color_a: glow::UniformLocation,
+    /// `u_color_b` — gradient end color.
+    color_b: glow::UniformLocation,
+    /// `u_bounds` — `[min, max]` for gradient interpolation axis.
+    bounds: glow::UniformLocation,
+    /// `u_outline_width` — outline width in pixels.
+    outline_width: glow::UniformLocation,
+    /// `u_outline_color` — outline color.
+    outline_color: glow::UniformLocation,
+    /// `u_glow_radius` — glow radius in pixels.
+    glow_radius: glow::UniformLocation,
+    /// `u_glow_color` — glow color.
+    glow_color: glow::UniformLocation,
+    /// `u_coverage` — 1 if the atlas holds coverage, not distance.
+    coverage: glow::UniformLocation,
+}
+
+/// Cached uniform locations for the downsample shader program.
+struct DownsampleUniforms {
+    /// `u_texture` — texture unit index (always 0).
+    texture: glow::UniformLocation,
+    /// `u_factor` — supersampling factor.
+    factor: glow::UniformLocation,
+    /// `u_filter` — 0 = box, 1 = Lanczos.
+    filter: glow::UniformLocation,
+    /// `u_dither` — dither amplitude (0 = off).
+    dither: glow::UniformLocation,
+    /// Uniforms of the final pass, including `u_peak`, the largest color
+    /// channel value per unit of alpha.
+    output: OutputUniforms,
+}
+
+/// Cached uniform locations for the shadow blur program.
+struct ShadowBlurUniforms {
+    /// `u_texture` — texture unit index (always 0).
+    texture: glow::UniformLocation,
+    /// `u_direction` — axis to blur along.
+    direction: glow::UniformLocation,
+    /// `u_region` — texels that may be read.
+    region: glow::UniformLocation,
+    /// `u_sigma` — standard deviation in texels.
+    sigma: glow::UniformLocation,
+    /// `u_taps` — texels sampled on each side.
+    taps: glow::UniformLocation,
 }
 
 /// Cached blurred background texture.
@@ -104,7 +298,7 @@ struct BlurCache {
 /// # Example
 ///
 /// ```no_run
-/// # use livesplit_renderer_gpu::GlowRenderer;
+/// # use livesplit_renderer_gpu::{GlowRenderer, Viewport};
 /// # use std::sync::Arc;
 /// # fn example(gl: Arc<glow::Context>, state: &livesplit_core::layout::LayoutState,
 /// #            image_cache: &livesplit_core::settings::ImageCache) {
@@ -113,6 +307,11 @@ struct BlurCache {
 ///
 /// // Each frame:
 /// let new_size = unsafe { renderer.render(state, image_cache, [800, 600], true) };
+///
+/// // Or draw into a 400x300 panel at (16, 16) of a larger framebuffer:
+/// let new_size = unsafe {
+///     renderer.render(state, image_cache, Viewport::new(16, 16, 400, 300), true)
+/// };
 /// # }
 /// ```
 pub struct GlowRenderer {
@@ -137,8 +336,54 @@ pub struct GlowRenderer {
     /// Cached uniform locations for [`image_program`](Self::image_program).
     image_uniforms: ImageUniforms,
 
+    /// Compiled shader program for text drawn from the glyph atlas.
+    text_program: glow::Program,
+    /// Cached uniform locations for [`text_program`](Self::text_program).
+    text_uniforms: TextUniforms,
+    /// How labels are drawn.
+    text_rendering: TextRendering,
+    /// Distance fields of the glyphs drawn with
+    /// [`TextRendering::DistanceField`]. Wrapped in `RefCell` because glyphs
+    /// are added while drawing, which only borrows the renderer.
+    glyph_atlas: RefCell<GlyphAtlas>,
+    /// Single-channel texture holding [`glyph_atlas`](Self::glyph_atlas).
+    atlas_texture: glow::Texture,
+
+    /// Dither applied to paths and images before they are stored.
+    dithering: Dithering,
+    /// Pixel format of the off-screen targets and the caller's framebuffer.
+    output_format: OutputFormat,
+    /// Brightness of the layout's white on scRGB outputs, in nits.
+    sdr_white_level: f32,
+    /// How the layout's stroked paths are joined, capped, and dashed.
+    stroke_style: StrokeStyle,
+    /// Outline drawn around every label glyph, if any.
+    text_outline: Option<TextOutline>,
+    /// Shape of the shadows drawn under labels.
+    text_shadow: TextShadow,
+    /// Compiled shader program that blurs text shadows along one axis.
+    shadow_program: glow::Program,
+    /// Cached uniform locations for
+    /// [`shadow_program`](Self::shadow_program).
+    shadow_uniforms: ShadowBlurUniforms,
+    /// Viewport-sized framebuffers that blurred text shadows are drawn and
+    /// blurred in, ping-ponging between the two.
+    shadow_fbos: [glow::Framebuffer; 2],
+    /// Textures attached to [`shadow_fbos`](Self::shadow_fbos).
+    shadow_textures: [glow::Texture; 2],
+
+    /// Compiled shader program that downsamples a supersampled frame into
+    /// the caller's framebuffer.
+    downsample_program: glow::Program,
+    /// Cached uniform locations for
+    /// [`downsample_program`](Self::downsample_program).
+    downsample_uniforms: DownsampleUniforms,
+
     /// Vertex array object with a single `vec2` position attribute.
     vao: glow::VertexArray,
+    /// Vertex array object without attributes, for draws whose vertices are
+    /// generated from `gl_VertexID`.
+    empty_vao: glow::VertexArray,
     /// Vertex buffer for streaming path vertex data each frame.
     vbo: glow::Buffer,
     /// Element (index) buffer for streaming path index data each frame.
@@ -156,12 +401,56 @@ pub struct GlowRenderer {
     msaa_fbo: glow::Framebuffer,
     /// MSAA renderbuffer (color attachment) for [`msaa_fbo`](Self::msaa_fbo).
     msaa_rbo: glow::Renderbuffer,
+    /// Single-sample texture attached to [`msaa_fbo`](Self::msaa_fbo)
+    /// instead of [`msaa_rbo`](Self::msaa_rbo) when supersampling.
+    ssaa_texture: glow::Texture,
+    /// How edges are antialiased.
+    antialiasing: Antialiasing,
+
+    /// Non-MSAA framebuffer the finished frame is resolved into when it has
+    /// to be drawn into the caller's framebuffer as a textured quad (see
+    /// [`CompositeMode::Blend`]).
+    composite_fbo: glow::Framebuffer,
+    /// Texture attached to [`composite_fbo`](Self::composite_fbo).
+    composite_texture: glow::Texture,
+
+    /// How the finished frame is combined with the caller's framebuffer.
+    composite_mode: CompositeMode,
+    /// Whether the finished frame is written with premultiplied or straight
+    /// alpha.
+    alpha_mode: AlphaMode,
+    /// Color matrix applied to the finished frame.
+    color_matrix: ColorMatrix,
+    /// Rounded-rectangle mask and border applied to the finished frame.
+    output_mask: OutputMask,
+    /// Key color that replaces the background, if any.
+    chroma_key: Option<ChromaKey>,
+    /// Affine transform from layout to viewport coordinates set by the
+    /// user.
+    output_transform: OutputTransform,
+    /// Ratio of physical pixels to logical layout units.
+    scale_factor: f32,
+    /// [`scale_factor`](Self::scale_factor) followed by
+    /// [`output_transform`](Self::output_transform), applied to every
+    /// entity draw.
+    layout_transform: OutputTransform,
 
     /// Current dimensions of the off-screen framebuffers.
     fbo_size: [u32; 2],
+    /// Largest width and height of an off-screen target the context
+    /// supports, which bounds the supersampling factor.
+    max_target_dimension: u32,
     /// Whether the cached bottom layer needs re-rendering (e.g., after a
     /// resize).
     bottom_layer_dirty: bool,
+    /// Entity and stroke cache counts of the frame being drawn, or of the
+    /// last one. A `Cell` because they are counted while drawing, which
+    /// only borrows the renderer.
+    frame_stats: Cell<FrameStats>,
+
+    /// Opacity of the whole layout, applied when compositing the cached
+    /// bottom layer and the top layer.
+    opacity: LayoutOpacity,
 
     /// Cached blurred background image texture, reused across frames when
     /// the source image and blur setting are unchanged.
@@ -191,12 +480,35 @@ impl GlowRenderer {
     /// indicates a bug in the shader source code.
     #[expect(clippy::too_many_lines)] // GL initialization is inherently verbose
     pub unsafe fn new(gl: Arc<glow::Context>) -> Result<Self, String> {
+        // Supersampled targets are both textures and renderbuffers.
+        let max_target_dimension = unsafe {
+            gl.get_parameter_i32(glow::MAX_TEXTURE_SIZE)
+                .min(gl.get_parameter_i32(glow::MAX_RENDERBUFFER_SIZE))
+        };
+        let max_target_dimension = u32::try_from(max_target_dimension).unwrap_or(0);
         let path_program = unsafe {
             shaders::compile_program(&gl, shaders::PATH_VERTEX_SRC, shaders::PATH_FRAGMENT_SRC)?
         };
         let image_program = unsafe {
             shaders::compile_program(&gl, shaders::IMAGE_VERTEX_SRC, shaders::IMAGE_FRAGMENT_SRC)?
         };
+        let downsample_program = unsafe {
+            shaders::compile_program(
+                &gl,
+                shaders::DOWNSAMPLE_VERTEX_SRC,
+                shaders::DOWNSAMPLE_FRAGMENT_SRC,
+            )?
+        };
+        let text_program = unsafe {
+            shaders::compile_program(&gl, shaders::TEXT_VERTEX_SRC, shaders::TEXT_FRAGMENT_SRC)?
+        };
+        let shadow_program = unsafe {
+            shaders::compile_program(
+                &gl,
+                shaders::DOWNSAMPLE_VERTEX_SRC,
+                shaders::SHADOW_BLUR_FRAGMENT_SRC,
+            )?
+        };
 
         let path_uniforms = unsafe {
             PathUniforms {
@@ -206,9 +518,21 @@ impl GlowRenderer {
                 offset: gl
                     .get_uniform_location(path_program, "u_offset")
                     .expect("u_offset missing from path shader"),
+                transform_x: gl
+                    .get_uniform_location(path_program, "u_transform_x")
+                    .expect("u_transform_x missing from path shader"),
+                transform_y: gl
+                    .get_uniform_location(path_program, "u_transform_y")
+                    .expect("u_transform_y missing from path shader"),
                 resolution: gl
                     .get_uniform_location(path_program, "u_resolution")
                     .expect("u_resolution missing from path shader"),
+                fringe_width: gl
+                    .get_uniform_location(path_program, "u_fringe_width")
+                    .expect("u_fringe_width missing from path shader"),
+                dilation: gl
+                    .get_uniform_location(path_program, "u_dilation")
+                    .expect("u_dilation missing from path shader"),
                 shader_type: gl
                     .get_uniform_location(path_program, "u_shader_type")
                     .expect("u_shader_type missing from path shader"),
@@ -221,6 +545,9 @@ impl GlowRenderer {
                 bounds: gl
                     .get_uniform_location(path_program, "u_bounds")
                     .expect("u_bounds missing from path shader"),
+                dither: gl
+                    .get_uniform_location(path_program, "u_dither")
+                    .expect("u_dither missing from path shader"),
             }
         };
 
@@ -232,6 +559,12 @@ impl GlowRenderer {
                 offset: gl
                     .get_uniform_location(image_program, "u_offset")
                     .expect("u_offset missing from image shader"),
+                transform_x: gl
+                    .get_uniform_location(image_program, "u_transform_x")
+                    .expect("u_transform_x missing from image shader"),
+                transform_y: gl
+                    .get_uniform_location(image_program, "u_transform_y")
+                    .expect("u_transform_y missing from image shader"),
                 resolution: gl
                     .get_uniform_location(image_program, "u_resolution")
                     .expect("u_resolution missing from image shader"),
@@ -250,6 +583,71 @@ impl GlowRenderer {
                 already_premultiplied: gl
                     .get_uniform_location(image_program, "u_already_premultiplied")
                     .expect("u_already_premultiplied missing from image shader"),
+                dither: gl
+                    .get_uniform_location(image_program, "u_dither")
+                    .expect("u_dither missing from image shader"),
+                linearize: gl
+                    .get_uniform_location(image_program, "u_linearize")
+                    .expect("u_linearize missing from image shader"),
+                output: OutputUniforms::new(&gl, image_program, "image"),
+            }
+        };
+
+        let downsample_uniforms = unsafe {
+            DownsampleUniforms {
+                texture: gl
+                    .get_uniform_location(downsample_program, "u_texture")
+                    .expect("u_texture missing from downsample shader"),
+                factor: gl
+                    .get_uniform_location(downsample_program, "u_factor")
+                    .expect("u_factor missing from downsample shader"),
+                filter: gl
+                    .get_uniform_location(downsample_program, "u_filter")
+                    .expect("u_filter missing from downsample shader"),
+                dither: gl
+                    .get_uniform_location(downsample_program, "u_dither")
+                    .expect("u_dither missing from downsample shader"),
+                output: OutputUniforms::new(&gl, downsample_program, "downsample"),
+            }
+        };
+
+        let text_uniforms = unsafe {
+            let location = |name: &str| {
+                gl.get_uniform_location(text_program, name)
+                    .unwrap_or_else(|| panic!("{name} missing from text shader"))
+            };
+            TextUniforms {
+                scale: location("u_scale"),
+                offset: location("u_offset"),
+                transform_x: location("u_transform_x"),
+                transform_y: location("u_transform_y"),
+                resolution: location("u_resolution"),
+                glyph_rect: location("u_glyph_rect"),
+                uv_rect: location("u_uv_rect"),
+                atlas: location("u_atlas"),
+                shader_type: location("u_shader_type"),
+                color_a: location("u_color_a"),
+                color_b: location("u_color_b"),
+                bounds: location("u_bounds"),
+                outline_width: location("u_outline_width"),
+                outline_color: location("u_outline_color"),
+                glow_radius: location("u_glow_radius"),
+                glow_color: location("u_glow_color"),
+                coverage: location("u_coverage"),
+            }
+        };
+
+        let shadow_uniforms = unsafe {
+            let location = |name: &str| {
+                gl.get_uniform_location(shadow_program, name)
+                    .unwrap_or_else(|| panic!("{name} missing from shadow blur shader"))
+            };
+            ShadowBlurUniforms {
+                texture: location("u_texture"),
+                direction: location("u_direction"),
+                region: location("u_region"),
+                sigma: location("u_sigma"),
+                taps: location("u_taps"),
             }
         };
 
@@ -258,23 +656,18 @@ impl GlowRenderer {
             let vbo = gl.create_buffer()?;
             let ebo = gl.create_buffer()?;
 
-            // Set up VAO with a single vec2 position attribute.
+            // Set up VAO with the position, normal, and coverage attributes
+            // (locations as in `shaders::VERTEX_ATTRIBUTES`).
             gl.bind_vertex_array(Some(vao));
             gl.bind_buffer(glow::ARRAY_BUFFER, Some(vbo));
             gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, Some(ebo));
-            gl.enable_vertex_attrib_array(0);
-            gl.vertex_attrib_pointer_f32(
-                0,
-                2,
-                glow::FLOAT,
-                false,
-                // Vertex is 8 bytes — well within i32 range.
-                #[expect(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
-                {
-                    std::mem::size_of::<Vertex>() as i32
-                },
-                0,
-            );
+            // Vertex is 20 bytes — well within i32 range.
+            #[expect(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+            let stride = std::mem::size_of::<Vertex>() as i32;
+            for (location, size, offset) in [(0, 2, 0), (1, 2, 8), (2, 1, 16)] {
+                gl.enable_vertex_attrib_array(location);
+                gl.vertex_attrib_pointer_f32(location, size, glow::FLOAT, false, stride, offset);
+            }
             gl.bind_vertex_array(None);
 
             (vao, vbo, ebo)
@@ -289,6 +682,17 @@ impl GlowRenderer {
             let msaa_renderbuffer = gl.create_renderbuffer()?;
             (fbo, fbo_texture, msaa_framebuffer, msaa_renderbuffer)
         };
+        let (composite_fbo, composite_texture) =
+            unsafe { (gl.create_framebuffer()?, gl.create_texture()?) };
+        let (empty_vao, ssaa_texture) =
+            unsafe { (gl.create_vertex_array()?, gl.create_texture()?) };
+        let atlas_texture = unsafe { gl.create_texture()? };
+        let (shadow_fbos, shadow_textures) = unsafe {
+            (
+                [gl.create_framebuffer()?, gl.create_framebuffer()?],
+                [gl.create_texture()?, gl.create_texture()?],
+            )
+        };
 
         let mut allocator = GlAllocator::new();
         let scene_manager = SceneManager::new(&mut allocator);
@@ -301,25 +705,314 @@ impl GlowRenderer {
             path_uniforms,
             image_program,
             image_uniforms,
+            text_program,
+            text_uniforms,
+            text_rendering: TextRendering::default(),
+            glyph_atlas: RefCell::new(GlyphAtlas::new()),
+            atlas_texture,
+            dithering: Dithering::None,
+            output_format: OutputFormat::default(),
+            sdr_white_level: SCRGB_WHITE_NITS,
+            stroke_style: StrokeStyle::default(),
+            text_outline: None,
+            text_shadow: TextShadow::default(),
+            shadow_program,
+            shadow_uniforms,
+            shadow_fbos,
+            shadow_textures,
+            downsample_program,
+            downsample_uniforms,
             vao,
+            empty_vao,
             vbo,
             ebo,
             fbo,
             fbo_texture,
             msaa_fbo: msaa_framebuffer,
             msaa_rbo: msaa_renderbuffer,
+            ssaa_texture,
+            antialiasing: Antialiasing::default(),
+            composite_fbo,
+            composite_texture,
+            composite_mode: CompositeMode::default(),
+            alpha_mode: AlphaMode::default(),
+            color_matrix: ColorMatrix::IDENTITY,
+            output_mask: OutputMask::NONE,
+            chroma_key: None,
+            output_transform: OutputTransform::IDENTITY,
+            scale_factor: 1.0,
+            layout_transform: OutputTransform::IDENTITY,
             fbo_size: [0, 0],
+            max_target_dimension,
             bottom_layer_dirty: true,
+            frame_stats: Cell::default(),
+            opacity: LayoutOpacity::OPAQUE,
             blur_cache: None,
         })
     }
 
+    /// Set how the finished layout is combined with the caller's
+    /// framebuffer. Defaults to [`CompositeMode::Replace`].
+    pub fn set_composite_mode(&mut self, mode: CompositeMode) {
+        self.composite_mode = mode;
+    }
+
+    /// Set whether the finished layout is written to the caller's
+    /// framebuffer with premultiplied or straight alpha. Defaults to
+    /// [`AlphaMode::Premultiplied`].
+    ///
+    /// Straight alpha replaces the final framebuffer blit with a textured
+    /// quad that divides the colors by their alpha.
+    pub fn set_alpha_mode(&mut self, alpha_mode: AlphaMode) {
+        self.alpha_mode = alpha_mode;
+    }
+
+    /// Set the color matrix applied to the finished frame as it is written
+    /// to the caller's framebuffer, e.g. [`ColorMatrix::GRAYSCALE`] or a
+    /// [daltonization](ColorMatrix::daltonize) for colorblind viewers.
+    /// Defaults to [`ColorMatrix::IDENTITY`].
+    ///
+    /// Applied at composite time, so changing it does not re-render the
+    /// cached bottom layer. Any other matrix replaces the final framebuffer
+    /// blit with a textured quad.
+    pub fn set_color_matrix(&mut self, color_matrix: ColorMatrix) {
+        self.color_matrix = color_matrix;
+    }
+
+    /// Set the rounded-rectangle mask and border applied to the finished
+    /// frame as it is written to the caller's framebuffer, e.g. for a
+    /// borderless transparent window. Defaults to [`OutputMask::NONE`].
+    ///
+    /// Applied at composite time, so changing it does not re-render the
+    /// cached bottom layer. Any other mask replaces the final framebuffer
+    /// blit with a textured quad.
+    pub fn set_output_mask(&mut self, mask: OutputMask) {
+        self.output_mask = mask;
+    }
+
+    /// Replace the layout's background with an opaque [`ChromaKey`] color,
+    /// for capture setups that key it out instead of capturing alpha, or
+    /// `None` to draw the background again (the default).
+    ///
+    /// While set, [`render`](Self::render) ignores the layout's background
+    /// and fills the viewport with the key wherever the layout does not
+    /// cover it. Changing whether a key is set re-renders the cached bottom
+    /// layer on the next frame; changing the key itself does not.
+    pub fn set_chroma_key(&mut self, chroma_key: Option<ChromaKey>) {
+        if self.chroma_key.is_some() != chroma_key.is_some() {
+            self.bottom_layer_dirty = true;
+        }
+        self.chroma_key = chroma_key;
+    }
+
+    /// Set the affine transform applied to the whole layout, e.g. to rotate
+    /// it for a vertical monitor or mirror it for a camera overlay.
+    ///
+    /// The transform maps the layout, scaled to physical pixels by the
+    /// [scale factor](Self::set_scale_factor), to viewport pixels (y pointing
+    /// down, as in the layout). If it turns the layout on its side, the
+    /// layout is laid out with the viewport's width and height swapped (see
+    /// [`OutputTransform::layout_size`]). Changing the transform re-renders
+    /// the cached bottom layer on the next frame.
+    pub fn set_output_transform(&mut self, transform: OutputTransform) {
+        if self.output_transform != transform {
+            self.output_transform = transform;
+            self.layout_transform = layout_transform(&transform, self.scale_factor);
+            self.bottom_layer_dirty = true;
+        }
+    }
+
+    /// Set how text labels are drawn. Defaults to
+    /// [`TextRendering::Geometry`].
+    ///
+    /// Changing the mode re-renders the cached bottom layer on the next
+    /// frame.
+    pub fn set_text_rendering(&mut self, text_rendering: TextRendering) {
+        if self.text_rendering != text_rendering {
+            self.text_rendering = text_rendering;
+            self.bottom_layer_dirty = true;
+        }
+    }
+
+    /// Set the dither added to colors to hide banding in gradients and
+    /// blurred backgrounds. Defaults to [`Dithering::None`].
+    ///
+    /// Changing it re-renders the cached bottom layer on the next frame.
+    pub fn set_dithering(&mut self, dithering: Dithering) {
+        if self.dithering != dithering {
+            self.dithering = dithering;
+            self.bottom_layer_dirty = true;
+        }
+    }
+
+    /// Set the pixel format of the framebuffer the layout is rendered into.
+    /// Defaults to [`OutputFormat::Rgba8`].
+    ///
+    /// The off-screen targets use the same format, so 10-bit and scRGB
+    /// outputs keep their precision through the cached bottom layer.
+    /// Switching formats reallocates the off-screen framebuffers on the next
+    /// frame.
+    pub fn set_output_format(&mut self, output_format: OutputFormat) {
+        if self.output_format != output_format {
+            self.output_format = output_format;
+            // Force `render` to reallocate the off-screen targets.
+            self.fbo_size = [0, 0];
+            self.bottom_layer_dirty = true;
+        }
+    }
+
+    /// Set how bright, in nits, the layout's white is shown on
+    /// [`OutputFormat::Rgba16Float`] outputs. Defaults to
+    /// [`SCRGB_WHITE_NITS`], which leaves colors unchanged.
+    ///
+    /// HDR desktops show SDR content brighter than scRGB's reference white,
+    /// so pass their SDR white level to keep the layout from looking dim
+    /// next to other windows. Other formats ignore the setting. Changing it
+    /// re-renders the cached bottom layer on the next frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `nits` is not positive and finite.
+    pub fn set_sdr_white_level(&mut self, nits: f32) {
+        assert!(
+            nits.is_finite() && nits > 0.0,
+            "SDR white level must be positive and finite, got {nits}"
+        );
+        if (self.sdr_white_level - nits).abs() > f32::EPSILON {
+            self.sdr_white_level = nits;
+            self.bottom_layer_dirty = true;
+        }
+    }
+
+    /// Set how the layout's stroked paths are joined, capped, and dashed.
+    /// Defaults to [`StrokeStyle::default`].
+    ///
+    /// The style applies to every stroke in the layout, but not to text
+    /// outlines. Changing it re-renders the cached bottom layer on the next
+    /// frame.
+    pub fn set_stroke_style(&mut self, stroke_style: StrokeStyle) {
+        if self.stroke_style != stroke_style {
+            self.stroke_style = stroke_style;
+            self.bottom_layer_dirty = true;
+        }
+    }
+
+    /// Set the outline drawn around every label glyph, beneath its fill,
+    /// or `None` for no outline (the default).
+    ///
+    /// Changing the outline re-renders the cached bottom layer on the next
+    /// frame.
+    pub fn set_text_outline(&mut self, text_outline: Option<TextOutline>) {
+        if self.text_outline != text_outline {
+            self.text_outline = text_outline;
+            self.bottom_layer_dirty = true;
+        }
+    }
+
+    /// Set the shape of the shadows drawn under labels. Defaults to the
+    /// hard-edged shadow of [`TextShadow::default`].
+    ///
+    /// Whether a label has a shadow, and its color, is still up to the
+    /// layout. Changing the shape re-renders the cached bottom layer on the
+    /// next frame.
+    pub fn set_text_shadow(&mut self, text_shadow: TextShadow) {
+        if self.text_shadow != text_shadow {
+            self.text_shadow = text_shadow;
+            self.bottom_layer_dirty = true;
+        }
+    }
+
+    /// Set how path and image edges are antialiased. Defaults to
+    /// [`Antialiasing::Msaa`].
+    ///
+    /// Switching modes reallocates the off-screen framebuffers on the next
+    /// frame.
+    pub fn set_antialiasing(&mut self, antialiasing: Antialiasing) {
+        if self.antialiasing != antialiasing {
+            self.antialiasing = antialiasing;
+            // Force `render` to reallocate the off-screen targets.
+            self.fbo_size = [0, 0];
+            self.bottom_layer_dirty = true;
+        }
+    }
+
+    /// Set the high-DPI scale factor (physical pixels per logical unit), e.g.
+    /// `1.5` or `2.0` on a 150% or 200% scaled display.
+    ///
+    /// The viewport stays in physical pixels and the layout is rasterized
+    /// at full physical resolution, but it is laid out in logical units:
+    /// text and component sizes match what the layout would get on an
+    /// unscaled display, and the size hint returned when rendering is in
+    /// logical units too. Defaults to `1.0`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale_factor` is not a positive, finite number.
+    pub fn set_scale_factor(&mut self, scale_factor: f32) {
+        assert!(
+            scale_factor.is_finite() && scale_factor > 0.0,
+            "scale factor must be positive and finite, got {scale_factor}"
+        );
+        if (self.scale_factor - scale_factor).abs() > f32::EPSILON {
+            self.scale_factor = scale_factor;
+            self.layout_transform = layout_transform(&self.output_transform, scale_factor);
+            self.bottom_layer_dirty = true;
+        }
+    }
+
+    /// Set the opacity of the whole layout, from `0.0` (invisible) to `1.0`
+    /// (opaque, the default), stopping any running fade.
+    ///
+    /// The opacity fades the finished frame as a whole in the final pass,
+    /// so overlapping entities do not show through each other, and changing
+    /// it does not re-render the cached layer. Any opacity below `1.0`
+    /// always goes through the intermediate final pass.
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.opacity.set(opacity);
+    }
+
+    /// The opacity of the whole layout, as of the last frame rendered.
+    pub fn opacity(&self) -> f32 {
+        self.opacity.get()
+    }
+
+    /// Start fading the whole layout from its current opacity to `opacity`
+    /// over `duration`, e.g. to fade the splits out when the timer is reset.
+    ///
+    /// The fade advances each time the layout is [rendered](Self::render);
+    /// keep rendering frames while [`is_fading`](Self::is_fading).
+    pub fn fade_to(&mut self, opacity: f32, duration: Duration) {
+        self.opacity.fade_to(opacity, duration, Instant::now());
+    }
+
+    /// Whether a fade started with [`fade_to`](Self::fade_to) is still
+    /// running.
+    pub fn is_fading(&self) -> bool {
+        self.opacity.is_fading()
+    }
+
+    /// Counts of the last frame: how many entities were drawn, how many
+    /// were culled because they lie entirely outside the viewport, and how
+    /// many strokes hit or missed the stroke cache.
+    pub fn frame_stats(&self) -> FrameStats {
+        self.frame_stats.get()
+    }
+
     /// Render the layout into the currently-bound framebuffer (typically the
     /// default framebuffer / screen).
     ///
+    /// `viewport` selects the rectangle of the framebuffer the layout is
+    /// drawn into; pass a plain `[width, height]` to cover the whole
+    /// framebuffer. Its origin is the top-left corner, as for
+    /// [`WgpuRenderer`](crate::WgpuRenderer); the height of the framebuffer
+    /// is taken from the caller's current `glViewport`, which should cover
+    /// the whole framebuffer. Pixels outside the viewport are left
+    /// untouched, and the caller's viewport and scissor state are restored
+    /// afterwards.
+    ///
     /// Returns an optional new resolution hint from livesplit-core's layout
-    /// engine, indicating the layout's preferred size changed. The caller
-    /// can use this to resize the window or viewport.
+    /// engine, in logical units, indicating the layout's preferred size
+    /// changed. The caller can use this to resize the window or viewport.
     ///
     /// # Safety
     ///
@@ -330,17 +1023,27 @@ impl GlowRenderer {
         &mut self,
         state: &LayoutState,
         image_cache: &ImageCache,
-        [width, height]: [u32; 2],
+        viewport: impl Into<Viewport>,
         draw_background: bool,
     ) -> Option<[f32; 2]> {
-        if width == 0 || height == 0 {
+        self.frame_stats.set(FrameStats::default());
+        self.opacity.advance(Instant::now());
+        // A chroma key takes the background's place.
+        let draw_background = draw_background && self.chroma_key.is_none();
+        let viewport = viewport.into();
+        if viewport.is_empty() {
             return None;
         }
+        let [width, height] = viewport.size();
 
         // Precision loss is acceptable: viewport dimensions are small
         // relative to f32 mantissa range.
         #[expect(clippy::cast_precision_loss)]
         let resolution = [width as f32, height as f32];
+        // The layout is laid out in logical units, possibly on its side (see
+        // `OutputTransform::layout_size`).
+        let layout_resolution =
+            layout_resolution([width, height], &self.output_transform, self.scale_factor);
 
         // Ensure FBOs match the viewport size.
         if self.fbo_size != [width, height] {
@@ -348,9 +1051,12 @@ impl GlowRenderer {
             self.bottom_layer_dirty = true;
         }
 
-        let new_resolution =
-            self.scene_manager
-                .update_scene(&mut self.allocator, resolution, state, image_cache);
+        let new_resolution = self.scene_manager.update_scene(
+            &mut self.allocator,
+            layout_resolution,
+            state,
+            image_cache,
+        );
 
         // Pre-compute blur before starting render passes (needs &mut self).
         // Extract the blur parameters while scene is borrowed, then drop
@@ -358,12 +1064,8 @@ impl GlowRenderer {
         let blur_params = {
             let scene = self.scene_manager.scene();
             if scene.bottom_layer_changed() || self.bottom_layer_dirty {
-                match scene.background() {
-                    Some(Background::Image(bg_image, _)) if bg_image.blur > 0.0 => {
-                        Some((Arc::clone(&bg_image.image.data), bg_image.blur))
-                    }
-                    _ => None,
-                }
+                blurred_background(scene.background().as_ref().filter(|_| draw_background))
+                    .map(|(image, blur)| (Arc::clone(&image.data), blur))
             } else {
                 None
             }
@@ -372,6 +1074,8 @@ impl GlowRenderer {
             unsafe { self.update_blur_cache(&image_data, blur_value) };
         }
 
+        self.glyph_atlas.get_mut().begin_frame();
+
         let scene = self.scene_manager.scene();
         let bottom_layer_changed = scene.bottom_layer_changed();
 
@@ -379,6 +1083,12 @@ impl GlowRenderer {
 
         // Save caller's GL state that we will modify.
         let scissor_was_enabled = unsafe { gl.is_enabled(glow::SCISSOR_TEST) };
+        let mut caller_viewport = [0; 4];
+        let mut caller_scissor = [0; 4];
+        unsafe {
+            gl.get_parameter_i32_slice(glow::VIEWPORT, &mut caller_viewport);
+            gl.get_parameter_i32_slice(glow::SCISSOR_BOX, &mut caller_scissor);
+        }
         // Save the framebuffer the caller wants us to render into (usually the
         // default FB / 0, but could be an intermediate FBO from egui).
         let caller_fbo = unsafe {
@@ -396,39 +1106,45 @@ impl GlowRenderer {
 
         let w = gl_size(width);
         let h = gl_size(height);
+        // Off-screen targets are larger than the viewport when supersampling.
+        let [target_width, target_height] = self
+            .antialiasing
+            .target_size([width, height], self.max_target_dimension);
+        let tw = gl_size(target_width);
+        let th = gl_size(target_height);
 
         if bottom_layer_changed || self.bottom_layer_dirty {
-            // Render bottom layer into MSAA FBO.
+            // Render bottom layer into MSAA FBO, at full opacity.
             unsafe {
                 gl.bind_framebuffer(glow::FRAMEBUFFER, Some(self.msaa_fbo));
-                gl.viewport(0, 0, w, h);
+                gl.viewport(0, 0, tw, th);
                 gl.clear_color(0.0, 0.0, 0.0, 0.0);
                 gl.clear(glow::COLOR_BUFFER_BIT);
             }
 
-            if draw_background {
-                if let Some(bg) = scene.background() {
-                    unsafe { self.render_background(bg, resolution) };
-                }
-            }
-
-            for entity in scene.bottom_layer() {
-                unsafe { self.render_entity(entity, resolution) };
-            }
+            let rectangle = scene.rectangle();
+            let list = DisplayList::bottom_layer(
+                &self.display_settings(resolution, layout_resolution),
+                scene.background().as_ref().filter(|_| draw_background),
+                rectangle.as_ref(),
+                scene.bottom_layer(),
+            );
+            unsafe { self.draw_display_list(&list, resolution) };
 
-            // Resolve MSAA to cached texture.
+            // Resolve MSAA to cached texture (a plain copy when
+            // supersampling).
             unsafe {
                 gl.bind_framebuffer(glow::READ_FRAMEBUFFER, Some(self.msaa_fbo));
                 gl.bind_framebuffer(glow::DRAW_FRAMEBUFFER, Some(self.fbo));
                 gl.blit_framebuffer(
                     0,
                     0,
-                    w,
-                    h,
+                    tw,
+                    th,
                     0,
                     0,
-                    w,
-                    h,
+                    tw,
+                    th,
                     glow::COLOR_BUFFER_BIT,
                     glow::NEAREST,
                 );
@@ -440,36 +1156,94 @@ impl GlowRenderer {
         // Composite: blit cached bottom layer + render top layer into MSAA FBO.
         unsafe {
             gl.bind_framebuffer(glow::FRAMEBUFFER, Some(self.msaa_fbo));
-            gl.viewport(0, 0, w, h);
+            gl.viewport(0, 0, tw, th);
             gl.clear_color(0.0, 0.0, 0.0, 0.0);
             gl.clear(glow::COLOR_BUFFER_BIT);
         }
 
-        // Draw cached bottom layer texture into MSAA FBO.
-        unsafe { self.blit_fbo(resolution) };
-
-        // Render top layer into MSAA FBO.
-        for entity in scene.top_layer() {
-            unsafe { self.render_entity(entity, resolution) };
+        // Draw cached bottom layer texture into MSAA FBO, and the top layer
+        // over it, at full opacity; the final pass fades the whole frame.
+        let list = DisplayList::top_layer(
+            &self.display_settings(resolution, layout_resolution),
+            scene.top_layer(),
+        );
+        unsafe { self.draw_display_list(&list, resolution) };
+
+        // Resolve MSAA into the viewport rectangle of the caller's
+        // framebuffer. The scissor keeps the resolve from touching anything
+        // outside the viewport. The viewport's origin is its top-left corner,
+        // while GL window coordinates start at the bottom-left, so flip it
+        // using the framebuffer height (the top edge of the caller's
+        // viewport).
+        let framebuffer_height = caller_viewport[1] + caller_viewport[3];
+        let x = gl_size(viewport.x);
+        let y = framebuffer_height - gl_size(viewport.y) - h;
+        if let Antialiasing::Supersample { filter, .. } = self.antialiasing {
+            // The supersampled frame is filtered down to the viewport by
+            // drawing it as a full-viewport triangle.
+            unsafe {
+                gl.bind_framebuffer(glow::FRAMEBUFFER, caller_fbo);
+                gl.viewport(x, y, w, h);
+                gl.enable(glow::SCISSOR_TEST);
+                gl.scissor(x, y, w, h);
+                self.set_output_blend();
+                self.draw_downsample(filter, resolution);
+            }
+        } else if self.composite_mode == CompositeMode::Replace
+            && self.alpha_mode == AlphaMode::Premultiplied
+            && self.color_matrix.is_identity()
+            && self.output_mask.is_none()
+            && self.chroma_key.is_none()
+            && self.opacity.get() >= 1.0
+        {
+            unsafe {
+                gl.bind_framebuffer(glow::READ_FRAMEBUFFER, Some(self.msaa_fbo));
+                gl.bind_framebuffer(glow::DRAW_FRAMEBUFFER, caller_fbo);
+                gl.enable(glow::SCISSOR_TEST);
+                gl.scissor(x, y, w, h);
+                gl.blit_framebuffer(
+                    0,
+                    0,
+                    w,
+                    h,
+                    x,
+                    y,
+                    x + w,
+                    y + h,
+                    glow::COLOR_BUFFER_BIT,
+                    glow::NEAREST,
+                );
+            }
+        } else {
+            // blit_framebuffer overwrites the destination and copies the
+            // premultiplied pixels as is, so resolve into a texture first and
+            // draw it into the caller's pixels as a textured quad (which
+            // applies the alpha mode, output mask, color matrix, and
+            // opacity).
+            unsafe {
+                gl.bind_framebuffer(glow::READ_FRAMEBUFFER, Some(self.msaa_fbo));
+                gl.bind_framebuffer(glow::DRAW_FRAMEBUFFER, Some(self.composite_fbo));
+                gl.blit_framebuffer(
+                    0,
+                    0,
+                    w,
+                    h,
+                    0,
+                    0,
+                    w,
+                    h,
+                    glow::COLOR_BUFFER_BIT,
+                    glow::NEAREST,
+                );
+                gl.bind_framebuffer(glow::FRAMEBUFFER, caller_fbo);
+                gl.viewport(x, y, w, h);
+                gl.enable(glow::SCISSOR_TEST);
+                gl.scissor(x, y, w, h);
+                self.set_output_blend();
+            }
+            unsafe { self.blit_texture(self.composite_texture, resolution, true) };
         }
-
-        // Resolve MSAA to the caller's framebuffer.
         unsafe {
-            gl.bind_framebuffer(glow::READ_FRAMEBUFFER, Some(self.msaa_fbo));
-            gl.bind_framebuffer(glow::DRAW_FRAMEBUFFER, caller_fbo);
-            gl.blit_framebuffer(
-                0,
-                0,
-                w,
-                h,
-                0,
-                0,
-                w,
-                h,
-                glow::COLOR_BUFFER_BIT,
-                glow::NEAREST,
-            );
-
             gl.disable(glow::BLEND);
             // Ensure the blit completes before the caller (or the windowing
             // system) touches the framebuffer — some X11 drivers need this.
@@ -479,52 +1253,96 @@ impl GlowRenderer {
         // Restore caller's GL state.
         unsafe {
             gl.bind_framebuffer(glow::FRAMEBUFFER, caller_fbo);
-        }
-        if scissor_was_enabled {
-            unsafe { gl.enable(glow::SCISSOR_TEST) };
+            let [vx, vy, vw, vh] = caller_viewport;
+            gl.viewport(vx, vy, vw, vh);
+            let [sx, sy, sw, sh] = caller_scissor;
+            gl.scissor(sx, sy, sw, sh);
+            if !scissor_was_enabled {
+                gl.disable(glow::SCISSOR_TEST);
+            }
         }
 
         new_resolution
     }
 
-    /// Render a single scene entity.
-    unsafe fn render_entity(
+    /// Draw the items of a display list into the current framebuffer, and
+    /// count its entities in the frame's statistics.
+    unsafe fn draw_display_list(&self, list: &DisplayList<'_, GlImage>, resolution: [f32; 2]) {
+        for item in list.items() {
+            match item {
+                DisplayItem::CachedLayer => unsafe {
+                    self.blit_texture(self.fbo_texture, resolution, false);
+                },
+                DisplayItem::Path {
+                    path,
+                    fill,
+                    transform,
+                    dilation,
+                } => unsafe { self.draw_path(path, fill, transform, resolution, *dilation) },
+                DisplayItem::Glyph {
+                    path,
+                    fill,
+                    transform,
+                    effects,
+                } => unsafe {
+                    self.draw_glyph(path, fill, transform, resolution, effects.as_ref());
+                },
+                DisplayItem::Shadow(shadow) => unsafe {
+                    self.draw_blurred_shadow(shadow, resolution);
+                },
+                DisplayItem::Image(image) => unsafe { self.draw_image(image, resolution) },
+            }
+        }
+        let mut stats = self.frame_stats.get();
+        stats.add(list.stats());
+        self.frame_stats.set(stats);
+    }
+
+    /// The settings display lists are built with, for a viewport of
+    /// `resolution` pixels and a layout of `layout_resolution` units.
+    fn display_settings(
         &self,
-        entity: &Entity<Option<GlPath>, GlImage, GlLabel>,
         resolution: [f32; 2],
-    ) {
-        match entity {
-            Entity::FillPath(path, shader, transform) => {
-                if let Some(path) = path.as_ref() {
-                    unsafe { self.draw_path(path, shader, transform, resolution) };
-                }
-            }
-            Entity::StrokePath(path, stroke_width, color, transform) => {
-                if let Some(path) = path.as_ref() {
-                    if let Some(stroked) = tessellate_stroke(path, *stroke_width) {
-                        let shader = FillShader::SolidColor(*color);
-                        unsafe { self.draw_path(&stroked, &shader, transform, resolution) };
-                    }
-                }
-            }
-            Entity::Image(image, transform) => {
-                unsafe { self.draw_image(image, transform, resolution) };
-            }
-            Entity::Label(label, shader, text_shadow, transform) => {
-                unsafe {
-                    self.draw_label(label, shader, text_shadow.as_ref(), transform, resolution);
-                };
-            }
+        layout_resolution: [f32; 2],
+    ) -> DisplaySettings<'_> {
+        DisplaySettings {
+            resolution,
+            layout_resolution,
+            layout_transform: &self.layout_transform,
+            scale_factor: self.scale_factor,
+            stroke_style: &self.stroke_style,
+            text_shadow: &self.text_shadow,
+            text_outline: self.text_outline,
+            text_rendering: self.text_rendering,
+            encoding: self.color_encoding(),
         }
     }
 
-    /// Draw a filled path with the given shader and transform.
+    /// How colors are written to the output format.
+    fn color_encoding(&self) -> ColorEncoding {
+        self.output_format.color_encoding(self.sdr_white_level)
+    }
+
+    /// Dither amplitude for the output format, or `0.0` if disabled.
+    fn dither_amplitude(&self) -> f32 {
+        self.dithering.amplitude(self.output_format)
+    }
+
+    /// The fill of `path` tessellated finely enough for the size it is
+    /// drawn at with `transform` (see [`curve_tolerance`]).
+    fn fill_for(&self, path: &GlPath, transform: &Transform) -> GlPath {
+        fill_at_tolerance(path, curve_tolerance(transform, &self.layout_transform))
+    }
+
+    /// Draw a tessellated path with the given fill and transform, grown by
+    /// `dilation` pixels along its edge normals.
     unsafe fn draw_path(
         &self,
         path: &GlPath,
-        shader: &FillShader,
+        fill: &Fill,
         transform: &Transform,
         resolution: [f32; 2],
+        dilation: f32,
     ) {
         let gl = &self.gl;
 
@@ -541,41 +1359,27 @@ impl GlowRenderer {
                 transform.scale_y,
             );
             gl.uniform_2_f32(Some(&self.path_uniforms.offset), transform.x, transform.y);
-
-            self.set_shader_uniforms(shader, path);
-            self.upload_and_draw(path);
-        }
-    }
-
-    /// Configure the fragment shader uniforms for a fill shader.
-    ///
-    /// For gradient shaders, we compute the bounding box of the path vertices
-    /// in local space to determine the interpolation range.
-    unsafe fn set_shader_uniforms(&self, shader: &FillShader, path: &GlPath) {
-        let gl = &self.gl;
-        let u = &self.path_uniforms;
-
-        unsafe {
-            match shader {
-                FillShader::SolidColor(color) => {
-                    gl.uniform_1_i32(Some(&u.shader_type), 0);
-                    gl.uniform_4_f32(Some(&u.color_a), color[0], color[1], color[2], color[3]);
-                }
-                FillShader::VerticalGradient(top, bottom) => {
-                    let [min, max] = vertex_bounds(&path.vertices, 1);
-                    gl.uniform_1_i32(Some(&u.shader_type), 1);
-                    gl.uniform_4_f32(Some(&u.color_a), top[0], top[1], top[2], top[3]);
-                    gl.uniform_4_f32(Some(&u.color_b), bottom[0], bottom[1], bottom[2], bottom[3]);
-                    gl.uniform_2_f32(Some(&u.bounds), min, max);
-                }
-                FillShader::HorizontalGradient(left, right) => {
-                    let [min, max] = vertex_bounds(&path.vertices, 0);
-                    gl.uniform_1_i32(Some(&u.shader_type), 2);
-                    gl.uniform_4_f32(Some(&u.color_a), left[0], left[1], left[2], left[3]);
-                    gl.uniform_4_f32(Some(&u.color_b), right[0], right[1], right[2], right[3]);
-                    gl.uniform_2_f32(Some(&u.bounds), min, max);
-                }
-            }
+            set_transform_uniforms(
+                gl,
+                [
+                    &self.path_uniforms.transform_x,
+                    &self.path_uniforms.transform_y,
+                ],
+                &self.layout_transform,
+            );
+            let fringe_width = self.antialiasing.fringe_width();
+            gl.uniform_1_f32(Some(&self.path_uniforms.fringe_width), fringe_width);
+            gl.uniform_1_f32(Some(&self.path_uniforms.dilation), dilation);
+            gl.uniform_1_f32(Some(&self.path_uniforms.dither), self.dither_amplitude());
+
+            let u = &self.path_uniforms;
+            set_fill_uniforms(
+                gl,
+                [&u.shader_type, &u.color_a, &u.color_b, &u.bounds],
+                fill,
+            );
+            let (vertices, indices) = path.draw_geometry(fringe_width > 0.0, dilation > 0.0);
+            self.upload_and_draw(vertices, indices);
         }
     }
 
@@ -584,7 +1388,7 @@ impl GlowRenderer {
     /// # Panics
     ///
     /// Panics if the index count exceeds `i32::MAX`.
-    unsafe fn upload_and_draw(&self, path: &GlPath) {
+    unsafe fn upload_and_draw(&self, vertices: &[Vertex], indices: &[u32]) {
         let gl = &self.gl;
 
         unsafe {
@@ -593,94 +1397,262 @@ impl GlowRenderer {
             gl.bind_buffer(glow::ARRAY_BUFFER, Some(self.vbo));
             gl.buffer_data_u8_slice(
                 glow::ARRAY_BUFFER,
-                bytemuck::cast_slice(&path.vertices),
+                bytemuck::cast_slice(vertices),
                 glow::STREAM_DRAW,
             );
 
             gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, Some(self.ebo));
             gl.buffer_data_u8_slice(
                 glow::ELEMENT_ARRAY_BUFFER,
-                bytemuck::cast_slice(&path.indices),
+                bytemuck::cast_slice(indices),
                 glow::STREAM_DRAW,
             );
 
-            let index_count =
-                i32::try_from(path.indices.len()).expect("index count exceeds i32::MAX");
+            let index_count = i32::try_from(indices.len()).expect("index count exceeds i32::MAX");
             gl.draw_elements(glow::TRIANGLES, index_count, glow::UNSIGNED_INT, 0);
 
             gl.bind_vertex_array(None);
         }
     }
 
-    /// Draw a text label (each glyph is a filled path).
-    ///
-    /// If `text_shadow` is set, a shadow pass is rendered first at a small
-    /// offset with the shadow color modulated by the label's alpha.
+    /// Draw a label's blurred shadow.
     ///
-    /// # Panics
-    ///
-    /// Panics if the label's internal [`RwLock`](std::sync::RwLock) is poisoned.
-    unsafe fn draw_label(
+    /// The shadow's masks, grown by its spread, are drawn into the first
+    /// shadow texture, blurred horizontally into the second and vertically
+    /// back into the first, and then drawn into the MSAA framebuffer in the
+    /// shadow's color with the text program. Each pass is limited by the
+    /// scissor to the label's region of the textures.
+    unsafe fn draw_blurred_shadow(&self, shadow: &ShadowItem, resolution: [f32; 2]) {
+        let Some([x, y, width, height]) =
+            shadow_region(shadow.bounds, shadow.margin(), self.fbo_size)
+        else {
+            return;
+        };
+
+        let gl = &self.gl;
+        // GL window coordinates count rows from the bottom.
+        let [sx, sy] = [gl_size(x), gl_size(self.fbo_size[1] - y - height)];
+        let [sw, sh] = [gl_size(width), gl_size(height)];
+
+        unsafe {
+            gl.bind_framebuffer(glow::FRAMEBUFFER, Some(self.shadow_fbos[0]));
+            gl.viewport(0, 0, gl_size(self.fbo_size[0]), gl_size(self.fbo_size[1]));
+            gl.enable(glow::SCISSOR_TEST);
+            gl.scissor(sx, sy, sw, sh);
+            gl.clear_color(0.0, 0.0, 0.0, 0.0);
+            gl.clear(glow::COLOR_BUFFER_BIT);
+        }
+
+        for (path, transform) in &shadow.masks {
+            unsafe { self.draw_path(path, &Fill::MASK, transform, resolution, shadow.spread) };
+        }
+
+        let u = &self.shadow_uniforms;
+        unsafe {
+            gl.disable(glow::BLEND);
+            gl.use_program(Some(self.shadow_program));
+            gl.uniform_4_i32(Some(&u.region), sx, sy, sx + sw, sy + sh);
+            gl.uniform_1_f32(Some(&u.sigma), shadow.sigma);
+            gl.uniform_1_i32(
+                Some(&u.taps),
+                i32::try_from(shadow.taps).expect("tap count exceeds i32::MAX"),
+            );
+            gl.active_texture(glow::TEXTURE0);
+            gl.uniform_1_i32(Some(&u.texture), 0);
+            gl.bind_vertex_array(Some(self.empty_vao));
+            for (source, target, [dx, dy]) in [(0, 1, [1, 0]), (1, 0, [0, 1])] {
+                gl.bind_framebuffer(glow::FRAMEBUFFER, Some(self.shadow_fbos[target]));
+                gl.bind_texture(glow::TEXTURE_2D, Some(self.shadow_textures[source]));
+                gl.uniform_2_i32(Some(&u.direction), dx, dy);
+                gl.draw_arrays(glow::TRIANGLES, 0, 3);
+            }
+            gl.bind_vertex_array(None);
+
+            gl.enable(glow::BLEND);
+            gl.disable(glow::SCISSOR_TEST);
+            gl.bind_framebuffer(glow::FRAMEBUFFER, Some(self.msaa_fbo));
+            let [target_width, target_height] = self
+                .antialiasing
+                .target_size(self.fbo_size, self.max_target_dimension);
+            gl.viewport(0, 0, gl_size(target_width), gl_size(target_height));
+        }
+
+        // Draw the blurred coverage as a quad over the region, which is
+        // already in viewport pixels.
+        // Precision loss is acceptable: texture dimensions are small
+        // relative to f32 mantissa range.
+        #[expect(clippy::cast_precision_loss)]
+        let [x, y, width, height] = [x as f32, y as f32, width as f32, height as f32];
+        let [size_x, size_y] = resolution;
+        let t = &self.text_uniforms;
+        unsafe {
+            gl.use_program(Some(self.text_program));
+            gl.uniform_2_f32(Some(&t.resolution), size_x, size_y);
+            gl.uniform_2_f32(Some(&t.scale), 1.0, 1.0);
+            gl.uniform_2_f32(Some(&t.offset), 0.0, 0.0);
+            set_transform_uniforms(
+                gl,
+                [&t.transform_x, &t.transform_y],
+                &OutputTransform::IDENTITY,
+            );
+            gl.uniform_4_f32(Some(&t.glyph_rect), x, y, width, height);
+            // The texture's rows run bottom to top.
+            gl.uniform_4_f32(
+                Some(&t.uv_rect),
+                x / size_x,
+                1.0 - y / size_y,
+                width / size_x,
+                -height / size_y,
+            );
+            gl.uniform_1_i32(Some(&t.atlas), 0);
+            gl.uniform_1_i32(Some(&t.shader_type), 0);
+            gl.uniform_4_f32_slice(Some(&t.color_a), &shadow.color);
+            gl.uniform_1_i32(Some(&t.coverage), 1);
+            gl.bind_texture(glow::TEXTURE_2D, Some(self.shadow_textures[0]));
+        }
+
+        let scene = self.scene_manager.scene();
+        let rect = scene.rectangle();
+        if let Some(path) = rect.as_ref() {
+            unsafe { self.upload_and_draw(&path.vertices, &path.indices) };
+        }
+
+        unsafe { gl.bind_texture(glow::TEXTURE_2D, None) };
+    }
+
+    /// Draw one glyph of a label from the glyph atlas: as a distance field
+    /// with `effects`, or as rasterized coverage without. Glyphs that are
+    /// not in the atlas are drawn as geometry.
+    unsafe fn draw_glyph(
         &self,
-        label: &LabelHandle<GlLabel>,
-        shader: &FillShader,
-        text_shadow: Option<&[f32; 4]>,
+        path: &GlPath,
+        fill: &Fill,
         transform: &Transform,
         resolution: [f32; 2],
+        effects: Option<&TextEffects>,
     ) {
-        let label = label.read().expect("label RwLock poisoned");
-
-        // Render shadow pass first.
-        if let Some(shadow_color) = text_shadow {
-            let alpha = match shader {
-                FillShader::SolidColor([.., a]) => *a,
-                FillShader::VerticalGradient([.., a1], [.., a2])
-                | FillShader::HorizontalGradient([.., a1], [.., a2]) => 0.5 * (a1 + a2),
-            };
-            let shadow_rgba = [
-                shadow_color[0],
-                shadow_color[1],
-                shadow_color[2],
-                shadow_color[3] * alpha,
-            ];
-            let shadow_shader = FillShader::SolidColor(shadow_rgba);
-            let shadow_transform = transform.pre_translate(SHADOW_OFFSET, SHADOW_OFFSET);
-
-            for glyph in label.glyphs() {
-                if let Some(path) = &glyph.path {
-                    let t = shadow_transform
-                        .pre_translate(glyph.x, glyph.y)
-                        .pre_scale(glyph.scale, glyph.scale);
-                    unsafe { self.draw_path(path, &shadow_shader, &t, resolution) };
-                }
+        if let Some(effects) = effects {
+            let glyph = self.glyph_atlas.borrow_mut().distance_field_glyph(path);
+            if let Some(glyph) = glyph {
+                unsafe {
+                    self.draw_atlas_glyph(&glyph, fill, transform, resolution, Some(effects));
+                };
+                return;
             }
-        }
-
-        // Render glyphs.
-        for glyph in label.glyphs() {
-            if let Some(path) = &glyph.path {
-                let t = transform
-                    .pre_translate(glyph.x, glyph.y)
-                    .pre_scale(glyph.scale, glyph.scale);
-                let glyph_shader = if let Some(color) = &glyph.color {
-                    FillShader::SolidColor(*color)
-                } else {
-                    *shader
+        } else {
+            let placement = place_rasterized_glyph(transform, &self.layout_transform);
+            let glyph = self
+                .glyph_atlas
+                .borrow_mut()
+                .coverage_glyph(path, &placement);
+            if let Some(glyph) = glyph {
+                unsafe {
+                    self.draw_atlas_glyph(&glyph, fill, &placement.transform, resolution, None);
                 };
-                unsafe { self.draw_path(path, &glyph_shader, &t, resolution) };
+                return;
             }
         }
+        let path = self.fill_for(path, transform);
+        unsafe { self.draw_path(&path, fill, transform, resolution, 0.0) };
     }
 
-    /// Draw an image entity as a textured quad.
-    unsafe fn draw_image(
+    /// Draw a glyph as a quad sampling the atlas, uploading the atlas first
+    /// if it changed. `effects` is `None` for rasterized coverage glyphs,
+    /// and the distance field effects, with resolved colors, otherwise.
+    unsafe fn draw_atlas_glyph(
         &self,
-        image: &Handle<GlImage>,
+        glyph: &AtlasGlyph,
+        fill: &Fill,
         transform: &Transform,
         resolution: [f32; 2],
+        effects: Option<&TextEffects>,
     ) {
         let gl = &self.gl;
-        let texture = unsafe { self.ensure_texture(image) };
+        let u = &self.text_uniforms;
+
+        unsafe {
+            gl.active_texture(glow::TEXTURE0);
+            gl.bind_texture(glow::TEXTURE_2D, Some(self.atlas_texture));
+            let mut atlas = self.glyph_atlas.borrow_mut();
+            if atlas.take_dirty() {
+                let size = gl_size(ATLAS_SIZE);
+                // Rows are single bytes; don't let GL assume 4-byte alignment.
+                gl.pixel_store_i32(glow::UNPACK_ALIGNMENT, 1);
+                // GL constant values are small enough that the casts are
+                // always safe.
+                #[expect(clippy::cast_possible_wrap)]
+                {
+                    gl.tex_image_2d(
+                        glow::TEXTURE_2D,
+                        0,
+                        glow::R8 as i32,
+                        size,
+                        size,
+                        0,
+                        glow::RED,
+                        glow::UNSIGNED_BYTE,
+                        PixelUnpackData::Slice(Some(atlas.pixels())),
+                    );
+                    gl.tex_parameter_i32(
+                        glow::TEXTURE_2D,
+                        glow::TEXTURE_MIN_FILTER,
+                        glow::LINEAR as i32,
+                    );
+                    gl.tex_parameter_i32(
+                        glow::TEXTURE_2D,
+                        glow::TEXTURE_MAG_FILTER,
+                        glow::LINEAR as i32,
+                    );
+                }
+                gl.pixel_store_i32(glow::UNPACK_ALIGNMENT, 4);
+            }
+            drop(atlas);
+
+            gl.use_program(Some(self.text_program));
+            gl.uniform_2_f32(Some(&u.resolution), resolution[0], resolution[1]);
+            gl.uniform_2_f32(Some(&u.scale), transform.scale_x, transform.scale_y);
+            gl.uniform_2_f32(Some(&u.offset), transform.x, transform.y);
+            set_transform_uniforms(gl, [&u.transform_x, &u.transform_y], &self.layout_transform);
+            gl.uniform_4_f32_slice(Some(&u.glyph_rect), &glyph.rect);
+            gl.uniform_4_f32_slice(Some(&u.uv_rect), &glyph.uv);
+            gl.uniform_1_i32(Some(&u.atlas), 0);
+            set_fill_uniforms(
+                gl,
+                [&u.shader_type, &u.color_a, &u.color_b, &u.bounds],
+                fill,
+            );
+            gl.uniform_1_i32(Some(&u.coverage), i32::from(effects.is_none()));
+            let effects = effects.copied().unwrap_or_default();
+            gl.uniform_1_f32(Some(&u.outline_width), effects.outline_width);
+            gl.uniform_4_f32_slice(Some(&u.outline_color), &effects.outline_color);
+            gl.uniform_1_f32(Some(&u.glow_radius), effects.glow_radius);
+            gl.uniform_4_f32_slice(Some(&u.glow_color), &effects.glow_color);
+        }
+
+        // Stretch the scene's unit rectangle over the glyph's quad.
+        let scene = self.scene_manager.scene();
+        let rect = scene.rectangle();
+        if let Some(path) = rect.as_ref() {
+            unsafe { self.upload_and_draw(&path.vertices, &path.indices) };
+        }
+
+        unsafe { gl.bind_texture(glow::TEXTURE_2D, None) };
+    }
+
+    /// Draw an image or background image as a textured quad.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the GL context has been lost.
+    unsafe fn draw_image(&self, item: &ImageItem<'_, GlImage>, resolution: [f32; 2]) {
+        let gl = &self.gl;
+        let transform = &item.transform;
+
+        // Blurred background images use the pre-computed blurred copy.
+        let texture = match &self.blur_cache {
+            Some(cache) if item.blurred => cache.texture,
+            _ => unsafe { self.ensure_texture(item.image) },
+        };
 
         unsafe {
             gl.use_program(Some(self.image_program));
@@ -695,10 +1667,24 @@ impl GlowRenderer {
                 transform.scale_y,
             );
             gl.uniform_2_f32(Some(&self.image_uniforms.offset), transform.x, transform.y);
+            set_transform_uniforms(
+                gl,
+                [
+                    &self.image_uniforms.transform_x,
+                    &self.image_uniforms.transform_y,
+                ],
+                &self.layout_transform,
+            );
             gl.uniform_1_i32(Some(&self.image_uniforms.flip_uv_y), 0);
-            gl.uniform_1_f32(Some(&self.image_uniforms.brightness), 1.0);
-            gl.uniform_1_f32(Some(&self.image_uniforms.opacity), 1.0);
+            gl.uniform_1_f32(Some(&self.image_uniforms.brightness), item.brightness);
+            gl.uniform_1_f32(Some(&self.image_uniforms.opacity), item.opacity);
             gl.uniform_1_i32(Some(&self.image_uniforms.already_premultiplied), 0);
+            gl.uniform_1_i32(
+                Some(&self.image_uniforms.linearize),
+                i32::from(self.color_encoding().is_linear()),
+            );
+            self.set_output_uniforms(&self.image_uniforms.output, None);
+            gl.uniform_1_f32(Some(&self.image_uniforms.dither), self.dither_amplitude());
 
             gl.active_texture(glow::TEXTURE0);
             gl.bind_texture(glow::TEXTURE_2D, Some(texture));
@@ -709,7 +1695,7 @@ impl GlowRenderer {
         let scene = self.scene_manager.scene();
         let rect = scene.rectangle();
         if let Some(path) = rect.as_ref() {
-            unsafe { self.upload_and_draw(path) };
+            unsafe { self.upload_and_draw(&path.vertices, &path.indices) };
         }
 
         unsafe { gl.bind_texture(glow::TEXTURE_2D, None) };
@@ -785,85 +1771,6 @@ impl GlowRenderer {
         }
     }
 
-    /// Render the scene background (solid color, gradient, or image fill).
-    unsafe fn render_background(&self, background: &Background<GlImage>, resolution: [f32; 2]) {
-        match background {
-            Background::Shader(shader) => {
-                // Full-screen quad using the scene rectangle.
-                let transform = Transform {
-                    scale_x: resolution[0],
-                    scale_y: resolution[1],
-                    x: 0.0,
-                    y: 0.0,
-                };
-                let scene = self.scene_manager.scene();
-                let rect = scene.rectangle();
-                if let Some(path) = rect.as_ref() {
-                    unsafe { self.draw_path(path, shader, &transform, resolution) };
-                }
-            }
-            Background::Image(bg_image, transform) => {
-                unsafe { self.draw_background_image(bg_image, transform, resolution) };
-            }
-        }
-    }
-
-    /// Draw a background image with brightness, opacity, and optional blur.
-    ///
-    /// # Panics
-    ///
-    /// Panics if the GL context has been lost.
-    unsafe fn draw_background_image(
-        &self,
-        bg_image: &BackgroundImage<Handle<GlImage>>,
-        transform: &Transform,
-        resolution: [f32; 2],
-    ) {
-        let gl = &self.gl;
-
-        // Determine which texture to use: blurred (from pre-computed cache) or original.
-        let texture = if bg_image.blur > 0.0 {
-            self.blur_cache.as_ref().map_or_else(
-                || unsafe { self.ensure_texture(&bg_image.image) },
-                |c| c.texture,
-            )
-        } else {
-            unsafe { self.ensure_texture(&bg_image.image) }
-        };
-
-        unsafe {
-            gl.use_program(Some(self.image_program));
-            gl.uniform_2_f32(
-                Some(&self.image_uniforms.resolution),
-                resolution[0],
-                resolution[1],
-            );
-            gl.uniform_2_f32(
-                Some(&self.image_uniforms.scale),
-                transform.scale_x,
-                transform.scale_y,
-            );
-            gl.uniform_2_f32(Some(&self.image_uniforms.offset), transform.x, transform.y);
-            gl.uniform_1_i32(Some(&self.image_uniforms.flip_uv_y), 0);
-            gl.uniform_1_f32(Some(&self.image_uniforms.brightness), bg_image.brightness);
-            gl.uniform_1_f32(Some(&self.image_uniforms.opacity), bg_image.opacity);
-            gl.uniform_1_i32(Some(&self.image_uniforms.already_premultiplied), 0);
-
-            gl.active_texture(glow::TEXTURE0);
-            gl.bind_texture(glow::TEXTURE_2D, Some(texture));
-            gl.uniform_1_i32(Some(&self.image_uniforms.texture), 0);
-        }
-
-        // Draw the scene's unit rectangle with this texture.
-        let scene = self.scene_manager.scene();
-        let rect = scene.rectangle();
-        if let Some(path) = rect.as_ref() {
-            unsafe { self.upload_and_draw(path) };
-        }
-
-        unsafe { gl.bind_texture(glow::TEXTURE_2D, None) };
-    }
-
     /// Pre-compute the blurred background texture if needed.
     ///
     /// Called before the render passes while we still have `&mut self`,
@@ -936,9 +1843,84 @@ impl GlowRenderer {
         });
     }
 
-    /// Blit the cached bottom-layer FBO texture to the current framebuffer as
-    /// a fullscreen textured quad.
-    unsafe fn blit_fbo(&self, resolution: [f32; 2]) {
+    /// Set up how the finished frame is blended into the caller's
+    /// framebuffer, for the current [`CompositeMode`] and [`AlphaMode`].
+    unsafe fn set_output_blend(&self) {
+        let gl = &self.gl;
+        unsafe {
+            match (self.composite_mode, self.alpha_mode) {
+                (CompositeMode::Replace, _) => gl.disable(glow::BLEND),
+                (CompositeMode::Blend, AlphaMode::Premultiplied) => {
+                    gl.blend_func(glow::ONE, glow::ONE_MINUS_SRC_ALPHA);
+                }
+                (CompositeMode::Blend, AlphaMode::Straight) => gl.blend_func_separate(
+                    glow::SRC_ALPHA,
+                    glow::ONE_MINUS_SRC_ALPHA,
+                    glow::ONE,
+                    glow::ONE_MINUS_SRC_ALPHA,
+                ),
+            }
+        }
+    }
+
+    /// Configure the output uniforms of the program in use. In the final
+    /// pass into the caller's framebuffer, whose viewport is `final_size`
+    /// pixels, they apply the output mask, color matrix, layout opacity,
+    /// chroma key, and alpha mode; otherwise they leave the colors as they
+    /// are.
+    unsafe fn set_output_uniforms(&self, u: &OutputUniforms, final_size: Option<[f32; 2]>) {
+        let gl = &self.gl;
+        let encoding = self.color_encoding();
+        let (color_matrix, mask, opacity, key, straight, size) = match final_size {
+            Some(size) => (
+                &self.color_matrix,
+                &self.output_mask,
+                self.opacity.get(),
+                self.chroma_key.as_ref(),
+                self.alpha_mode == AlphaMode::Straight,
+                size,
+            ),
+            None => (
+                &ColorMatrix::IDENTITY,
+                &OutputMask::NONE,
+                1.0,
+                None,
+                false,
+                [0.0; 2],
+            ),
+        };
+        let (columns, offset) = color_matrix.columns_and_offset();
+        let mask_size = if mask.is_none() { [0.0; 2] } else { size };
+        let [corner_radius, border_width] = mask.pixel_extents(self.scale_factor, mask_size);
+        let border_color = mask.border_color(encoding);
+        let (key_color, key_threshold) = key.map_or(([0.0; 4], 0.0), |key| {
+            (
+                key.encoded_color(encoding),
+                key.clamped_threshold().unwrap_or(0.0),
+            )
+        });
+
+        unsafe {
+            gl.uniform_1_i32(Some(&u.unpremultiply), i32::from(straight));
+            gl.uniform_matrix_4_f32_slice(Some(&u.color_matrix), false, columns.as_flattened());
+            gl.uniform_4_f32_slice(Some(&u.color_offset), &offset);
+            gl.uniform_1_f32(Some(&u.peak), encoding.brightness());
+            gl.uniform_2_f32(Some(&u.mask_size), mask_size[0], mask_size[1]);
+            gl.uniform_1_f32(Some(&u.corner_radius), corner_radius);
+            gl.uniform_1_f32(Some(&u.border_width), border_width);
+            gl.uniform_4_f32_slice(Some(&u.border_color), &border_color);
+            gl.uniform_4_f32_slice(Some(&u.key_color), &key_color);
+            gl.uniform_1_f32(Some(&u.key_threshold), key_threshold);
+            gl.uniform_1_f32(Some(&u.layout_opacity), opacity);
+        }
+    }
+
+    /// Blit an off-screen render target texture (the cached bottom layer or
+    /// the resolved frame) to the current framebuffer as a fullscreen
+    /// textured quad. The texture's content must be premultiplied. The
+    /// `final_pass` into the caller's framebuffer also applies the output
+    /// mask, color matrix, layout opacity, chroma key, and alpha mode.
+    unsafe fn blit_texture(&self, texture: glow::Texture, resolution: [f32; 2], final_pass: bool) {
         let gl = &self.gl;
 
         unsafe {
@@ -954,44 +1936,185 @@ impl GlowRenderer {
                 resolution[1],
             );
             gl.uniform_2_f32(Some(&self.image_uniforms.offset), 0.0, 0.0);
+            // Off-screen targets already have the output transform applied.
+            set_transform_uniforms(
+                gl,
+                [
+                    &self.image_uniforms.transform_x,
+                    &self.image_uniforms.transform_y,
+                ],
+                &OutputTransform::IDENTITY,
+            );
             gl.uniform_1_i32(Some(&self.image_uniforms.flip_uv_y), 1);
             gl.uniform_1_f32(Some(&self.image_uniforms.brightness), 1.0);
             gl.uniform_1_f32(Some(&self.image_uniforms.opacity), 1.0);
             gl.uniform_1_i32(Some(&self.image_uniforms.already_premultiplied), 1);
+            gl.uniform_1_f32(Some(&self.image_uniforms.dither), 0.0);
+            gl.uniform_1_i32(Some(&self.image_uniforms.linearize), 0);
+            self.set_output_uniforms(
+                &self.image_uniforms.output,
+                final_pass.then_some(resolution),
+            );
 
             gl.active_texture(glow::TEXTURE0);
-            gl.bind_texture(glow::TEXTURE_2D, Some(self.fbo_texture));
+            gl.bind_texture(glow::TEXTURE_2D, Some(texture));
             gl.uniform_1_i32(Some(&self.image_uniforms.texture), 0);
         }
 
         let scene = self.scene_manager.scene();
         let rect = scene.rectangle();
         if let Some(path) = rect.as_ref() {
-            unsafe { self.upload_and_draw(path) };
+            unsafe { self.upload_and_draw(&path.vertices, &path.indices) };
         }
 
         unsafe { gl.bind_texture(glow::TEXTURE_2D, None) };
     }
 
-    /// Resize (or initially create) both the resolve FBO and MSAA FBO to
-    /// match the given viewport dimensions.
+    /// Draw the supersampled frame in [`ssaa_texture`](Self::ssaa_texture)
+    /// into the current framebuffer and viewport, `resolution` pixels in
+    /// size, filtering it down with `filter`.
+    unsafe fn draw_downsample(&self, filter: DownsampleFilter, resolution: [f32; 2]) {
+        let gl = &self.gl;
+        let u = &self.downsample_uniforms;
+        let factor = self
+            .antialiasing
+            .fitted_supersample_factor(self.fbo_size, self.max_target_dimension);
+        let factor = i32::try_from(factor).expect("supersampling factor exceeds i32::MAX");
+
+        unsafe {
+            gl.use_program(Some(self.downsample_program));
+            gl.uniform_1_i32(Some(&u.factor), factor);
+            gl.uniform_1_i32(
+                Some(&u.filter),
+                match filter {
+                    DownsampleFilter::Box => 0,
+                    DownsampleFilter::Lanczos => 1,
+                },
+            );
+            gl.uniform_1_f32(Some(&u.dither), self.dither_amplitude());
+            self.set_output_uniforms(&u.output, Some(resolution));
+
+            gl.active_texture(glow::TEXTURE0);
+            gl.bind_texture(glow::TEXTURE_2D, Some(self.ssaa_texture));
+            gl.uniform_1_i32(Some(&u.texture), 0);
+
+            gl.bind_vertex_array(Some(self.empty_vao));
+            gl.draw_arrays(glow::TRIANGLES, 0, 3);
+            gl.bind_vertex_array(None);
+
+            gl.bind_texture(glow::TEXTURE_2D, None);
+        }
+    }
+
+    /// Resize (or initially create) the resolve, composite, and MSAA FBOs
+    /// to match the given viewport dimensions.
+    ///
+    /// When supersampling, the bottom-layer and MSAA targets are enlarged by
+    /// the supersampling factor (lowered to fit the context's limits) and
+    /// the MSAA FBO renders into a
+    /// single-sample texture instead of a multisampled renderbuffer.
     unsafe fn resize_fbo(&mut self, width: u32, height: u32) {
         let gl = &self.gl;
         let w = gl_size(width);
         let h = gl_size(height);
+        let [target_width, target_height] = self
+            .antialiasing
+            .target_size([width, height], self.max_target_dimension);
+        let tw = gl_size(target_width);
+        let th = gl_size(target_height);
+
+        unsafe {
+            // Set up the resolve targets (non-MSAA textures).
+            let format = self.output_format;
+            Self::allocate_texture_target(gl, self.fbo, self.fbo_texture, format, tw, th);
+            Self::allocate_texture_target(
+                gl,
+                self.composite_fbo,
+                self.composite_texture,
+                format,
+                w,
+                h,
+            );
+            // The shadow textures only hold coverage.
+            for (fbo, texture) in self.shadow_fbos.into_iter().zip(self.shadow_textures) {
+                Self::allocate_texture_target(gl, fbo, texture, OutputFormat::Rgba8, w, h);
+            }
+
+            match self.antialiasing {
+                Antialiasing::Msaa | Antialiasing::Analytic => {
+                    // Set up the MSAA renderbuffer (single-sample for
+                    // analytic antialiasing).
+                    let samples = if self.antialiasing == Antialiasing::Msaa {
+                        MSAA_SAMPLES
+                    } else {
+                        0
+                    };
+                    gl.bind_renderbuffer(glow::RENDERBUFFER, Some(self.msaa_rbo));
+                    gl.renderbuffer_storage_multisample(
+                        glow::RENDERBUFFER,
+                        samples,
+                        render_target_format(format).0,
+                        w,
+                        h,
+                    );
+
+                    gl.bind_framebuffer(glow::FRAMEBUFFER, Some(self.msaa_fbo));
+                    gl.framebuffer_renderbuffer(
+                        glow::FRAMEBUFFER,
+                        glow::COLOR_ATTACHMENT0,
+                        glow::RENDERBUFFER,
+                        Some(self.msaa_rbo),
+                    );
+                    debug_assert_eq!(
+                        gl.check_framebuffer_status(glow::FRAMEBUFFER),
+                        glow::FRAMEBUFFER_COMPLETE,
+                        "MSAA FBO incomplete",
+                    );
+                }
+                Antialiasing::Supersample { .. } => {
+                    Self::allocate_texture_target(
+                        gl,
+                        self.msaa_fbo,
+                        self.ssaa_texture,
+                        format,
+                        tw,
+                        th,
+                    );
+                }
+            }
+
+            gl.bind_framebuffer(glow::FRAMEBUFFER, None);
+            gl.bind_renderbuffer(glow::RENDERBUFFER, None);
+            gl.bind_texture(glow::TEXTURE_2D, None);
+        }
+
+        self.fbo_size = [width, height];
+    }
 
+    /// (Re)allocate `texture` as a `w`×`h` render target in `format` and
+    /// attach it to `fbo`. Leaves `fbo` bound.
+    unsafe fn allocate_texture_target(
+        gl: &glow::Context,
+        fbo: glow::Framebuffer,
+        texture: glow::Texture,
+        format: OutputFormat,
+        w: i32,
+        h: i32,
+    ) {
+        let (internal_format, pixel_type) = render_target_format(format);
         unsafe {
-            // Set up the resolve target (non-MSAA texture).
-            gl.bind_texture(glow::TEXTURE_2D, Some(self.fbo_texture));
+            gl.bind_texture(glow::TEXTURE_2D, Some(texture));
+            // GL constant values are small enough that the cast is always safe.
+            #[expect(clippy::cast_possible_wrap)]
             gl.tex_image_2d(
                 glow::TEXTURE_2D,
                 0,
-                RGBA8_INTERNAL_FORMAT,
+                internal_format as i32,
                 w,
                 h,
                 0,
                 glow::RGBA,
-                glow::UNSIGNED_BYTE,
+                pixel_type,
                 PixelUnpackData::Slice(None),
             );
             // GL constant values are small enough that the cast is always safe.
@@ -1009,12 +2132,12 @@ impl GlowRenderer {
                 );
             }
 
-            gl.bind_framebuffer(glow::FRAMEBUFFER, Some(self.fbo));
+            gl.bind_framebuffer(glow::FRAMEBUFFER, Some(fbo));
             gl.framebuffer_texture_2d(
                 glow::FRAMEBUFFER,
                 glow::COLOR_ATTACHMENT0,
                 glow::TEXTURE_2D,
-                Some(self.fbo_texture),
+                Some(texture),
                 0,
             );
             debug_assert_eq!(
@@ -1022,36 +2145,7 @@ impl GlowRenderer {
                 glow::FRAMEBUFFER_COMPLETE,
                 "resolve FBO incomplete",
             );
-
-            // Set up the MSAA renderbuffer.
-            gl.bind_renderbuffer(glow::RENDERBUFFER, Some(self.msaa_rbo));
-            gl.renderbuffer_storage_multisample(
-                glow::RENDERBUFFER,
-                MSAA_SAMPLES,
-                glow::RGBA8,
-                w,
-                h,
-            );
-
-            gl.bind_framebuffer(glow::FRAMEBUFFER, Some(self.msaa_fbo));
-            gl.framebuffer_renderbuffer(
-                glow::FRAMEBUFFER,
-                glow::COLOR_ATTACHMENT0,
-                glow::RENDERBUFFER,
-                Some(self.msaa_rbo),
-            );
-            debug_assert_eq!(
-                gl.check_framebuffer_status(glow::FRAMEBUFFER),
-                glow::FRAMEBUFFER_COMPLETE,
-                "MSAA FBO incomplete",
-            );
-
-            gl.bind_framebuffer(glow::FRAMEBUFFER, None);
-            gl.bind_renderbuffer(glow::RENDERBUFFER, None);
-            gl.bind_texture(glow::TEXTURE_2D, None);
         }
-
-        self.fbo_size = [width, height];
     }
 
     /// Clean up all GL resources owned by this renderer.
@@ -1065,13 +2159,25 @@ impl GlowRenderer {
         unsafe {
             gl.delete_program(self.path_program);
             gl.delete_program(self.image_program);
+            gl.delete_program(self.downsample_program);
+            gl.delete_program(self.text_program);
+            gl.delete_program(self.shadow_program);
+            for (fbo, texture) in self.shadow_fbos.into_iter().zip(self.shadow_textures) {
+                gl.delete_framebuffer(fbo);
+                gl.delete_texture(texture);
+            }
+            gl.delete_texture(self.atlas_texture);
             gl.delete_vertex_array(self.vao);
+            gl.delete_vertex_array(self.empty_vao);
             gl.delete_buffer(self.vbo);
             gl.delete_buffer(self.ebo);
             gl.delete_framebuffer(self.fbo);
             gl.delete_texture(self.fbo_texture);
             gl.delete_framebuffer(self.msaa_fbo);
             gl.delete_renderbuffer(self.msaa_rbo);
+            gl.delete_texture(self.ssaa_texture);
+            gl.delete_framebuffer(self.composite_fbo);
+            gl.delete_texture(self.composite_texture);
         }
         if let Some(cache) = &self.blur_cache {
             unsafe { gl.delete_texture(cache.texture) };
@@ -5,37 +5,176 @@
 
 use glow::HasContext;
 
+/// GLSL `bayer8` and `dither` functions shared by the path, image, and
+/// downsample fragment shaders. `dither(color, amplitude)` adds an 8x8
+/// ordered dither keyed to `gl_FragCoord` to a premultiplied color.
+macro_rules! dither_snippet {
+    () => {
+        r"
+// 8x8 ordered (Bayer) dither threshold of a pixel, in [-0.5, 0.5).
+float bayer8(ivec2 pixel) {
+    int x = pixel.x & 7;
+    int xy = x ^ (pixel.y & 7);
+    int index = ((xy & 1) << 5) | ((x & 1) << 4) | ((xy & 2) << 2)
+              | ((x & 2) << 1) | ((xy & 4) >> 1) | ((x & 4) >> 2);
+    return (float(index) + 0.5) / 64.0 - 0.5;
+}
+
+// Dither a premultiplied color by `amplitude` (in color units) before it
+// is quantized to the target's precision, keeping it premultiplied.
+vec4 dither(vec4 color, float amplitude) {
+    float noise = bayer8(ivec2(gl_FragCoord.xy)) * amplitude * color.a;
+    return vec4(clamp(color.rgb + noise, 0.0, color.a), color.a);
+}
+"
+    };
+}
+
+/// GLSL output stage shared by the image and downsample fragment shaders:
+/// the output mask, color matrix, layout opacity, and chroma key uniforms,
+/// and the `unpremultiply`, `apply_color_matrix`, `apply_mask`,
+/// `apply_output`, and `apply_key` functions that read them.
+macro_rules! output_snippet {
+    () => {
+        r"
+uniform mat4 u_color_matrix;
+uniform vec4 u_color_offset;
+uniform float u_peak;
+uniform vec2 u_mask_size;
+uniform float u_corner_radius;
+uniform float u_border_width;
+uniform vec4 u_border_color;
+uniform vec4 u_key_color;
+uniform float u_key_threshold;
+uniform float u_layout_opacity;
+
+// Divide a premultiplied color by its alpha.
+vec4 unpremultiply(vec4 color) {
+    return color.a > 0.0 ? vec4(color.rgb / color.a, color.a) : vec4(0.0);
+}
+
+// Apply the color matrix to the straight channels of a premultiplied color,
+// with the layout's white at `u_peak`, keeping it premultiplied.
+vec4 apply_color_matrix(vec4 color) {
+    vec4 straight = unpremultiply(color);
+    straight.rgb /= u_peak;
+    vec4 result = clamp(u_color_matrix * straight + u_color_offset, 0.0, 1.0);
+    return vec4(result.rgb * u_peak * result.a, result.a);
+}
+
+// Cut a premultiplied color to the rounded rectangle of the mask at
+// `position` in pixels, with antialiased edges, and draw the border over
+// its inside edge.
+vec4 apply_mask(vec4 color, vec2 position) {
+    vec2 size = u_mask_size;
+    float radius = u_corner_radius;
+    vec2 q = abs(position - 0.5 * size) - (0.5 * size - radius);
+    float dist = length(max(q, 0.0)) + min(max(q.x, q.y), 0.0) - radius;
+    float outer = clamp(0.5 - dist, 0.0, 1.0);
+    float border = outer - clamp(0.5 - dist - u_border_width, 0.0, 1.0);
+    return u_border_color * border + color * outer * (1.0 - u_border_color.a * border);
+}
+
+// Apply the output mask, if any, and then the color matrix, if it is not
+// the identity, to a premultiplied color at `uv` in the viewport, and fade
+// it by the layout's opacity.
+vec4 apply_output(vec4 color, vec2 uv) {
+    if (u_mask_size.x > 0.0) {
+        color = apply_mask(color, uv * u_mask_size);
+    }
+    if (u_color_matrix != mat4(1.0) || u_color_offset != vec4(0.0)) {
+        color = apply_color_matrix(color);
+    }
+    return color * u_layout_opacity;
+}
+
+// Composite a premultiplied color over the opaque key color (a zero key
+// color, for no key, leaves it as it is). With a key threshold, replace a
+// color that covers less than the threshold of its pixel with the key
+// color and make any other color opaque instead, so that no pixel mixes
+// the layout with the key.
+vec4 apply_key(vec4 color) {
+    if (u_key_threshold <= 0.0) {
+        return color + u_key_color * (1.0 - color.a);
+    }
+    return color.a < u_key_threshold ? u_key_color : vec4(color.rgb / color.a, 1.0);
+}
+"
+    };
+}
+
 /// Vertex shader for filled/stroked paths.
 ///
-/// Transforms vertices by the entity's scale+translate transform, and passes
-/// the *local-space* position to the fragment shader for gradient
-/// interpolation.
+/// Transforms vertices by the entity's scale+translate transform followed by
+/// the renderer's affine output transform, and passes the *local-space*
+/// position to the fragment shader for gradient interpolation.
+///
+/// Edge vertices are then moved along their normal to open up the coverage
+/// fringe: fully covered vertices half a fringe width inwards, uncovered
+/// ones half a width outwards. The normal is carried into viewport space
+/// with the inverse transpose of the transform's linear part (its cofactor
+/// matrix, up to the sign of the determinant) so it stays perpendicular to
+/// the edge under non-uniform scaling. A non-zero `u_dilation` moves all
+/// edge vertices outwards by that many pixels on top, growing the shape
+/// (used for text shadow spread).
 ///
 /// # Uniforms
 ///
-/// | Name           | Type   | Description                              |
-/// |----------------|--------|------------------------------------------|
-/// | `u_scale`      | `vec2` | Entity scale (width, height)             |
-/// | `u_offset`     | `vec2` | Entity translation (x, y)                |
-/// | `u_resolution` | `vec2` | Viewport size in pixels                  |
+/// | Name             | Type    | Description                             |
+/// |------------------|---------|-----------------------------------------|
+/// | `u_scale`        | `vec2`  | Entity scale (width, height)            |
+/// | `u_offset`       | `vec2`  | Entity translation (x, y)               |
+/// | `u_transform_x`  | `vec3`  | First row of the output transform       |
+/// | `u_transform_y`  | `vec3`  | Second row of the output transform      |
+/// | `u_resolution`   | `vec2`  | Viewport size in pixels                 |
+/// | `u_fringe_width` | `float` | Coverage fringe width in pixels (or 0)  |
+/// | `u_dilation`     | `float` | Outward shift of the edges in pixels    |
 pub const PATH_VERTEX_SRC: &str = r"#version 140
 
 in vec2 a_position;
+in vec2 a_normal;
+in float a_coverage;
 
 // Entity transform: output = offset + scale * input
 uniform vec2 u_scale;
 uniform vec2 u_offset;
 
+// Output transform (layout -> viewport pixels), one row per axis
+uniform vec3 u_transform_x;
+uniform vec3 u_transform_y;
+
 // Viewport resolution for NDC conversion
 uniform vec2 u_resolution;
 
+// Width of the antialiasing fringe in pixels, 0 when it is not drawn
+uniform float u_fringe_width;
+
+// Distance in pixels to grow the shape by, 0 for its true outline
+uniform float u_dilation;
+
 // Local-space position for gradient interpolation
 out vec2 v_local;
+out float v_coverage;
 
 void main() {
     v_local = a_position;
-
-    vec2 world = u_offset + u_scale * a_position;
+    v_coverage = a_coverage;
+
+    vec3 layout_pos = vec3(u_offset + u_scale * a_position, 1.0);
+    vec2 world = vec2(dot(u_transform_x, layout_pos), dot(u_transform_y, layout_pos));
+
+    // Cofactors of the entity scale, then of the output transform.
+    vec2 n = a_normal * u_scale.yx;
+    n = vec2(u_transform_y.y * n.x - u_transform_y.x * n.y,
+             u_transform_x.x * n.y - u_transform_x.y * n.x);
+    float det = (u_transform_x.x * u_transform_y.y - u_transform_x.y * u_transform_y.x)
+              * u_scale.x * u_scale.y;
+    float n_len = length(n);
+    if (n_len > 0.0) {
+        // Keep the miter length, in pixels, that was computed in local space.
+        n *= sign(det) * length(a_normal) / n_len;
+        world += n * (u_fringe_width * (0.5 - a_coverage) + u_dilation);
+    }
 
     // Convert from [0, resolution] to [-1, 1] (flip Y for GL)
     vec2 ndc = (world / u_resolution) * 2.0 - 1.0;
@@ -55,18 +194,26 @@ void main() {
 /// | `1`   | Vertical gradient   | local Y            |
 /// | `2`   | Horizontal gradient | local X            |
 ///
-/// All output colors are premultiplied by alpha before writing.
-pub const PATH_FRAGMENT_SRC: &str = r"#version 140
+/// All output colors are premultiplied by alpha and scaled by the
+/// interpolated edge coverage before writing. A non-zero `u_dither` adds an
+/// ordered dither of that amplitude, keyed to the pixel position, to break
+/// up banding in slow gradients.
+pub const PATH_FRAGMENT_SRC: &str = concat!(
+    r"#version 140
 
 in vec2 v_local;
+in float v_coverage;
 
 uniform int u_shader_type;
 uniform vec4 u_color_a;   // solid color, or gradient start
 uniform vec4 u_color_b;   // gradient end (unused for solid)
 uniform vec2 u_bounds;    // [min, max] for gradient axis
+uniform float u_dither;   // dither amplitude, 0 = off
 
 out vec4 frag_color;
-
+",
+    dither_snippet!(),
+    r"
 void main() {
     if (u_shader_type == 0) {
         // Solid color
@@ -86,8 +233,16 @@ void main() {
 
     // Premultiply alpha for correct blending
     frag_color.rgb *= frag_color.a;
+
+    // Fade out across the antialiasing fringe
+    frag_color *= v_coverage;
+
+    if (u_dither > 0.0) {
+        frag_color = dither(frag_color, u_dither);
+    }
 }
-";
+",
+);
 
 /// Vertex shader for textured quads (images).
 ///
@@ -97,18 +252,22 @@ void main() {
 ///
 /// # Uniforms
 ///
-/// | Name           | Type    | Description                           |
-/// |----------------|---------|---------------------------------------|
-/// | `u_scale`      | `vec2`  | Entity scale (width, height)          |
-/// | `u_offset`     | `vec2`  | Entity translation (x, y)             |
-/// | `u_resolution` | `vec2`  | Viewport size in pixels               |
-/// | `u_flip_uv_y`  | `bool`  | Flip V coordinate (for FBO blitting)  |
+/// | Name            | Type    | Description                           |
+/// |-----------------|---------|---------------------------------------|
+/// | `u_scale`       | `vec2`  | Entity scale (width, height)          |
+/// | `u_offset`      | `vec2`  | Entity translation (x, y)             |
+/// | `u_transform_x` | `vec3`  | First row of the output transform     |
+/// | `u_transform_y` | `vec3`  | Second row of the output transform    |
+/// | `u_resolution`  | `vec2`  | Viewport size in pixels               |
+/// | `u_flip_uv_y`   | `bool`  | Flip V coordinate (for FBO blitting)  |
 pub const IMAGE_VERTEX_SRC: &str = r"#version 140
 
 in vec2 a_position;
 
 uniform vec2 u_scale;
 uniform vec2 u_offset;
+uniform vec3 u_transform_x;
+uniform vec3 u_transform_y;
 uniform vec2 u_resolution;
 uniform bool u_flip_uv_y;
 
@@ -120,7 +279,8 @@ void main() {
         v_uv.y = 1.0 - v_uv.y;
     }
 
-    vec2 world = u_offset + u_scale * a_position;
+    vec3 layout_pos = vec3(u_offset + u_scale * a_position, 1.0);
+    vec2 world = vec2(dot(u_transform_x, layout_pos), dot(u_transform_y, layout_pos));
     vec2 ndc = (world / u_resolution) * 2.0 - 1.0;
     ndc.y = -ndc.y;
 
@@ -130,8 +290,14 @@ void main() {
 
 /// Fragment shader for textured quads.
 ///
-/// Samples the bound texture, applies brightness and opacity adjustments,
-/// and premultiplies the result by alpha.
+/// Samples the bound texture, converts it to linear for linear targets,
+/// applies brightness and opacity adjustments, premultiplies the result by
+/// alpha, and optionally dithers it like [`PATH_FRAGMENT_SRC`]. The final
+/// pass clips the frame to the output mask, if `u_mask_size` is non-zero,
+/// applies the color matrix, if it is not the identity, fades it by
+/// `u_layout_opacity`, composites it over the chroma key `u_key_color`
+/// (zero for no key) or cuts it against the key at `u_key_threshold`, if
+/// that is non-zero, and divides a straight-alpha frame by alpha.
 ///
 /// # Uniforms
 ///
@@ -141,7 +307,21 @@ void main() {
 /// | `u_brightness`           | `float`     | Brightness multiplier (1.0 = normal)               |
 /// | `u_opacity`              | `float`     | Opacity multiplier (1.0 = opaque)                  |
 /// | `u_already_premultiplied`| `int`       | If non-zero, skip alpha premultiplication (FBO blit)|
-pub const IMAGE_FRAGMENT_SRC: &str = r"#version 140
+/// | `u_dither`               | `float`     | Dither amplitude (0.0 = off)                       |
+/// | `u_linearize`            | `int`       | If non-zero, convert sampled sRGB colors to linear |
+/// | `u_unpremultiply`        | `int`       | If non-zero, output straight alpha                 |
+/// | `u_color_matrix`         | `mat4`      | Color matrix (identity = off)                      |
+/// | `u_color_offset`         | `vec4`      | Offset column of the color matrix                  |
+/// | `u_peak`                 | `float`     | Color value of the layout's white                  |
+/// | `u_mask_size`            | `vec2`      | Output mask size in pixels (0 = off)               |
+/// | `u_corner_radius`        | `float`     | Output mask corner radius in pixels                |
+/// | `u_border_width`         | `float`     | Output mask border width in pixels                 |
+/// | `u_border_color`         | `vec4`      | Premultiplied border color                         |
+/// | `u_key_color`            | `vec4`      | Opaque chroma key color (0.0 = no key)             |
+/// | `u_key_threshold`        | `float`     | Coverage shown as layout (0.0 = soft matte)        |
+/// | `u_layout_opacity`       | `float`     | Opacity of the finished frame                      |
+pub const IMAGE_FRAGMENT_SRC: &str = concat!(
+    r"#version 140
 
 in vec2 v_uv;
 
@@ -149,20 +329,341 @@ uniform sampler2D u_texture;
 uniform float u_brightness;
 uniform float u_opacity;
 uniform int u_already_premultiplied;
+uniform float u_dither;
+uniform int u_linearize;
+uniform int u_unpremultiply;
 
 out vec4 frag_color;
+",
+    dither_snippet!(),
+    output_snippet!(),
+    r"
+// Convert sRGB-encoded color channels to linear.
+vec3 srgb_to_linear(vec3 c) {
+    vec3 low = c / 12.92;
+    vec3 high = pow((c + 0.055) / 1.055, vec3(2.4));
+    return mix(high, low, vec3(lessThanEqual(c, vec3(0.04045))));
+}
 
 void main() {
     frag_color = texture(u_texture, v_uv);
+    if (u_linearize != 0) {
+        frag_color.rgb = srgb_to_linear(frag_color.rgb);
+    }
     frag_color.rgb *= u_brightness;
     frag_color.a *= u_opacity;
-    // Premultiply alpha (skip if content is already premultiplied, e.g. FBO blit)
+    // Premultiply alpha (content that is already premultiplied, e.g. an
+    // FBO blit, only needs the opacity applied to its colors too)
     if (u_already_premultiplied == 0) {
         frag_color.rgb *= frag_color.a;
+    } else {
+        frag_color.rgb *= u_opacity;
+    }
+    frag_color = apply_output(frag_color, v_uv);
+    if (u_dither > 0.0) {
+        frag_color = dither(frag_color, u_dither);
+    }
+    frag_color = apply_key(frag_color);
+    if (u_unpremultiply != 0) {
+        frag_color = unpremultiply(frag_color);
+    }
+}
+",
+);
+
+/// Vertex shader for distance field text.
+///
+/// Draws the scene's unit rectangle stretched over one glyph's quad from
+/// the glyph atlas. The quad is placed in the glyph's local space, then
+/// transformed like a path.
+///
+/// # Uniforms
+///
+/// | Name            | Type   | Description                                  |
+/// |-----------------|--------|----------------------------------------------|
+/// | `u_scale`       | `vec2` | Glyph scale (width, height)                  |
+/// | `u_offset`      | `vec2` | Glyph translation (x, y)                     |
+/// | `u_transform_x` | `vec3` | First row of the output transform            |
+/// | `u_transform_y` | `vec3` | Second row of the output transform           |
+/// | `u_resolution`  | `vec2` | Viewport size in pixels                      |
+/// | `u_glyph_rect`  | `vec4` | Quad origin and size in glyph-local space    |
+/// | `u_uv_rect`     | `vec4` | Quad origin and size in the atlas            |
+pub const TEXT_VERTEX_SRC: &str = r"#version 140
+
+in vec2 a_position;
+
+uniform vec2 u_scale;
+uniform vec2 u_offset;
+uniform vec3 u_transform_x;
+uniform vec3 u_transform_y;
+uniform vec2 u_resolution;
+uniform vec4 u_glyph_rect;
+uniform vec4 u_uv_rect;
+
+// Glyph-local position for gradient interpolation
+out vec2 v_local;
+out vec2 v_uv;
+
+void main() {
+    v_local = u_glyph_rect.xy + a_position * u_glyph_rect.zw;
+    v_uv = u_uv_rect.xy + a_position * u_uv_rect.zw;
+
+    vec3 layout_pos = vec3(u_offset + u_scale * v_local, 1.0);
+    vec2 world = vec2(dot(u_transform_x, layout_pos), dot(u_transform_y, layout_pos));
+    vec2 ndc = (world / u_resolution) * 2.0 - 1.0;
+    ndc.y = -ndc.y;
+
+    gl_Position = vec4(ndc, 0.0, 1.0);
+}
+";
+
+/// Fragment shader for text drawn from the glyph atlas.
+///
+/// The fill color is computed as in [`PATH_FRAGMENT_SRC`]. Unless
+/// `u_coverage` is set, the atlas holds the signed distance to the glyph
+/// outline, `0.5` on the outline; its screen-space gradient converts it to
+/// pixels, which gives a one-pixel antialiased edge at any scale. The
+/// outline is the same shape grown by `u_outline_width` pixels, drawn under
+/// the fill, and the glow fades out over `u_glow_radius` pixels beyond
+/// that, drawn under both. With `u_coverage` set, the atlas holds
+/// rasterized coverage, which scales the fill directly.
+///
+/// # Uniforms
+///
+/// | Name              | Type        | Description                           |
+/// |-------------------|-------------|---------------------------------------|
+/// | `u_atlas`         | `sampler2D` | Glyph atlas (distance or coverage in red) |
+/// | `u_shader_type`   | `int`       | As in the path fragment shader        |
+/// | `u_color_a`       | `vec4`      | Solid color, or gradient start        |
+/// | `u_color_b`       | `vec4`      | Gradient end                          |
+/// | `u_bounds`        | `vec2`      | `[min, max]` for the gradient axis    |
+/// | `u_outline_width` | `float`     | Outline width in pixels (or 0)        |
+/// | `u_outline_color` | `vec4`      | Outline color                         |
+/// | `u_glow_radius`   | `float`     | Glow radius in pixels (or 0)          |
+/// | `u_glow_color`    | `vec4`      | Glow color                            |
+/// | `u_coverage`      | `int`       | 1 if the atlas holds coverage         |
+pub const TEXT_FRAGMENT_SRC: &str = r"#version 140
+
+in vec2 v_local;
+in vec2 v_uv;
+
+uniform sampler2D u_atlas;
+uniform int u_shader_type;
+uniform vec4 u_color_a;
+uniform vec4 u_color_b;
+uniform vec2 u_bounds;
+uniform float u_outline_width;
+uniform vec4 u_outline_color;
+uniform float u_glow_radius;
+uniform vec4 u_glow_color;
+uniform int u_coverage;
+
+out vec4 frag_color;
+
+vec4 premultiply(vec4 color) {
+    return vec4(color.rgb * color.a, color.a);
+}
+
+void main() {
+    vec4 fill;
+    if (u_shader_type == 0) {
+        fill = u_color_a;
+    } else {
+        float coord = (u_shader_type == 1) ? v_local.y : v_local.x;
+        float range = u_bounds.y - u_bounds.x;
+        float t = (range > 0.0) ? clamp((coord - u_bounds.x) / range, 0.0, 1.0) : 0.0;
+        fill = mix(u_color_a, u_color_b, t);
+    }
+
+    float dist = texture(u_atlas, v_uv).r;
+    if (u_coverage != 0) {
+        // The atlas holds the glyph's coverage, not a distance field.
+        frag_color = premultiply(fill) * dist;
+        return;
     }
+
+    // Signed distance to the outline in pixels, positive inside.
+    float per_pixel = length(vec2(dFdx(dist), dFdy(dist)));
+    float px = (dist - 0.5) / max(per_pixel, 1e-6);
+
+    float fill_alpha = clamp(px + 0.5, 0.0, 1.0);
+    float outline_alpha = u_outline_width > 0.0
+        ? clamp(px + u_outline_width + 0.5, 0.0, 1.0)
+        : 0.0;
+    float outside = max(-(px + u_outline_width), 0.0);
+    float glow_alpha = u_glow_radius > 0.0
+        ? 1.0 - smoothstep(0.0, u_glow_radius, outside)
+        : 0.0;
+
+    frag_color = premultiply(fill) * fill_alpha;
+    frag_color += premultiply(u_outline_color) * outline_alpha * (1.0 - frag_color.a);
+    frag_color += premultiply(u_glow_color) * glow_alpha * (1.0 - frag_color.a);
+}
+";
+
+/// Vertex shader for downsampling a supersampled frame.
+///
+/// Draws a single triangle covering the current viewport, generated from
+/// `gl_VertexID` (no vertex attributes), and passes the matching UV.
+pub const DOWNSAMPLE_VERTEX_SRC: &str = r"#version 140
+
+out vec2 v_uv;
+
+void main() {
+    // UVs (0,0), (2,0), (0,2): one triangle that covers the whole viewport.
+    vec2 uv = vec2(float((gl_VertexID << 1) & 2), float(gl_VertexID & 2));
+    v_uv = uv;
+    gl_Position = vec4(uv * 2.0 - 1.0, 0.0, 1.0);
 }
 ";
 
+/// Fragment shader that downsamples a supersampled frame.
+///
+/// Each output pixel covers a `u_factor`×`u_factor` block of source texels.
+/// The box filter averages that block; the Lanczos filter weights the
+/// texels within two output pixels with a two-lobe Lanczos kernel. The
+/// source is premultiplied, and the result is clamped so that Lanczos
+/// ringing cannot produce colors brighter than their alpha allows
+/// (`u_peak` times alpha). The output mask, color matrix, and layout
+/// opacity are applied as in [`IMAGE_FRAGMENT_SRC`]. The averaged samples
+/// are quantized again, so they are dithered like [`PATH_FRAGMENT_SRC`] too,
+/// and then keyed like in [`IMAGE_FRAGMENT_SRC`] and divided by alpha for
+/// straight-alpha output.
+///
+/// # Uniforms
+///
+/// | Name              | Type        | Description                          |
+/// |-------------------|-------------|--------------------------------------|
+/// | `u_texture`       | `sampler2D` | Supersampled frame                   |
+/// | `u_factor`        | `int`       | Supersampling factor                 |
+/// | `u_filter`        | `int`       | `0` = box, `1` = Lanczos             |
+/// | `u_dither`        | `float`     | Dither amplitude (0.0 = off)         |
+/// | `u_peak`          | `float`     | Largest color value per unit alpha   |
+/// | `u_unpremultiply` | `int`       | If non-zero, output straight alpha   |
+/// | `u_color_matrix`  | `mat4`      | Color matrix (identity = off)        |
+/// | `u_color_offset`  | `vec4`      | Offset column of the color matrix    |
+/// | `u_mask_size`     | `vec2`      | Output mask size in pixels (0 = off) |
+/// | `u_corner_radius` | `float`     | Output mask corner radius in pixels  |
+/// | `u_border_width`  | `float`     | Output mask border width in pixels   |
+/// | `u_border_color`  | `vec4`      | Premultiplied border color           |
+/// | `u_key_color`     | `vec4`      | Opaque chroma key color (0.0 = off)  |
+/// | `u_key_threshold` | `float`     | Coverage shown as layout (0.0 = mix) |
+/// | `u_layout_opacity`| `float`     | Opacity of the finished frame        |
+pub const DOWNSAMPLE_FRAGMENT_SRC: &str = concat!(
+    r"#version 140
+
+in vec2 v_uv;
+
+uniform sampler2D u_texture;
+uniform int u_factor;
+uniform int u_filter;
+uniform float u_dither;
+uniform int u_unpremultiply;
+
+out vec4 frag_color;
+",
+    dither_snippet!(),
+    output_snippet!(),
+    r"
+const float PI = 3.14159265;
+
+float sinc(float x) {
+    if (abs(x) < 1e-5) {
+        return 1.0;
+    }
+    float px = PI * x;
+    return sin(px) / px;
+}
+
+float lanczos2(float x) {
+    return abs(x) < 2.0 ? sinc(x) * sinc(x * 0.5) : 0.0;
+}
+
+void main() {
+    ivec2 size = textureSize(u_texture, 0);
+    float factor = float(u_factor);
+    // Center of this output pixel, in source texels.
+    vec2 center = v_uv * vec2(size);
+
+    int taps = u_filter == 1 ? 4 * u_factor : u_factor;
+    ivec2 first = ivec2(floor(center - 0.5 * float(taps)));
+
+    vec4 sum = vec4(0.0);
+    float weight_sum = 0.0;
+    for (int j = 0; j < taps; j++) {
+        for (int i = 0; i < taps; i++) {
+            ivec2 texel = first + ivec2(i, j);
+            float weight = 1.0;
+            if (u_filter == 1) {
+                vec2 d = (vec2(texel) + 0.5 - center) / factor;
+                weight = lanczos2(d.x) * lanczos2(d.y);
+            }
+            vec4 color = texelFetch(u_texture, clamp(texel, ivec2(0), size - 1), 0);
+            sum += weight * color;
+            weight_sum += weight;
+        }
+    }
+
+    float alpha = clamp(sum.a / weight_sum, 0.0, 1.0);
+    frag_color = vec4(clamp(sum.rgb / weight_sum, 0.0, alpha * u_peak), alpha);
+    frag_color = apply_output(frag_color, v_uv);
+    if (u_dither > 0.0) {
+        frag_color = dither(frag_color, u_dither);
+    }
+    frag_color = apply_key(frag_color);
+    if (u_unpremultiply != 0) {
+        frag_color = unpremultiply(frag_color);
+    }
+}
+",
+);
+
+/// Fragment shader that blurs a text shadow's coverage along one axis.
+///
+/// Uses the vertex stage of [`DOWNSAMPLE_VERTEX_SRC`] and works in texels
+/// of the target, which must match the source's size. Each pixel is a
+/// gaussian-weighted sum of the source's red channel over `u_taps` texels on
+/// either side along `u_direction`. Taps are clamped to `u_region`, so that
+/// texels outside the shadow being blurred are never read. The result is
+/// written to all four channels.
+///
+/// # Uniforms
+///
+/// | Name          | Type        | Description                                |
+/// |---------------|-------------|--------------------------------------------|
+/// | `u_texture`   | `sampler2D` | Coverage to blur                           |
+/// | `u_direction` | `ivec2`     | `(1, 0)` or `(0, 1)`                       |
+/// | `u_region`    | `ivec4`     | Texels read: min corner, then max (exclusive) |
+/// | `u_sigma`     | `float`     | Standard deviation in texels               |
+/// | `u_taps`      | `int`       | Texels sampled on each side                |
+pub const SHADOW_BLUR_FRAGMENT_SRC: &str = r"#version 140
+
+uniform sampler2D u_texture;
+uniform ivec2 u_direction;
+uniform ivec4 u_region;
+uniform float u_sigma;
+uniform int u_taps;
+
+out vec4 frag_color;
+
+void main() {
+    ivec2 texel = ivec2(gl_FragCoord.xy);
+    float sum = 0.0;
+    float weight_sum = 0.0;
+    for (int i = -u_taps; i <= u_taps; i++) {
+        ivec2 tap = clamp(texel + i * u_direction, u_region.xy, u_region.zw - 1);
+        float weight = exp(-float(i * i) / (2.0 * u_sigma * u_sigma));
+        sum += weight * texelFetch(u_texture, tap, 0).r;
+        weight_sum += weight;
+    }
+    frag_color = vec4(sum / weight_sum);
+}
+";
+
+/// Vertex attribute names, in attribute location order. They match the
+/// fields of [`Vertex`](crate::common::Vertex).
+pub const VERTEX_ATTRIBUTES: [&str; 3] = ["a_position", "a_normal", "a_coverage"];
+
 /// Compile a shader program from vertex and fragment source strings.
 ///
 /// The compiled shader objects are detached and deleted after successful
@@ -189,6 +690,11 @@ pub unsafe fn compile_program(
     unsafe {
         gl.attach_shader(program, vs);
         gl.attach_shader(program, fs);
+        // Pin the vertex attributes so that every program can share one
+        // VAO. Names a shader does not declare are ignored.
+        for (location, name) in (0..).zip(VERTEX_ATTRIBUTES) {
+            gl.bind_attrib_location(program, location, name);
+        }
         gl.link_program(program);
 
         if !gl.get_program_link_status(program) {
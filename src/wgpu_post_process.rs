@@ -0,0 +1,482 @@
+//! User-supplied post-processing effects for the wgpu renderer.
+//!
+//! When effects are set, the finished frame is resolved (or downsampled)
+//! into an intermediate texture instead of the output. Each effect's
+//! fragment shader reads the previous texture and writes the other one of a
+//! ping-pong pair, and the renderer's present pass copies or blends the last
+//! one into the viewport.
+
+use bytemuck::{Pod, Zeroable};
+use std::{fmt, time::Instant};
+use wgpu::util::DeviceExt;
+
+use crate::{wgpu_render::OutputParams, wgpu_shaders};
+
+/// A post-processing effect: a WGSL fragment shader run over the finished
+/// layout by [`WgpuRenderer`](crate::WgpuRenderer), e.g. CRT scanlines,
+/// chromatic aberration, bloom, or color grading.
+///
+/// The source must define the fragment entry point `fs_main`. It is
+/// compiled after a prelude that declares:
+///
+/// - `post.resolution: vec2f`, the viewport size in pixels, and
+///   `post.time: f32`, the seconds since the effects were set;
+/// - `layout_texture: texture_2d<f32>`, the frame so far (the layout, or
+///   the previous effect's output) with premultiplied alpha, and
+///   `layout_sampler`, a linear sampler clamped to the edges;
+/// - `struct VertexOutput { clip_position: vec4f, uv: vec2f }`, the input
+///   of `fs_main`, with `uv` running from `(0, 0)` at the top left to
+///   `(1, 1)` at the bottom right.
+///
+/// The effect must output premultiplied alpha. Effects are checked with
+/// [`validate`](Self::validate) when they are
+/// [set](crate::WgpuRenderer::set_post_effects).
+///
+/// ```
+/// # use livesplit_renderer_gpu::PostEffect;
+/// let scanlines = PostEffect::new(
+///     "scanlines",
+///     r"
+///     @fragment
+///     fn fs_main(in: VertexOutput) -> @location(0) vec4f {
+///         let color = textureSample(layout_texture, layout_sampler, in.uv);
+///         let row = in.uv.y * post.resolution.y;
+///         return color * (0.85 + 0.15 * sin(row * 3.14159));
+///     }
+///     ",
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PostEffect {
+    label: String,
+    source: String,
+}
+
+impl PostEffect {
+    /// Create an effect from its WGSL fragment shader source. `label`
+    /// names the effect's shader and pipeline in wgpu errors and graphics
+    /// debuggers.
+    pub fn new(label: impl Into<String>, source: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            source: source.into(),
+        }
+    }
+
+    /// Parse and validate the effect's source after the prelude, and check
+    /// that it defines the fragment entry point `fs_main`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`PostEffectError`] with the WGSL front end's or
+    /// validator's message if the source does not compile.
+    pub fn validate(&self) -> Result<(), PostEffectError> {
+        let source = wgpu_shaders::post_process_source(&self.source);
+        let module = naga::front::wgsl::parse_str(&source)
+            .map_err(|error| self.error(error.emit_to_string(&source)))?;
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::default(),
+        )
+        .validate(&module)
+        .map_err(|error| self.error(error.emit_to_string(&source)))?;
+        let has_entry_point = module
+            .entry_points
+            .iter()
+            .any(|entry| entry.name == "fs_main" && entry.stage == naga::ShaderStage::Fragment);
+        if !has_entry_point {
+            return Err(self.error("missing fragment entry point `fs_main`".to_owned()));
+        }
+        Ok(())
+    }
+
+    fn error(&self, message: String) -> PostEffectError {
+        PostEffectError {
+            label: self.label.clone(),
+            message,
+        }
+    }
+}
+
+/// A [`PostEffect`] whose source does not compile, returned by
+/// [`PostEffect::validate`] and
+/// [`WgpuRenderer::set_post_effects`](crate::WgpuRenderer::set_post_effects).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PostEffectError {
+    /// The effect's label.
+    pub label: String,
+    /// What is wrong with the source, with line numbers counted from the
+    /// start of the prelude.
+    pub message: String,
+}
+
+impl fmt::Display for PostEffectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "post effect `{}` is invalid: {}",
+            self.label, self.message
+        )
+    }
+}
+
+impl std::error::Error for PostEffectError {}
+
+/// Inputs of every effect, uploaded as a uniform buffer.
+///
+/// Layout must match the `PostUniforms` struct in the WGSL prelude
+/// (see [`wgpu_shaders::POST_PROCESS_PRELUDE`]).
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+#[repr(C)]
+struct PostUniformData {
+    resolution: [f32; 2],
+    time: f32,
+    _pad: f32,
+}
+
+const _: () = assert!(std::mem::size_of::<PostUniformData>() == 16);
+
+/// The ping-pong pair of viewport-sized textures the effects render into.
+struct PostTargets {
+    /// Kept alive so the views and bind groups remain valid.
+    #[allow(dead_code)]
+    textures: [wgpu::Texture; 2],
+    views: [wgpu::TextureView; 2],
+    /// Bind groups for reading each texture in an effect pass.
+    effect_bind_groups: [wgpu::BindGroup; 2],
+    /// Bind groups for reading each texture in the present pass.
+    present_bind_groups: [wgpu::BindGroup; 2],
+}
+
+/// A chain of [`PostEffect`]s and the GPU state to run it.
+pub(crate) struct PostProcess {
+    /// One pipeline per effect, in order.
+    pipelines: Vec<wgpu::RenderPipeline>,
+    /// Layout of the effects' bind group (see
+    /// [`wgpu_shaders::POST_PROCESS_PRELUDE`]).
+    bind_group_layout: wgpu::BindGroupLayout,
+    /// Holds a [`PostUniformData`].
+    uniform_buffer: wgpu::Buffer,
+    /// Downsamples a supersampled frame into the first target, keeping
+    /// premultiplied alpha and replacing the target's contents.
+    downsample_pipeline: wgpu::RenderPipeline,
+    /// Neutral output parameters for the downsample pass; the renderer
+    /// applies its color matrix and output mask when presenting the last
+    /// effect's output.
+    neutral_output_bind_group: wgpu::BindGroup,
+    /// When the effects were set; `post.time` counts from here.
+    start: Instant,
+    /// `None` until [`resize`](Self::resize) is called.
+    targets: Option<PostTargets>,
+}
+
+impl PostProcess {
+    /// Compile `effects` for targets of the given format. The layouts are
+    /// the renderer's texture, downsample parameter, and output parameter
+    /// bind group layouts.
+    pub(crate) fn new(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        effects: &[PostEffect],
+        texture_bind_group_layout: &wgpu::BindGroupLayout,
+        params_bind_group_layout: &wgpu::BindGroupLayout,
+        output_params_bind_group_layout: &wgpu::BindGroupLayout,
+    ) -> Self {
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("post_process_bind_group_layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: std::num::NonZeroU64::new(std::mem::size_of::<
+                            PostUniformData,
+                        >()
+                            as u64),
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+        let pipelines = effects
+            .iter()
+            .map(|effect| {
+                wgpu_shaders::create_post_process_pipeline(
+                    device,
+                    format,
+                    &bind_group_layout,
+                    &effect.label,
+                    &effect.source,
+                )
+            })
+            .collect();
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("post_process_uniform_buffer"),
+            size: std::mem::size_of::<PostUniformData>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let downsample_pipeline = wgpu_shaders::create_downsample_pipeline(
+            device,
+            format,
+            texture_bind_group_layout,
+            params_bind_group_layout,
+            output_params_bind_group_layout,
+            None,
+            false,
+        );
+        let neutral_output_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("post_process_output_params_buffer"),
+            contents: bytemuck::bytes_of(&OutputParams::NEUTRAL),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        let neutral_output_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("post_process_output_params_bind_group"),
+            layout: output_params_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: neutral_output_buffer.as_entire_binding(),
+            }],
+        });
+
+        Self {
+            pipelines,
+            bind_group_layout,
+            uniform_buffer,
+            downsample_pipeline,
+            neutral_output_bind_group,
+            start: Instant::now(),
+            targets: None,
+        }
+    }
+
+    /// Recreate the ping-pong targets for a viewport of the given size.
+    pub(crate) fn resize(
+        &mut self,
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        [width, height]: [u32; 2],
+        sampler: &wgpu::Sampler,
+        texture_bind_group_layout: &wgpu::BindGroupLayout,
+    ) {
+        let textures = ["post_process_texture_a", "post_process_texture_b"].map(|label| {
+            device.create_texture(&wgpu::TextureDescriptor {
+                label: Some(label),
+                size: wgpu::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            })
+        });
+        let views = textures
+            .each_ref()
+            .map(|texture| texture.create_view(&wgpu::TextureViewDescriptor::default()));
+        let effect_bind_groups = views.each_ref().map(|view| {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("post_process_bind_group"),
+                layout: &self.bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: self.uniform_buffer.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::TextureView(view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: wgpu::BindingResource::Sampler(sampler),
+                    },
+                ],
+            })
+        });
+        let present_bind_groups = views.each_ref().map(|view| {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("post_process_texture_bind_group"),
+                layout: texture_bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(sampler),
+                    },
+                ],
+            })
+        });
+
+        self.targets = Some(PostTargets {
+            textures,
+            views,
+            effect_bind_groups,
+            present_bind_groups,
+        });
+    }
+
+    /// Upload the effects' inputs for a frame of `resolution` pixels.
+    pub(crate) fn update(&self, queue: &wgpu::Queue, resolution: [f32; 2]) {
+        let data = PostUniformData {
+            resolution,
+            time: self.start.elapsed().as_secs_f32(),
+            _pad: 0.0,
+        };
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(&data));
+    }
+
+    fn targets(&self) -> &PostTargets {
+        self.targets
+            .as_ref()
+            .expect("post-process targets not initialized")
+    }
+
+    /// The texture the finished frame must be resolved into before
+    /// [`encode`](Self::encode).
+    pub(crate) fn input_view(&self) -> &wgpu::TextureView {
+        &self.targets().views[0]
+    }
+
+    /// Record a pass that downsamples a supersampled frame into the
+    /// [input](Self::input_view), with the renderer's supersampling and
+    /// downsample parameter bind groups.
+    pub(crate) fn encode_downsample(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        [texture, params]: [&wgpu::BindGroup; 2],
+    ) {
+        encode_pass(
+            encoder,
+            "post_process_downsample_pass",
+            &self.downsample_pipeline,
+            &[texture, params, &self.neutral_output_bind_group],
+            self.input_view(),
+        );
+    }
+
+    /// Record the effect passes and return the bind group for presenting
+    /// the result.
+    pub(crate) fn encode(&self, encoder: &mut wgpu::CommandEncoder) -> &wgpu::BindGroup {
+        let targets = self.targets();
+        let mut source = 0;
+        for pipeline in &self.pipelines {
+            encode_pass(
+                encoder,
+                "post_process_pass",
+                pipeline,
+                &[&targets.effect_bind_groups[source]],
+                &targets.views[1 - source],
+            );
+            source = 1 - source;
+        }
+        &targets.present_bind_groups[source]
+    }
+}
+
+/// Record a pass that draws a full-viewport triangle with `pipeline`,
+/// replacing the contents of `target`.
+fn encode_pass(
+    encoder: &mut wgpu::CommandEncoder,
+    label: &str,
+    pipeline: &wgpu::RenderPipeline,
+    bind_groups: &[&wgpu::BindGroup],
+    target: &wgpu::TextureView,
+) {
+    let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some(label),
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view: target,
+            resolve_target: None,
+            ops: wgpu::Operations {
+                load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                store: wgpu::StoreOp::Store,
+            },
+            depth_slice: None,
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+        multiview_mask: None,
+    });
+    pass.set_pipeline(pipeline);
+    for (index, bind_group) in (0..).zip(bind_groups) {
+        pass.set_bind_group(index, *bind_group, &[]);
+    }
+    pass.draw(0..3, 0..1);
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn documented_effect_validates() {
+        let scanlines = PostEffect::new(
+            "scanlines",
+            r"
+            @fragment
+            fn fs_main(in: VertexOutput) -> @location(0) vec4f {
+                let color = textureSample(layout_texture, layout_sampler, in.uv);
+                let row = in.uv.y * post.resolution.y;
+                return color * (0.85 + 0.15 * sin(row * 3.14159));
+            }
+            ",
+        );
+        assert_eq!(scanlines.validate(), Ok(()));
+    }
+
+    #[test]
+    fn invalid_effects_are_reported_by_label() {
+        let typo = PostEffect::new(
+            "typo",
+            r"
+            @fragment
+            fn fs_main(in: VertexOutput) -> @location(0) vec4f {
+                return textureSample(layout_texture, layout_sampler, in.vu);
+            }
+            ",
+        );
+        let error = typo.validate().unwrap_err();
+        assert_eq!(error.label, "typo");
+        assert!(error.message.contains("vu"), "{}", error.message);
+
+        let unnamed = PostEffect::new(
+            "unnamed",
+            r"
+            @fragment
+            fn main(in: VertexOutput) -> @location(0) vec4f {
+                return vec4f(0.0);
+            }
+            ",
+        );
+        assert!(unnamed.validate().unwrap_err().message.contains("fs_main"));
+    }
+}
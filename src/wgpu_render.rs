@@ -6,17 +6,28 @@
 use bytemuck::{Pod, Zeroable};
 use livesplit_core::{
     layout::LayoutState,
-    rendering::{Background, Entity, FillShader, Handle, LabelHandle, SceneManager, Transform},
-    settings::{BackgroundImage, ImageCache},
+    rendering::{Handle, SceneManager, Transform},
+    settings::ImageCache,
 };
-use std::cell::{OnceCell, RefCell};
+use std::cell::{Cell, OnceCell, RefCell};
+use std::ops::Range;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use wgpu::util::DeviceExt;
 
 use crate::{
-    common::{tessellate_stroke, vertex_bounds, BLUR_FACTOR, SHADOW_OFFSET},
+    common::{
+        blurred_background, curve_tolerance, fill_at_tolerance, layout_resolution,
+        layout_transform, shadow_region, AlphaMode, Antialiasing, ChromaKey, ColorEncoding,
+        ColorMatrix, CompositeMode, DisplayItem, DisplayList, DisplaySettings, Dithering,
+        DownsampleFilter, Fill, FrameStats, ImageItem, LayoutOpacity, OutputFormat, OutputMask,
+        OutputTransform, ShadowItem, StrokeStyle, TextEffects, TextOutline, TextRendering,
+        TextShadow, Viewport, BLUR_FACTOR, SCRGB_WHITE_NITS,
+    },
+    glyph_atlas::{place_rasterized_glyph, AtlasGlyph, GlyphAtlas, ShelfPacker, ATLAS_SIZE},
     wgpu_allocator::WgpuAllocator,
     wgpu_buffer_pool::FrameBufferPool,
+    wgpu_post_process::{PostEffect, PostEffectError, PostProcess},
     wgpu_shaders,
     wgpu_types::{WgpuFont, WgpuImage, WgpuLabel, WgpuPath},
 };
@@ -24,17 +35,58 @@ use crate::{
 /// Number of MSAA samples for antialiasing.
 pub const MSAA_SAMPLES: u32 = 4;
 
-/// Convert a single sRGB channel value to linear.
-///
-/// livesplit-core provides colors in sRGB space. When the render target uses
-/// an sRGB format, wgpu automatically applies linear→sRGB encoding on write.
-/// Without converting input colors to linear first, they would be
-/// double-encoded (washed out, with reduced contrast between dark shades).
-fn srgb_channel_to_linear(c: f32) -> f32 {
-    if c <= 0.04045 {
-        c / 12.92
-    } else {
-        ((c + 0.055) / 1.055).powf(2.4)
+/// Create the pipelines that copy, blend, or downsample the finished frame
+/// into the output with the given alpha mode, in the order `[present,
+/// present_blend, downsample, downsample_blend]`.
+fn output_pipelines(
+    device: &wgpu::Device,
+    format: wgpu::TextureFormat,
+    texture_bind_group_layout: &wgpu::BindGroupLayout,
+    params_bind_group_layout: &wgpu::BindGroupLayout,
+    output_params_bind_group_layout: &wgpu::BindGroupLayout,
+    alpha_mode: AlphaMode,
+) -> [wgpu::RenderPipeline; 4] {
+    let straight = alpha_mode == AlphaMode::Straight;
+    let blend = match alpha_mode {
+        AlphaMode::Premultiplied => wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING,
+        AlphaMode::Straight => wgpu::BlendState::ALPHA_BLENDING,
+    };
+    let present = |blend| {
+        wgpu_shaders::create_present_pipeline(
+            device,
+            format,
+            texture_bind_group_layout,
+            output_params_bind_group_layout,
+            blend,
+            straight,
+        )
+    };
+    let downsample = |blend| {
+        wgpu_shaders::create_downsample_pipeline(
+            device,
+            format,
+            texture_bind_group_layout,
+            params_bind_group_layout,
+            output_params_bind_group_layout,
+            blend,
+            straight,
+        )
+    };
+    [
+        present(None),
+        present(Some(blend)),
+        downsample(None),
+        downsample(Some(blend)),
+    ]
+}
+
+/// The [`OutputFormat`] whose precision and color encoding a wgpu texture
+/// format shares. sRGB formats are 8-bit formats that encode on write.
+fn output_format(format: wgpu::TextureFormat) -> OutputFormat {
+    match format {
+        wgpu::TextureFormat::Rgb10a2Unorm => OutputFormat::Rgb10A2,
+        wgpu::TextureFormat::Rgba16Float => OutputFormat::Rgba16Float,
+        _ => OutputFormat::Rgba8,
     }
 }
 
@@ -52,13 +104,17 @@ struct PathUniformData {
     color_a: [f32; 4],
     color_b: [f32; 4],
     shader_type: i32,
-    _pad0: i32,
-    _pad1: i32,
-    _pad2: i32,
+    fringe_width: f32,
+    /// Distance in pixels to grow the shape by (text shadow spread).
+    dilation: f32,
+    /// Dither amplitude, `0.0` for none.
+    dither: f32,
+    transform_x: [f32; 4],
+    transform_y: [f32; 4],
 }
 
 // Compile-time checks that Rust struct sizes match WGSL expectations.
-const _: () = assert!(std::mem::size_of::<PathUniformData>() == 80);
+const _: () = assert!(std::mem::size_of::<PathUniformData>() == 112);
 
 /// Uniform data for the image shader, uploaded as a uniform buffer.
 ///
@@ -76,11 +132,194 @@ struct ImageUniformData {
     /// When non-zero, the texture content is already premultiplied and the
     /// shader should skip the premultiplication step.  Used by FBO blitting.
     already_premultiplied: i32,
-    _pad1: i32,
+    /// Dither amplitude, `0.0` for none.
+    dither: f32,
     _pad2: i32,
+    transform_x: [f32; 4],
+    transform_y: [f32; 4],
+}
+
+const _: () = assert!(std::mem::size_of::<ImageUniformData>() == 80);
+
+/// Uniform data for the atlas text shader, uploaded as a uniform
+/// buffer.
+///
+/// Layout must match the `TextUniforms` struct in the WGSL shader
+/// (see [`wgpu_shaders::TEXT_SHADER_SRC`]).
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+#[repr(C)]
+struct TextUniformData {
+    scale: [f32; 2],
+    offset: [f32; 2],
+    resolution: [f32; 2],
+    bounds: [f32; 2],
+    color_a: [f32; 4],
+    color_b: [f32; 4],
+    shader_type: i32,
+    outline_width: f32,
+    glow_radius: f32,
+    /// Non-zero when the atlas holds rasterized coverage rather than a
+    /// distance field.
+    coverage: i32,
+    transform_x: [f32; 4],
+    transform_y: [f32; 4],
+    glyph_rect: [f32; 4],
+    uv_rect: [f32; 4],
+    outline_color: [f32; 4],
+    glow_color: [f32; 4],
+}
+
+const _: () = assert!(std::mem::size_of::<TextUniformData>() == 176);
+
+/// Parameters for the downsample shader, uploaded as a uniform buffer.
+///
+/// Layout must match the `DownsampleParams` struct in the WGSL shader
+/// (see [`wgpu_shaders::DOWNSAMPLE_SHADER_SRC`]).
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+#[repr(C)]
+struct DownsampleParams {
+    factor: u32,
+    /// `0` = box, `1` = Lanczos.
+    filter_type: u32,
+    /// Dither amplitude, `0.0` for none.
+    dither: f32,
+    /// Largest color channel value per unit of alpha (`1.0` unless the
+    /// layout is brightened for an HDR output).
+    peak: f32,
+}
+
+const _: () = assert!(std::mem::size_of::<DownsampleParams>() == 16);
+
+/// Color matrix, output mask, layout opacity, and chroma key applied by the
+/// present and downsample shaders, uploaded as a uniform buffer.
+///
+/// Layout must match the `OutputParams` struct in the WGSL shaders
+/// (see [`wgpu_shaders::PRESENT_SHADER_SRC`]).
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+#[repr(C)]
+pub(crate) struct OutputParams {
+    /// Columns of the matrix's 4×4 part.
+    columns: [[f32; 4]; 4],
+    offset: [f32; 4],
+    /// Color value of the layout's white.
+    peak: f32,
+    /// `0` for the identity matrix, which the shaders skip.
+    matrix_enabled: u32,
+    /// Size of the output in pixels, or `0` when there is no mask.
+    mask_size: [f32; 2],
+    /// Corner radius in pixels.
+    corner_radius: f32,
+    /// Border width in pixels.
+    border_width: f32,
+    _pad: [u32; 2],
+    /// Premultiplied border color.
+    border_color: [f32; 4],
+    /// Opaque chroma key color, or `0` for no key.
+    key_color: [f32; 4],
+    /// Coverage from which the layout is shown instead of the key, or `0`
+    /// to composite the layout over the key.
+    key_threshold: f32,
+    /// Multiplier of the finished frame.
+    opacity: f32,
+    _pad_key: [u32; 2],
+}
+
+const _: () = assert!(std::mem::size_of::<OutputParams>() == 160);
+
+impl OutputParams {
+    /// Parameters that leave the output untouched.
+    pub(crate) const NEUTRAL: Self = Self {
+        columns: [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ],
+        offset: [0.0; 4],
+        peak: 1.0,
+        matrix_enabled: 0,
+        mask_size: [0.0; 2],
+        corner_radius: 0.0,
+        border_width: 0.0,
+        _pad: [0; 2],
+        border_color: [0.0; 4],
+        key_color: [0.0; 4],
+        key_threshold: 0.0,
+        opacity: 1.0,
+        _pad_key: [0; 2],
+    };
+}
+
+/// Multiplier of every path, image, and text draw, uploaded as a uniform
+/// buffer. The renderer's own passes draw at full opacity; only
+/// [`WgpuRenderer::paint`] fades the layout this way.
+///
+/// Layout must match the `DrawParams` struct in the WGSL shaders
+/// (see [`wgpu_shaders::PATH_SHADER_SRC`]).
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+#[repr(C)]
+struct DrawParams {
+    opacity: f32,
+    _pad: [f32; 3],
+}
+
+const _: () = assert!(std::mem::size_of::<DrawParams>() == 16);
+
+impl DrawParams {
+    /// Parameters of a draw at the given opacity.
+    const fn with_opacity(opacity: f32) -> Self {
+        Self {
+            opacity,
+            _pad: [0.0; 3],
+        }
+    }
+}
+
+/// Parameters for one pass of the shadow blur shader, uploaded as a
+/// uniform buffer.
+///
+/// Layout must match the `ShadowBlurParams` struct in the WGSL shader
+/// (see [`wgpu_shaders::SHADOW_BLUR_SHADER_SRC`]).
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+#[repr(C)]
+struct ShadowBlurParams {
+    /// Texels that may be read: min xy, exclusive max zw.
+    region: [i32; 4],
+    direction: [i32; 2],
+    sigma: f32,
+    taps: i32,
+}
+
+const _: () = assert!(std::mem::size_of::<ShadowBlurParams>() == 32);
+
+/// The sample count of the renderer's own render targets.
+fn sample_count(antialiasing: Antialiasing) -> u32 {
+    match antialiasing {
+        Antialiasing::Msaa => MSAA_SAMPLES,
+        Antialiasing::Analytic | Antialiasing::Supersample { .. } => 1,
+    }
+}
+
+/// How the single-sample frame is filtered into the output, or `None` for
+/// MSAA, which is resolved instead. Analytic antialiasing renders at the
+/// output size, so its box filter is a plain copy.
+fn downsample_filter(antialiasing: Antialiasing) -> Option<DownsampleFilter> {
+    match antialiasing {
+        Antialiasing::Msaa => None,
+        Antialiasing::Analytic => Some(DownsampleFilter::Box),
+        Antialiasing::Supersample { filter, .. } => Some(filter),
+    }
 }
 
-const _: () = assert!(std::mem::size_of::<ImageUniformData>() == 48);
+/// Pack the rows of an output transform into the `vec4f` pair the shaders
+/// expect (the fourth component is padding).
+fn transform_uniform(transform: &OutputTransform) -> [[f32; 4]; 2] {
+    let [row_x, row_y] = transform.rows();
+    [
+        [row_x[0], row_x[1], row_x[2], 0.0],
+        [row_y[0], row_y[1], row_y[2], 0.0],
+    ]
+}
 
 /// Cached blurred background texture.
 struct BlurCache {
@@ -96,6 +335,119 @@ struct BlurCache {
     bind_group: Arc<wgpu::BindGroup>,
 }
 
+/// Single-channel, viewport-sized textures that blurred text shadows are
+/// drawn and blurred in.
+struct ShadowTargets {
+    /// The textures. Kept alive so the views and bind groups remain valid.
+    #[allow(dead_code)]
+    textures: [wgpu::Texture; 2],
+    /// Views of [`textures`](Self::textures). Shadow masks are drawn into
+    /// the first, blurred horizontally into the second, and vertically back
+    /// into the first.
+    views: [wgpu::TextureView; 2],
+    /// Bind groups for sampling [`textures`](Self::textures).
+    bind_groups: [wgpu::BindGroup; 2],
+}
+
+/// One blurred shadow waiting for [`WgpuRenderer::flush_shadows`].
+struct ShadowBlur {
+    /// The shadow's slot in the shadow textures, `[x, y, width, height]`.
+    slot: [u32; 4],
+    /// Bind group for the pool's uniform buffer.
+    uniform_bind_group: wgpu::BindGroup,
+    /// Dynamic offsets of the horizontal and vertical passes'
+    /// [`ShadowBlurParams`].
+    uniform_offsets: [u32; 2],
+}
+
+/// Blurred text shadows recorded since the last
+/// [`WgpuRenderer::flush_shadows`].
+struct ShadowBatch {
+    /// Free space in the shadow textures.
+    packer: ShelfPacker,
+    /// Glyph coverage of every shadow, already moved into its slot.
+    masks: Vec<DrawCommand>,
+    /// The blur passes to run over the masks.
+    blurs: Vec<ShadowBlur>,
+}
+
+impl ShadowBatch {
+    /// An empty batch for shadow textures of the given size.
+    fn new([width, height]: [u32; 2]) -> Self {
+        Self {
+            packer: ShelfPacker::new(width, height),
+            masks: Vec::new(),
+            blurs: Vec::new(),
+        }
+    }
+}
+
+/// A single draw recorded during [`WgpuRenderer::prepare`] and replayed
+/// into a render pass by [`WgpuRenderer::encode`] or
+/// [`WgpuRenderer::paint`].
+///
+/// All per-draw data has already been written to the buffer pool, so
+/// replaying a command needs neither the device nor the queue. Buffers and
+/// bind groups are cloned handles: if the pool grows mid-frame, commands
+/// recorded earlier keep referencing the (retired) buffer they were
+/// written to.
+enum DrawCommand {
+    /// An indexed triangle mesh drawn with the path pipeline.
+    Path {
+        /// Bind group for the pool's uniform buffer.
+        uniform_bind_group: wgpu::BindGroup,
+        /// Dynamic offset of this draw's [`PathUniformData`].
+        uniform_offset: u32,
+        /// Pool vertex buffer holding this draw's vertices.
+        vertex_buffer: wgpu::Buffer,
+        /// Byte range of the vertices within `vertex_buffer`.
+        vertices: Range<u64>,
+        /// Pool index buffer holding this draw's indices.
+        index_buffer: wgpu::Buffer,
+        /// Byte range of the indices within `index_buffer`.
+        indices: Range<u64>,
+        /// Number of indices to draw.
+        index_count: u32,
+    },
+    /// The scene's unit rectangle drawn with the image pipeline.
+    TexturedRect {
+        /// Bind group for the pool's uniform buffer.
+        uniform_bind_group: wgpu::BindGroup,
+        /// Dynamic offset of this draw's [`ImageUniformData`].
+        uniform_offset: u32,
+        /// Bind group for the sampled texture.
+        texture_bind_group: Arc<wgpu::BindGroup>,
+    },
+    /// The scene's unit rectangle stretched over a glyph's quad, drawn with
+    /// the text pipeline from the glyph atlas.
+    Glyph {
+        /// Bind group for the pool's uniform buffer.
+        uniform_bind_group: wgpu::BindGroup,
+        /// Dynamic offset of this draw's [`TextUniformData`].
+        uniform_offset: u32,
+    },
+    /// The scene's unit rectangle stretched over a blurred text shadow,
+    /// drawn with the text pipeline from the shadow textures.
+    Shadow {
+        /// Bind group for the pool's uniform buffer.
+        uniform_bind_group: wgpu::BindGroup,
+        /// Dynamic offset of this draw's [`TextUniformData`].
+        uniform_offset: u32,
+        /// Bind group for sampling the blurred shadow.
+        texture_bind_group: wgpu::BindGroup,
+    },
+}
+
+/// The path, image, and text render pipelines for one sample count.
+struct Pipelines {
+    /// Render pipeline for filled/stroked paths.
+    path: wgpu::RenderPipeline,
+    /// Render pipeline for textured quads (images, FBO blitting).
+    image: wgpu::RenderPipeline,
+    /// Render pipeline for glyphs drawn from the atlas.
+    text: wgpu::RenderPipeline,
+}
+
 /// A GPU-accelerated renderer for livesplit-core layouts using wgpu.
 ///
 /// Renders a livesplit-core [`LayoutState`] to a wgpu texture using two
@@ -111,6 +463,16 @@ struct BlurCache {
 /// resolved to a texture, and reused across frames when unchanged. This
 /// avoids re-rendering the majority of the scene every frame.
 ///
+/// # Integrating with an existing frame graph
+///
+/// [`render`](Self::render) creates its own command encoder and submits it.
+/// Engines that schedule their own GPU work can instead split a frame into
+/// [`prepare`](Self::prepare), which updates the scene and uploads all
+/// per-frame data, followed by either [`encode`](Self::encode) (records the
+/// composite pass into a caller-provided encoder) or [`paint`](Self::paint)
+/// (draws into a caller-provided render pass). Neither submits to the queue,
+/// mirroring the prepare/paint split used by egui-wgpu callbacks.
+///
 /// # Example
 ///
 /// ```no_run
@@ -134,17 +496,54 @@ pub struct WgpuRenderer {
     /// graph of entities.
     scene_manager: SceneManager<Option<WgpuPath>, WgpuImage, WgpuFont, WgpuLabel>,
 
-    /// Render pipeline for filled/stroked paths.
-    path_pipeline: wgpu::RenderPipeline,
+    /// Pipelines used for the renderer's own passes, matching the sample
+    /// count of the current [`Antialiasing`] mode.
+    pipelines: Pipelines,
+    /// Pipelines used by [`paint`](Self::paint), matching the sample count
+    /// of the caller's render pass.
+    paint_pipelines: Pipelines,
+    /// Pipeline that copies the composited layout into a sub-rectangle of
+    /// the output.
+    present_pipeline: wgpu::RenderPipeline,
+    /// Pipeline that blends the composited layout over the output's
+    /// existing contents ([`CompositeMode::Blend`]).
+    present_blend_pipeline: wgpu::RenderPipeline,
+    /// Pipeline that filters a supersampled frame down into the output.
+    downsample_pipeline: wgpu::RenderPipeline,
+    /// Pipeline that filters a supersampled frame down and blends it over
+    /// the output's existing contents.
+    downsample_blend_pipeline: wgpu::RenderPipeline,
+    /// Pipelines that draw text shadow masks into the single-channel
+    /// shadow textures.
+    shadow_pipelines: Pipelines,
+    /// Pipeline that blurs text shadows along one axis.
+    shadow_blur_pipeline: wgpu::RenderPipeline,
+
     /// Bind group layout for path uniforms.
     path_bind_group_layout: wgpu::BindGroupLayout,
 
-    /// Render pipeline for textured quads (images, FBO blitting).
-    image_pipeline: wgpu::RenderPipeline,
     /// Bind group layout for image uniforms.
     image_uniform_bind_group_layout: wgpu::BindGroupLayout,
     /// Bind group layout for image textures.
     image_texture_bind_group_layout: wgpu::BindGroupLayout,
+    /// Bind group layout for the downsample parameters.
+    downsample_params_bind_group_layout: wgpu::BindGroupLayout,
+    /// Bind group layout for atlas text uniforms.
+    text_bind_group_layout: wgpu::BindGroupLayout,
+    /// Bind group layout for the shadow blur parameters.
+    shadow_blur_bind_group_layout: wgpu::BindGroupLayout,
+    /// Bind group layout for the [`DrawParams`] of the path, image, and
+    /// text pipelines.
+    draw_params_bind_group_layout: wgpu::BindGroupLayout,
+    /// Bind group for [`DrawParams`] at full opacity, used by the
+    /// renderer's own passes.
+    opaque_draw_params_bind_group: wgpu::BindGroup,
+    /// Holds the [`DrawParams`] used by [`paint`](Self::paint), updated
+    /// every frame with the layout's opacity.
+    paint_draw_params_buffer: wgpu::Buffer,
+    /// Bind group for
+    /// [`paint_draw_params_buffer`](Self::paint_draw_params_buffer).
+    paint_draw_params_bind_group: wgpu::BindGroup,
 
     /// Default sampler for textures.
     sampler: wgpu::Sampler,
@@ -156,17 +555,64 @@ pub struct WgpuRenderer {
     fbo_texture: Option<wgpu::Texture>,
     /// Texture view for the resolve target.
     fbo_texture_view: Option<wgpu::TextureView>,
+    /// Bind group for sampling the resolve target when blitting the cached
+    /// bottom layer. Recreated whenever the texture is resized.
+    fbo_bind_group: Option<Arc<wgpu::BindGroup>>,
 
     /// MSAA texture used as the rendering target for antialiased content.
+    /// Single-sampled and enlarged by the supersampling factor when
+    /// supersampling.
     msaa_texture: Option<wgpu::Texture>,
     /// Texture view for the MSAA rendering target.
     msaa_texture_view: Option<wgpu::TextureView>,
+    /// Bind group for sampling the supersampled frame in the downsample
+    /// pass. `None` unless supersampling.
+    ssaa_bind_group: Option<wgpu::BindGroup>,
+    /// Bind group for the downsample parameters. `None` unless
+    /// supersampling.
+    downsample_params_bind_group: Option<wgpu::BindGroup>,
+    /// How edges are antialiased.
+    antialiasing: Antialiasing,
+    /// Color matrix applied to the finished frame.
+    color_matrix: ColorMatrix,
+    /// Rounded corners and border applied to the finished frame.
+    output_mask: OutputMask,
+    /// Key color that replaces the background, if any.
+    chroma_key: Option<ChromaKey>,
+    /// Layout of the output parameters bind group used by the present and
+    /// downsample passes.
+    output_params_bind_group_layout: wgpu::BindGroupLayout,
+    /// Holds the [`OutputParams`], updated every frame.
+    output_params_buffer: wgpu::Buffer,
+    /// Bind group for [`output_params_buffer`](Self::output_params_buffer).
+    output_params_bind_group: wgpu::BindGroup,
+
+    /// Single-sample texture the composite pass resolves into when the
+    /// layout cannot be resolved directly into the output (see
+    /// [`Viewport`]). Kept alive so the bind group remains valid.
+    #[allow(dead_code)]
+    composite_texture: Option<wgpu::Texture>,
+    /// Texture view for [`composite_texture`](Self::composite_texture).
+    composite_texture_view: Option<wgpu::TextureView>,
+    /// Bind group for sampling the composite texture in the present pass.
+    composite_bind_group: Option<wgpu::BindGroup>,
+    /// User effects run over the finished frame before the present pass.
+    /// `None` if no effects are set.
+    post_process: Option<PostProcess>,
 
     /// Current dimensions of the off-screen textures.
     fbo_size: [u32; 2],
     /// Whether the cached bottom layer needs re-rendering (e.g., after a
     /// resize).
     bottom_layer_dirty: bool,
+    /// Entity and stroke cache counts of the frame being drawn, or of the
+    /// last one. A `Cell` because they are counted while drawing, which
+    /// only borrows the renderer.
+    frame_stats: Cell<FrameStats>,
+
+    /// Opacity of the whole layout, applied when compositing the cached
+    /// bottom layer and the top layer.
+    opacity: LayoutOpacity,
 
     /// Cached blurred background image texture, reused across frames when
     /// the source image and blur setting are unchanged.
@@ -187,9 +633,109 @@ pub struct WgpuRenderer {
     path_uniform_bind_group: RefCell<Option<wgpu::BindGroup>>,
     /// Cached bind group for image uniform buffer.
     image_uniform_bind_group: RefCell<Option<wgpu::BindGroup>>,
+    /// Cached bind group for text uniform buffer.
+    text_uniform_bind_group: RefCell<Option<wgpu::BindGroup>>,
+    /// Cached bind group for shadow blur uniform buffer.
+    shadow_blur_uniform_bind_group: RefCell<Option<wgpu::BindGroup>>,
     /// The pool's uniform generation when the cached bind groups were
     /// created. Used to detect buffer growth and invalidate caches.
     cached_uniform_generation: RefCell<u64>,
+
+    /// Composite draws (cached bottom layer blit plus top layer) recorded by
+    /// the last [`prepare`](Self::prepare). `None` if nothing was prepared
+    /// (e.g. a zero-sized viewport).
+    prepared: Option<Vec<DrawCommand>>,
+    /// Draws that fill the viewport with the chroma key, recorded by the
+    /// last [`prepare`](Self::prepare) for [`paint`](Self::paint), which
+    /// has no final pass to key the frame in. Empty without a key.
+    prepared_key: Vec<DrawCommand>,
+    /// The output rectangle given to the last [`prepare`](Self::prepare).
+    viewport: Viewport,
+    /// How [`encode`](Self::encode) combines the layout with the output.
+    composite_mode: CompositeMode,
+    /// Whether [`encode`](Self::encode) writes premultiplied or straight
+    /// alpha.
+    alpha_mode: AlphaMode,
+    /// Affine transform from layout to viewport coordinates set by the
+    /// user.
+    output_transform: OutputTransform,
+    /// Ratio of physical pixels to logical layout units.
+    scale_factor: f32,
+    /// [`scale_factor`](Self::scale_factor) followed by
+    /// [`output_transform`](Self::output_transform), applied to every
+    /// entity draw.
+    layout_transform: OutputTransform,
+
+    /// How labels are drawn.
+    text_rendering: TextRendering,
+    /// Distance fields of the glyphs drawn with
+    /// [`TextRendering::DistanceField`]. Wrapped in `RefCell` because glyphs
+    /// are added while recording draws, which only borrows the renderer.
+    glyph_atlas: RefCell<GlyphAtlas>,
+    /// Single-channel texture holding [`glyph_atlas`](Self::glyph_atlas).
+    atlas_texture: wgpu::Texture,
+    /// Bind group for sampling [`atlas_texture`](Self::atlas_texture).
+    atlas_bind_group: wgpu::BindGroup,
+
+    /// Dither applied to paths and images before they are stored.
+    dithering: Dithering,
+    /// Brightness of the layout's white on scRGB outputs, in nits.
+    sdr_white_level: f32,
+    /// How the layout's stroked paths are joined, capped, and dashed.
+    stroke_style: StrokeStyle,
+    /// Outline drawn around every label glyph, if any.
+    text_outline: Option<TextOutline>,
+    /// Shape of the shadows drawn under labels.
+    text_shadow: TextShadow,
+    /// Textures blurred text shadows are drawn in. `None` until the first
+    /// frame.
+    shadow_targets: Option<ShadowTargets>,
+    /// Blurred shadows waiting to be drawn into
+    /// [`shadow_targets`](Self::shadow_targets). Wrapped in `RefCell`
+    /// because shadows are added while recording draws.
+    shadow_batch: RefCell<ShadowBatch>,
+}
+
+impl Pipelines {
+    /// Create the path, image, and text pipelines for the given sample
+    /// count.
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        sample_count: u32,
+        path_bind_group_layout: &wgpu::BindGroupLayout,
+        image_uniform_bind_group_layout: &wgpu::BindGroupLayout,
+        image_texture_bind_group_layout: &wgpu::BindGroupLayout,
+        text_bind_group_layout: &wgpu::BindGroupLayout,
+        draw_params_bind_group_layout: &wgpu::BindGroupLayout,
+    ) -> Self {
+        Self {
+            path: wgpu_shaders::create_path_pipeline(
+                device,
+                format,
+                sample_count,
+                path_bind_group_layout,
+                draw_params_bind_group_layout,
+            ),
+            image: wgpu_shaders::create_image_pipeline(
+                device,
+                format,
+                sample_count,
+                image_uniform_bind_group_layout,
+                image_texture_bind_group_layout,
+                draw_params_bind_group_layout,
+            ),
+            text: wgpu_shaders::create_text_pipeline(
+                device,
+                format,
+                sample_count,
+                text_bind_group_layout,
+                image_texture_bind_group_layout,
+                draw_params_bind_group_layout,
+            ),
+        }
+    }
 }
 
 /// Cached GPU vertex and index buffers for the unit rectangle quad.
@@ -203,9 +749,12 @@ impl WgpuRenderer {
     /// Create a new renderer.
     ///
     /// Creates render pipelines and initializes the livesplit-core scene
-    /// manager.
-    ///
+    /// manager. The off-screen targets share the output `format`;
+    /// `Rgb10a2Unorm` outputs are drawn with 10-bit precision and
+    /// `Rgba16Float` outputs with linear scRGB colors (see
+    /// [`set_sdr_white_level`](Self::set_sdr_white_level)).
     #[must_use]
+    #[expect(clippy::too_many_lines)] // Pipeline and layout setup is inherently verbose
     pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
         let path_bind_group_layout =
             device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
@@ -266,13 +815,180 @@ impl WgpuRenderer {
                 ],
             });
 
-        let path_pipeline =
-            wgpu_shaders::create_path_pipeline(device, format, &path_bind_group_layout);
-        let image_pipeline = wgpu_shaders::create_image_pipeline(
+        let downsample_params_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("downsample_params_bind_group_layout"),
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: std::num::NonZeroU64::new(std::mem::size_of::<
+                            DownsampleParams,
+                        >()
+                            as u64),
+                    },
+                    count: None,
+                }],
+            });
+
+        let output_params_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("output_params_bind_group_layout"),
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: std::num::NonZeroU64::new(
+                            std::mem::size_of::<OutputParams>() as u64,
+                        ),
+                    },
+                    count: None,
+                }],
+            });
+        let output_params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("output_params_buffer"),
+            size: std::mem::size_of::<OutputParams>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let output_params_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("output_params_bind_group"),
+            layout: &output_params_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: output_params_buffer.as_entire_binding(),
+            }],
+        });
+
+        let text_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("text_bind_group_layout"),
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: std::num::NonZeroU64::new(std::mem::size_of::<
+                            TextUniformData,
+                        >()
+                            as u64),
+                    },
+                    count: None,
+                }],
+            });
+
+        let draw_params_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("draw_params_bind_group_layout"),
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: std::num::NonZeroU64::new(
+                            std::mem::size_of::<DrawParams>() as u64,
+                        ),
+                    },
+                    count: None,
+                }],
+            });
+        let opaque_draw_params_buffer =
+            device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("opaque_draw_params_buffer"),
+                contents: bytemuck::bytes_of(&DrawParams::with_opacity(1.0)),
+                usage: wgpu::BufferUsages::UNIFORM,
+            });
+        let opaque_draw_params_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("opaque_draw_params_bind_group"),
+            layout: &draw_params_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: opaque_draw_params_buffer.as_entire_binding(),
+            }],
+        });
+        let paint_draw_params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("paint_draw_params_buffer"),
+            size: std::mem::size_of::<DrawParams>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let paint_draw_params_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("paint_draw_params_bind_group"),
+            layout: &draw_params_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: paint_draw_params_buffer.as_entire_binding(),
+            }],
+        });
+
+        let shadow_blur_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("shadow_blur_bind_group_layout"),
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: std::num::NonZeroU64::new(std::mem::size_of::<
+                            ShadowBlurParams,
+                        >()
+                            as u64),
+                    },
+                    count: None,
+                }],
+            });
+
+        let pipelines = Pipelines::new(
+            device,
+            format,
+            MSAA_SAMPLES,
+            &path_bind_group_layout,
+            &image_uniform_bind_group_layout,
+            &image_texture_bind_group_layout,
+            &text_bind_group_layout,
+            &draw_params_bind_group_layout,
+        );
+        let paint_pipelines = Pipelines::new(
             device,
             format,
+            1,
+            &path_bind_group_layout,
+            &image_uniform_bind_group_layout,
+            &image_texture_bind_group_layout,
+            &text_bind_group_layout,
+            &draw_params_bind_group_layout,
+        );
+        let [present_pipeline, present_blend_pipeline, downsample_pipeline, downsample_blend_pipeline] =
+            output_pipelines(
+                device,
+                format,
+                &image_texture_bind_group_layout,
+                &downsample_params_bind_group_layout,
+                &output_params_bind_group_layout,
+                AlphaMode::default(),
+            );
+
+        let shadow_pipelines = Pipelines::new(
+            device,
+            wgpu::TextureFormat::R8Unorm,
+            1,
+            &path_bind_group_layout,
             &image_uniform_bind_group_layout,
             &image_texture_bind_group_layout,
+            &text_bind_group_layout,
+            &draw_params_bind_group_layout,
+        );
+        let shadow_blur_pipeline = wgpu_shaders::create_shadow_blur_pipeline(
+            device,
+            &image_texture_bind_group_layout,
+            &shadow_blur_bind_group_layout,
         );
 
         let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
@@ -286,156 +1002,1067 @@ impl WgpuRenderer {
             ..Default::default()
         });
 
+        let atlas_texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("glyph_atlas_texture"),
+            size: wgpu::Extent3d {
+                width: ATLAS_SIZE,
+                height: ATLAS_SIZE,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::R8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let atlas_view = atlas_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let atlas_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("glyph_atlas_bind_group"),
+            layout: &image_texture_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&atlas_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&sampler),
+                },
+            ],
+        });
+
         let mut allocator = WgpuAllocator::new();
         let scene_manager = SceneManager::new(&mut allocator);
 
         Self {
             allocator,
             scene_manager,
-            path_pipeline,
+            pipelines,
+            paint_pipelines,
+            present_pipeline,
+            present_blend_pipeline,
+            downsample_pipeline,
+            downsample_blend_pipeline,
+            shadow_pipelines,
+            shadow_blur_pipeline,
             path_bind_group_layout,
-            image_pipeline,
             image_uniform_bind_group_layout,
             image_texture_bind_group_layout,
+            downsample_params_bind_group_layout,
+            text_bind_group_layout,
+            shadow_blur_bind_group_layout,
+            draw_params_bind_group_layout,
+            opaque_draw_params_bind_group,
+            paint_draw_params_buffer,
+            paint_draw_params_bind_group,
             sampler,
             format,
             fbo_texture: None,
             fbo_texture_view: None,
+            fbo_bind_group: None,
             msaa_texture: None,
             msaa_texture_view: None,
+            ssaa_bind_group: None,
+            downsample_params_bind_group: None,
+            antialiasing: Antialiasing::default(),
+            color_matrix: ColorMatrix::IDENTITY,
+            output_mask: OutputMask::NONE,
+            chroma_key: None,
+            output_params_bind_group_layout,
+            output_params_buffer,
+            output_params_bind_group,
+            composite_texture: None,
+            composite_texture_view: None,
+            composite_bind_group: None,
+            post_process: None,
             fbo_size: [0, 0],
             bottom_layer_dirty: true,
+            frame_stats: Cell::default(),
+            opacity: LayoutOpacity::OPAQUE,
             blur_cache: None,
             rect_buffers: OnceCell::new(),
             buffer_pool: RefCell::new(FrameBufferPool::new(device)),
             path_uniform_bind_group: RefCell::new(None),
             image_uniform_bind_group: RefCell::new(None),
+            text_uniform_bind_group: RefCell::new(None),
+            shadow_blur_uniform_bind_group: RefCell::new(None),
             cached_uniform_generation: RefCell::new(u64::MAX),
+            prepared: None,
+            prepared_key: Vec::new(),
+            viewport: Viewport::default(),
+            composite_mode: CompositeMode::default(),
+            alpha_mode: AlphaMode::default(),
+            output_transform: OutputTransform::IDENTITY,
+            scale_factor: 1.0,
+            layout_transform: OutputTransform::IDENTITY,
+            text_rendering: TextRendering::default(),
+            glyph_atlas: RefCell::new(GlyphAtlas::new()),
+            atlas_texture,
+            atlas_bind_group,
+            dithering: Dithering::None,
+            sdr_white_level: SCRGB_WHITE_NITS,
+            stroke_style: StrokeStyle::default(),
+            text_outline: None,
+            text_shadow: TextShadow::default(),
+            shadow_targets: None,
+            shadow_batch: RefCell::new(ShadowBatch::new([0, 0])),
         }
     }
 
-    /// Render the layout to the given output texture view.
+    /// Set how [`encode`](Self::encode) combines the layout with the
+    /// existing contents of the output.
     ///
-    /// Returns an optional new resolution hint from livesplit-core's layout
-    /// engine, indicating the layout's preferred size changed. The caller
-    /// can use this to resize the window or viewport.
-    /// # Panics
+    /// With [`CompositeMode::Blend`] the layout is always drawn through the
+    /// intermediate present pass, even if the viewport covers the whole
+    /// output. Has no effect on [`paint`](Self::paint), which always blends
+    /// into the caller's render pass.
+    pub fn set_composite_mode(&mut self, mode: CompositeMode) {
+        self.composite_mode = mode;
+    }
+
+    /// Set whether [`encode`](Self::encode) writes the layout to the output
+    /// with premultiplied or straight alpha. Defaults to
+    /// [`AlphaMode::Premultiplied`].
     ///
-    /// Panics if off-screen textures have not been initialized (i.e., if
-    /// the viewport dimensions are zero).
-    #[allow(clippy::too_many_arguments)]
-    pub fn render(
-        &mut self,
-        device: &wgpu::Device,
-        queue: &wgpu::Queue,
-        state: &LayoutState,
-        image_cache: &ImageCache,
-        [width, height]: [u32; 2],
-        output_view: &wgpu::TextureView,
-        draw_background: bool,
-    ) -> Option<[f32; 2]> {
-        if width == 0 || height == 0 {
-            return None;
+    /// Match it to the surface's `wgpu::CompositeAlphaMode`:
+    /// [`AlphaMode::Straight`] suits `PostMultiplied` surfaces and textures
+    /// read back for capture or PNG export. Straight alpha always goes
+    /// through the intermediate present pass. Recreates the output
+    /// pipelines, so call this at setup, not every frame. Has no effect on
+    /// [`paint`](Self::paint).
+    pub fn set_alpha_mode(&mut self, device: &wgpu::Device, alpha_mode: AlphaMode) {
+        if self.alpha_mode == alpha_mode {
+            return;
         }
+        self.alpha_mode = alpha_mode;
+        [
+            self.present_pipeline,
+            self.present_blend_pipeline,
+            self.downsample_pipeline,
+            self.downsample_blend_pipeline,
+        ] = output_pipelines(
+            device,
+            self.format,
+            &self.image_texture_bind_group_layout,
+            &self.downsample_params_bind_group_layout,
+            &self.output_params_bind_group_layout,
+            alpha_mode,
+        );
+    }
 
-        self.buffer_pool.borrow_mut().begin_frame();
+    /// Set the color matrix applied to the finished frame as
+    /// [`encode`](Self::encode) writes it to the output, e.g.
+    /// [`ColorMatrix::GRAYSCALE`] or a
+    /// [daltonization](ColorMatrix::daltonize) for colorblind viewers.
+    /// Defaults to [`ColorMatrix::IDENTITY`].
+    ///
+    /// Applied at composite time, after any
+    /// [post-processing effects](Self::set_post_effects), so changing it
+    /// does not re-render the cached bottom layer. Any other matrix always
+    /// goes through the intermediate present pass. Has no effect on
+    /// [`paint`](Self::paint).
+    pub fn set_color_matrix(&mut self, color_matrix: ColorMatrix) {
+        self.color_matrix = color_matrix;
+    }
 
-        // Precision loss is acceptable: viewport dimensions are small
-        // relative to f32 mantissa range.
-        #[expect(clippy::cast_precision_loss)]
-        let resolution = [width as f32, height as f32];
+    /// Set the rounded-rectangle mask and border applied to the finished
+    /// frame as [`encode`](Self::encode) writes it to the output, e.g. for a
+    /// borderless transparent window. Defaults to [`OutputMask::NONE`].
+    ///
+    /// Applied at composite time, after any
+    /// [post-processing effects](Self::set_post_effects), so changing it
+    /// does not re-render the cached bottom layer. Any other mask always
+    /// goes through the intermediate present pass. Has no effect on
+    /// [`paint`](Self::paint).
+    pub fn set_output_mask(&mut self, mask: OutputMask) {
+        self.output_mask = mask;
+    }
 
-        // Ensure off-screen textures match the viewport size.
-        if self.fbo_size != [width, height] {
-            self.resize_fbo(device, width, height);
+    /// Replace the layout's background with an opaque [`ChromaKey`] color,
+    /// for capture setups that key it out instead of capturing alpha, or
+    /// `None` to draw the background again (the default).
+    ///
+    /// While set, [`prepare`](Self::prepare) ignores the layout's
+    /// background and [`encode`](Self::encode) fills the viewport with the
+    /// key wherever the layout does not cover it, after any
+    /// [post-processing effects](Self::set_post_effects). Changing whether a
+    /// key is set re-renders the cached bottom layer on the next frame;
+    /// changing the key itself does not. [`paint`](Self::paint) has no
+    /// final pass to key the frame in, so it draws the layout over the key,
+    /// mixing partially covered pixels with it even if the key has a
+    /// threshold.
+    pub fn set_chroma_key(&mut self, chroma_key: Option<ChromaKey>) {
+        if self.chroma_key.is_some() != chroma_key.is_some() {
             self.bottom_layer_dirty = true;
         }
+        self.chroma_key = chroma_key;
+    }
 
-        let new_resolution =
-            self.scene_manager
-                .update_scene(&mut self.allocator, resolution, state, image_cache);
-
-        // Pre-compute blur before starting render passes (needs &mut self).
-        // Extract the blur parameters while scene is borrowed, then drop
-        // the borrow before calling update_blur_cache.
-        let blur_params = {
-            let scene = self.scene_manager.scene();
-            if scene.bottom_layer_changed() || self.bottom_layer_dirty {
-                match scene.background() {
-                    Some(Background::Image(bg_image, _)) if bg_image.blur > 0.0 => {
-                        Some((Arc::clone(&bg_image.image.data), bg_image.blur))
-                    }
-                    _ => None,
-                }
-            } else {
-                None
-            }
-        };
-        if let Some((image_data, blur_value)) = blur_params {
-            self.update_blur_cache(device, queue, &image_data, blur_value);
+    /// Set the post-processing effects [`encode`](Self::encode) runs over
+    /// the finished frame, in order, before writing it to the output. An
+    /// empty slice removes them.
+    ///
+    /// Each effect reads the output of the previous one (the first reads the
+    /// layout) through the declarations described in [`PostEffect`]. The
+    /// last effect's output is written to the viewport like the layout would
+    /// have been, honoring the [`CompositeMode`] and [`AlphaMode`]. Compiles
+    /// the effects' pipelines and reallocates the off-screen textures on the
+    /// next frame, so call this at setup or when the user changes the
+    /// effects, not every frame. Has no effect on [`paint`](Self::paint).
+    ///
+    /// # Errors
+    ///
+    /// Every effect is [validated](PostEffect::validate) first. If one does
+    /// not compile, its error is returned and the previous effects stay in
+    /// place.
+    pub fn set_post_effects(
+        &mut self,
+        device: &wgpu::Device,
+        effects: &[PostEffect],
+    ) -> Result<(), PostEffectError> {
+        for effect in effects {
+            effect.validate()?;
+        }
+        self.post_process = (!effects.is_empty()).then(|| {
+            PostProcess::new(
+                device,
+                self.format,
+                effects,
+                &self.image_texture_bind_group_layout,
+                &self.downsample_params_bind_group_layout,
+                &self.output_params_bind_group_layout,
+            )
+        });
+        self.fbo_size = [0, 0];
+        Ok(())
+    }
+
+    /// Set the affine transform applied to the whole layout, e.g. to rotate
+    /// it for a vertical monitor or mirror it for a camera overlay.
+    ///
+    /// The transform maps the layout, scaled to physical pixels by the
+    /// [scale factor](Self::set_scale_factor), to viewport pixels. If it
+    /// turns the layout on its side, the layout is laid out with the
+    /// viewport's width and height swapped (see
+    /// [`OutputTransform::layout_size`]). Changing the transform
+    /// re-renders the cached bottom layer on the next frame.
+    pub fn set_output_transform(&mut self, transform: OutputTransform) {
+        if self.output_transform != transform {
+            self.output_transform = transform;
+            self.layout_transform = layout_transform(&transform, self.scale_factor);
+            self.bottom_layer_dirty = true;
+        }
+    }
+
+    /// Set how path and image edges are antialiased. Defaults to
+    /// [`Antialiasing::Msaa`].
+    ///
+    /// Recreates the renderer's pipelines and reallocates its off-screen
+    /// textures on the next frame, so call this at setup or when the user
+    /// changes the setting, not every frame. Supersampling applies to
+    /// [`encode`](Self::encode); [`paint`](Self::paint) draws the top layer
+    /// directly at the resolution of the caller's render pass.
+    pub fn set_antialiasing(&mut self, device: &wgpu::Device, antialiasing: Antialiasing) {
+        if self.antialiasing == antialiasing {
+            return;
+        }
+        self.antialiasing = antialiasing;
+        self.pipelines = Pipelines::new(
+            device,
+            self.format,
+            sample_count(antialiasing),
+            &self.path_bind_group_layout,
+            &self.image_uniform_bind_group_layout,
+            &self.image_texture_bind_group_layout,
+            &self.text_bind_group_layout,
+            &self.draw_params_bind_group_layout,
+        );
+        // Force `prepare` to reallocate the off-screen textures.
+        self.fbo_size = [0, 0];
+        self.bottom_layer_dirty = true;
+    }
+
+    /// Set the high-DPI scale factor (physical pixels per logical unit), e.g.
+    /// `1.5` or `2.0` on a 150% or 200% scaled display.
+    ///
+    /// The viewport stays in physical pixels and the layout is rasterized
+    /// at full physical resolution, but it is laid out in logical units:
+    /// text and component sizes match what the layout would get on an
+    /// unscaled display, and the size hint returned when rendering is in
+    /// logical units too. Defaults to `1.0`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale_factor` is not a positive, finite number.
+    pub fn set_scale_factor(&mut self, scale_factor: f32) {
+        assert!(
+            scale_factor.is_finite() && scale_factor > 0.0,
+            "scale factor must be positive and finite, got {scale_factor}"
+        );
+        if (self.scale_factor - scale_factor).abs() > f32::EPSILON {
+            self.scale_factor = scale_factor;
+            self.layout_transform = layout_transform(&self.output_transform, scale_factor);
+            self.bottom_layer_dirty = true;
+        }
+    }
+
+    /// Set how text labels are drawn. Defaults to
+    /// [`TextRendering::Geometry`].
+    ///
+    /// Changing the mode re-renders the cached bottom layer on the next
+    /// frame.
+    pub fn set_text_rendering(&mut self, text_rendering: TextRendering) {
+        if self.text_rendering != text_rendering {
+            self.text_rendering = text_rendering;
+            self.bottom_layer_dirty = true;
+        }
+    }
+
+    /// Set the dither added to colors to hide banding in gradients and
+    /// blurred backgrounds. Defaults to [`Dithering::None`].
+    ///
+    /// Changing it re-renders the cached bottom layer on the next frame.
+    pub fn set_dithering(&mut self, dithering: Dithering) {
+        if self.dithering != dithering {
+            self.dithering = dithering;
+            // The supersampling filter's parameters live with the targets.
+            if self.antialiasing.supersample_factor() > 1 {
+                self.fbo_size = [0, 0];
+            }
+            self.bottom_layer_dirty = true;
+        }
+    }
+
+    /// Set how bright, in nits, the layout's white is shown on
+    /// [`Rgba16Float`](wgpu::TextureFormat::Rgba16Float) (scRGB) outputs.
+    /// Defaults to [`SCRGB_WHITE_NITS`], which leaves colors unchanged.
+    ///
+    /// HDR desktops show SDR content brighter than scRGB's reference white,
+    /// so pass their SDR white level to keep the layout from looking dim
+    /// next to other windows. Other formats ignore the setting. Changing it
+    /// re-renders the cached bottom layer on the next frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `nits` is not positive and finite.
+    pub fn set_sdr_white_level(&mut self, nits: f32) {
+        assert!(
+            nits.is_finite() && nits > 0.0,
+            "SDR white level must be positive and finite, got {nits}"
+        );
+        if (self.sdr_white_level - nits).abs() > f32::EPSILON {
+            self.sdr_white_level = nits;
+            // The supersampling filter's parameters live with the targets.
+            if self.antialiasing.supersample_factor() > 1 {
+                self.fbo_size = [0, 0];
+            }
+            self.bottom_layer_dirty = true;
+        }
+    }
+
+    /// Set how the layout's stroked paths are joined, capped, and dashed.
+    /// Defaults to [`StrokeStyle::default`].
+    ///
+    /// The style applies to every stroke in the layout, but not to text
+    /// outlines. Changing it re-renders the cached bottom layer on the next
+    /// frame.
+    pub fn set_stroke_style(&mut self, stroke_style: StrokeStyle) {
+        if self.stroke_style != stroke_style {
+            self.stroke_style = stroke_style;
+            self.bottom_layer_dirty = true;
+        }
+    }
+
+    /// Set the outline drawn around every label glyph, beneath its fill,
+    /// or `None` for no outline (the default).
+    ///
+    /// Changing the outline re-renders the cached bottom layer on the next
+    /// frame.
+    pub fn set_text_outline(&mut self, text_outline: Option<TextOutline>) {
+        if self.text_outline != text_outline {
+            self.text_outline = text_outline;
+            self.bottom_layer_dirty = true;
+        }
+    }
+
+    /// Set the shape of the shadows drawn under labels. Defaults to the
+    /// hard-edged shadow of [`TextShadow::default`].
+    ///
+    /// Whether a label has a shadow, and its color, is still up to the
+    /// layout. Changing the shape re-renders the cached bottom layer on the
+    /// next frame.
+    pub fn set_text_shadow(&mut self, text_shadow: TextShadow) {
+        if self.text_shadow != text_shadow {
+            self.text_shadow = text_shadow;
+            self.bottom_layer_dirty = true;
+        }
+    }
+
+    /// Set the sample count of the render passes handed to
+    /// [`paint`](Self::paint).
+    ///
+    /// Defaults to `1`. Recreates the paint pipelines, so call this once at
+    /// setup (or when the host's MSAA setting changes), not every frame.
+    pub fn set_paint_sample_count(&mut self, device: &wgpu::Device, sample_count: u32) {
+        self.paint_pipelines = Pipelines::new(
+            device,
+            self.format,
+            sample_count,
+            &self.path_bind_group_layout,
+            &self.image_uniform_bind_group_layout,
+            &self.image_texture_bind_group_layout,
+            &self.text_bind_group_layout,
+            &self.draw_params_bind_group_layout,
+        );
+    }
+
+    /// Set the opacity of the whole layout, from `0.0` (invisible) to `1.0`
+    /// (opaque, the default), stopping any running fade.
+    ///
+    /// The opacity fades the finished frame as a whole as
+    /// [`encode`](Self::encode) writes it to the output, so overlapping
+    /// entities do not show through each other, and changing it does not
+    /// re-render the cached layer. Any opacity below `1.0` always goes
+    /// through the intermediate present pass. [`paint`](Self::paint) has
+    /// no intermediate texture to fade, so it multiplies each of its draws
+    /// by the opacity instead, and overlapping entities show through each
+    /// other.
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.opacity.set(opacity);
+    }
+
+    /// The opacity of the whole layout, as of the last prepared frame.
+    pub fn opacity(&self) -> f32 {
+        self.opacity.get()
+    }
+
+    /// Start fading the whole layout from its current opacity to `opacity`
+    /// over `duration`, e.g. to fade the splits out when the timer is reset.
+    ///
+    /// The fade advances each time a frame is [prepared](Self::prepare);
+    /// keep preparing frames while [`is_fading`](Self::is_fading).
+    pub fn fade_to(&mut self, opacity: f32, duration: Duration) {
+        self.opacity.fade_to(opacity, duration, Instant::now());
+    }
+
+    /// Whether a fade started with [`fade_to`](Self::fade_to) is still
+    /// running.
+    pub fn is_fading(&self) -> bool {
+        self.opacity.is_fading()
+    }
+
+    /// Counts of the last frame: how many entities were drawn, how many
+    /// were culled because they lie entirely outside the viewport, and how
+    /// many strokes hit or missed the stroke cache.
+    pub fn frame_stats(&self) -> FrameStats {
+        self.frame_stats.get()
+    }
+
+    /// Render the layout to the given output texture view.
+    ///
+    /// This is a convenience wrapper around [`prepare`](Self::prepare) and
+    /// [`encode`](Self::encode) that creates its own command encoder and
+    /// submits it to `queue`.
+    ///
+    /// `viewport` selects the rectangle of `output_view` the layout is drawn
+    /// into; pass a plain `[width, height]` to cover the whole output.
+    ///
+    /// Returns an optional new resolution hint from livesplit-core's layout
+    /// engine, in logical units, indicating the layout's preferred size
+    /// changed. The caller can use this to resize the window or viewport.
+    ///
+    /// # Panics
+    ///
+    /// Panics if off-screen textures have not been initialized (i.e., if
+    /// the viewport dimensions are zero).
+    #[allow(clippy::too_many_arguments)]
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        state: &LayoutState,
+        image_cache: &ImageCache,
+        viewport: impl Into<Viewport>,
+        output_view: &wgpu::TextureView,
+        draw_background: bool,
+    ) -> Option<[f32; 2]> {
+        let viewport = viewport.into();
+        if viewport.is_empty() {
+            return None;
+        }
+
+        let mut encoder =
+            device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        let new_resolution = self.prepare(
+            device,
+            queue,
+            &mut encoder,
+            state,
+            image_cache,
+            viewport,
+            draw_background,
+        );
+        self.encode(&mut encoder, output_view);
+        queue.submit(std::iter::once(encoder.finish()));
+
+        new_resolution
+    }
+
+    /// Update the scene and upload all per-frame data, without submitting.
+    ///
+    /// Writes uniforms, vertices, and indices through `queue.write_buffer`
+    /// and, if the cached bottom layer is stale, records the off-screen pass
+    /// that re-renders it into `encoder`. The composite draws are kept
+    /// until the next call and replayed by [`encode`](Self::encode) or
+    /// [`paint`](Self::paint). Call this exactly once per frame, and submit
+    /// `encoder` before (or together with) the work that replays the frame.
+    ///
+    /// `viewport` is the rectangle of the eventual target the layout will
+    /// be drawn into; the layout is laid out at its size.
+    ///
+    /// Returns an optional new resolution hint from livesplit-core's layout
+    /// engine, as for [`render`](Self::render).
+    ///
+    /// # Panics
+    ///
+    /// Panics if off-screen textures have not been initialized.
+    #[allow(clippy::too_many_arguments)]
+    #[expect(clippy::too_many_lines)] // Records the off-screen and composite draws
+    pub fn prepare(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        encoder: &mut wgpu::CommandEncoder,
+        state: &LayoutState,
+        image_cache: &ImageCache,
+        viewport: impl Into<Viewport>,
+        draw_background: bool,
+    ) -> Option<[f32; 2]> {
+        self.prepared = None;
+        self.frame_stats.set(FrameStats::default());
+        self.opacity.advance(Instant::now());
+        // A chroma key takes the background's place.
+        let draw_background = draw_background && self.chroma_key.is_none();
+        self.viewport = viewport.into();
+        if self.viewport.is_empty() {
+            return None;
+        }
+        let [width, height] = self.viewport.size();
+
+        self.buffer_pool.borrow_mut().begin_frame();
+        self.glyph_atlas.get_mut().begin_frame();
+
+        // Precision loss is acceptable: viewport dimensions are small
+        // relative to f32 mantissa range.
+        #[expect(clippy::cast_precision_loss)]
+        let resolution = [width as f32, height as f32];
+        // The layout is laid out in logical units, possibly on its side (see
+        // `OutputTransform::layout_size`).
+        let layout_resolution =
+            layout_resolution([width, height], &self.output_transform, self.scale_factor);
+
+        // Ensure off-screen textures match the viewport size.
+        if self.fbo_size != [width, height] {
+            self.resize_fbo(device, width, height);
+            self.bottom_layer_dirty = true;
+        }
+        if let Some(post_process) = &self.post_process {
+            post_process.update(queue, resolution);
+        }
+        queue.write_buffer(
+            &self.output_params_buffer,
+            0,
+            bytemuck::bytes_of(&self.output_params()),
+        );
+        queue.write_buffer(
+            &self.paint_draw_params_buffer,
+            0,
+            bytemuck::bytes_of(&DrawParams::with_opacity(self.opacity.get())),
+        );
+
+        let new_resolution = self.scene_manager.update_scene(
+            &mut self.allocator,
+            layout_resolution,
+            state,
+            image_cache,
+        );
+
+        // Pre-compute blur before recording draws (needs &mut self).
+        // Extract the blur parameters while scene is borrowed, then drop
+        // the borrow before calling update_blur_cache.
+        let blur_params = {
+            let scene = self.scene_manager.scene();
+            if scene.bottom_layer_changed() || self.bottom_layer_dirty {
+                blurred_background(scene.background().as_ref().filter(|_| draw_background))
+                    .map(|(image, blur)| (Arc::clone(&image.data), blur))
+            } else {
+                None
+            }
+        };
+        if let Some((image_data, blur_value)) = blur_params {
+            self.update_blur_cache(device, queue, &image_data, blur_value);
         }
 
         let scene = self.scene_manager.scene();
         let bottom_layer_changed = scene.bottom_layer_changed();
 
+        if bottom_layer_changed || self.bottom_layer_dirty {
+            // Render bottom layer into MSAA texture, resolving to fbo_texture,
+            // at full opacity.
+            let rectangle = scene.rectangle();
+            let list = DisplayList::bottom_layer(
+                &self.display_settings(resolution, layout_resolution),
+                scene.background().as_ref().filter(|_| draw_background),
+                rectangle.as_ref(),
+                scene.bottom_layer(),
+            );
+            let mut commands = Vec::new();
+            self.record_display_list(device, queue, &mut commands, &list, resolution);
+            self.flush_shadows(encoder);
+
+            let msaa_view = self
+                .msaa_texture_view
+                .as_ref()
+                .expect("MSAA texture not initialized");
+            let fbo_view = self
+                .fbo_texture_view
+                .as_ref()
+                .expect("FBO texture not initialized");
+            // A single-sample target cannot be resolved, so the bottom
+            // layer is drawn straight into the cached texture.
+            let (view, resolve_target) = match downsample_filter(self.antialiasing) {
+                None => (msaa_view, Some(fbo_view)),
+                Some(_) => (fbo_view, None),
+            };
+
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("bottom_layer_pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view,
+                    resolve_target,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            self.execute(
+                &mut pass,
+                &self.pipelines,
+                &self.opaque_draw_params_bind_group,
+                &commands,
+            );
+        }
+
+        // Composite: blit cached bottom layer + render top layer, at full
+        // opacity; the present pass fades the whole frame.
+        let list = DisplayList::top_layer(
+            &self.display_settings(resolution, layout_resolution),
+            scene.top_layer(),
+        );
+        let mut commands = Vec::new();
+        self.record_display_list(device, queue, &mut commands, &list, resolution);
+        self.flush_shadows(encoder);
+
+        // `paint` draws the layout over the key instead.
+        let mut key_commands = Vec::new();
+        if let Some(key) = &self.chroma_key {
+            let list = DisplayList::key_layer(
+                &self.display_settings(resolution, layout_resolution),
+                key,
+                scene.rectangle().as_ref(),
+            );
+            self.record_display_list(device, queue, &mut key_commands, &list, resolution);
+        }
+
+        // Glyphs added to the atlas while recording either layer are
+        // uploaded once; the write lands before `encoder` is submitted.
+        self.upload_glyph_atlas(queue);
+
+        self.bottom_layer_dirty = false;
+        self.prepared = Some(commands);
+        self.prepared_key = key_commands;
+
+        new_resolution
+    }
+
+    /// Record the composite pass for the last prepared frame into a
+    /// caller-provided command encoder.
+    ///
+    /// Blits the cached bottom layer and draws the top layer into the
+    /// renderer's MSAA texture. If the viewport covers all of `output_view`,
+    /// the composite mode is [`CompositeMode::Replace`], the alpha mode is
+    /// [`AlphaMode::Premultiplied`], there is no color matrix, output mask,
+    /// or chroma key, and the layout is fully opaque, the MSAA texture is
+    /// resolved directly into it. Otherwise it is resolved into an
+    /// intermediate texture that a final present pass copies or blends into
+    /// the viewport rectangle, leaving the rest of the output untouched.
+    ///
+    /// When supersampling, a downsample pass filters the enlarged frame into
+    /// the viewport rectangle instead. With
+    /// [post-processing effects](Self::set_post_effects), the frame is
+    /// resolved or downsampled into the first effect's input, and the
+    /// present pass writes the last effect's output.
+    ///
+    /// Does nothing if [`prepare`](Self::prepare) was not called or was
+    /// given a zero-sized viewport. The caller is responsible for submitting
+    /// `encoder`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if off-screen textures have not been initialized.
+    pub fn encode(&self, encoder: &mut wgpu::CommandEncoder, output_view: &wgpu::TextureView) {
+        let Some(commands) = &self.prepared else {
+            return;
+        };
         let msaa_view = self
             .msaa_texture_view
             .as_ref()
             .expect("MSAA texture not initialized");
-        let fbo_view = self
-            .fbo_texture_view
-            .as_ref()
-            .expect("FBO texture not initialized");
 
-        if bottom_layer_changed || self.bottom_layer_dirty {
-            // Render bottom layer into MSAA texture, resolving to fbo_texture.
-            let mut encoder =
-                device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        if downsample_filter(self.antialiasing).is_some() {
+            self.encode_composite_pass(encoder, msaa_view, None, commands);
+
+            let ssaa_bind_group = self
+                .ssaa_bind_group
+                .as_ref()
+                .expect("supersampling bind group not initialized");
+            let params_bind_group = self
+                .downsample_params_bind_group
+                .as_ref()
+                .expect("downsample parameters not initialized");
+            if let Some(post_process) = &self.post_process {
+                post_process.encode_downsample(encoder, [ssaa_bind_group, params_bind_group]);
+                let bind_group = post_process.encode(encoder);
+                self.encode_present(encoder, output_view, bind_group);
+                return;
+            }
+            let pipeline = match self.composite_mode {
+                CompositeMode::Replace => &self.downsample_pipeline,
+                CompositeMode::Blend => &self.downsample_blend_pipeline,
+            };
+            self.encode_viewport_pass(
+                encoder,
+                output_view,
+                "downsample_pass",
+                pipeline,
+                &[
+                    ssaa_bind_group,
+                    params_bind_group,
+                    &self.output_params_bind_group,
+                ],
+            );
+            return;
+        }
 
-            {
-                let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                    label: Some("bottom_layer_pass"),
-                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                        view: msaa_view,
-                        resolve_target: Some(fbo_view),
-                        ops: wgpu::Operations {
-                            load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
-                            store: wgpu::StoreOp::Store,
-                        },
-                        depth_slice: None,
-                    })],
-                    depth_stencil_attachment: None,
-                    timestamp_writes: None,
-                    occlusion_query_set: None,
-                    multiview_mask: None,
-                });
+        let output = output_view.texture();
+        let direct = self.post_process.is_none()
+            && self.composite_mode == CompositeMode::Replace
+            && self.alpha_mode == AlphaMode::Premultiplied
+            && self.color_matrix.is_identity()
+            && self.output_mask.is_none()
+            && self.chroma_key.is_none()
+            && self.opacity.get() >= 1.0
+            && self.viewport.covers([output.width(), output.height()]);
+        let resolve_target = if direct {
+            output_view
+        } else if let Some(post_process) = &self.post_process {
+            post_process.input_view()
+        } else {
+            self.composite_texture_view
+                .as_ref()
+                .expect("composite texture not initialized")
+        };
 
-                if draw_background {
-                    if let Some(bg) = scene.background() {
-                        self.render_background(device, queue, &mut pass, bg, resolution);
-                    }
-                }
+        self.encode_composite_pass(encoder, msaa_view, Some(resolve_target), commands);
 
-                for entity in scene.bottom_layer() {
-                    self.render_entity(device, queue, &mut pass, entity, resolution);
-                }
+        if !direct {
+            let bind_group = match &self.post_process {
+                Some(post_process) => post_process.encode(encoder),
+                None => self
+                    .composite_bind_group
+                    .as_ref()
+                    .expect("composite bind group not initialized"),
+            };
+            self.encode_present(encoder, output_view, bind_group);
+        }
+    }
+
+    /// Record the pass that draws the prepared `commands` (the cached bottom
+    /// layer blit and the top layer) into the MSAA texture, resolving it into
+    /// `resolve_target` if given.
+    fn encode_composite_pass(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        msaa_view: &wgpu::TextureView,
+        resolve_target: Option<&wgpu::TextureView>,
+        commands: &[DrawCommand],
+    ) {
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("composite_pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: msaa_view,
+                resolve_target,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            multiview_mask: None,
+        });
+        self.execute(
+            &mut pass,
+            &self.pipelines,
+            &self.opaque_draw_params_bind_group,
+            commands,
+        );
+    }
+
+    /// Record the present pass, which copies or blends the texture bound by
+    /// `bind_group` into the viewport rectangle of `output_view`.
+    fn encode_present(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        output_view: &wgpu::TextureView,
+        bind_group: &wgpu::BindGroup,
+    ) {
+        let pipeline = match self.composite_mode {
+            CompositeMode::Replace => &self.present_pipeline,
+            CompositeMode::Blend => &self.present_blend_pipeline,
+        };
+        self.encode_viewport_pass(
+            encoder,
+            output_view,
+            "present_pass",
+            pipeline,
+            &[bind_group, &self.output_params_bind_group],
+        );
+    }
+
+    /// Record a pass that draws a full-viewport triangle with `pipeline`
+    /// into the viewport rectangle of `output_view`, preserving the
+    /// output's other pixels. Used to copy, blend, or downsample the
+    /// finished frame into the output.
+    fn encode_viewport_pass(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        output_view: &wgpu::TextureView,
+        label: &str,
+        pipeline: &wgpu::RenderPipeline,
+        bind_groups: &[&wgpu::BindGroup],
+    ) {
+        // The scissor rectangle must lie within the output; clip the part of
+        // the viewport that hangs off its edges.
+        let output = output_view.texture();
+        let Viewport {
+            x,
+            y,
+            width,
+            height,
+        } = self.viewport;
+        let scissor_width = width.min(output.width().saturating_sub(x));
+        let scissor_height = height.min(output.height().saturating_sub(y));
+        if scissor_width == 0 || scissor_height == 0 {
+            return;
+        }
+
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some(label),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: output_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+            multiview_mask: None,
+        });
+        // Precision loss is acceptable: viewport coordinates are small
+        // relative to f32 mantissa range.
+        #[expect(clippy::cast_precision_loss)]
+        pass.set_viewport(x as f32, y as f32, width as f32, height as f32, 0.0, 1.0);
+        pass.set_scissor_rect(x, y, scissor_width, scissor_height);
+        pass.set_pipeline(pipeline);
+        for (index, bind_group) in (0..).zip(bind_groups) {
+            pass.set_bind_group(index, *bind_group, &[]);
+        }
+        pass.draw(0..3, 0..1);
+    }
+
+    /// Draw the last prepared frame into a caller-provided render pass.
+    ///
+    /// The pass must target a texture of the format given to
+    /// [`new`](Self::new), with the sample count configured via
+    /// [`set_paint_sample_count`](Self::set_paint_sample_count), and
+    /// `target_size` is the `[width, height]` of that texture. The pass's
+    /// viewport is set to the viewport given to [`prepare`](Self::prepare)
+    /// and its scissor rectangle to the part of it that lies within the
+    /// target.
+    /// The layout's premultiplied pixels are blended over the pass's
+    /// existing contents, whatever the [`AlphaMode`], with each draw faded
+    /// by the [opacity](Self::set_opacity). Does nothing if no frame was
+    /// prepared.
+    pub fn paint(&self, pass: &mut wgpu::RenderPass<'_>, target_size: [u32; 2]) {
+        if let Some(commands) = &self.prepared {
+            let Viewport {
+                x,
+                y,
+                width,
+                height,
+            } = self.viewport;
+            // The scissor rectangle must lie within the target, as in
+            // `encode_viewport_pass`.
+            let [target_width, target_height] = target_size;
+            let scissor_width = width.min(target_width.saturating_sub(x));
+            let scissor_height = height.min(target_height.saturating_sub(y));
+            if scissor_width == 0 || scissor_height == 0 {
+                return;
             }
+            #[expect(clippy::cast_precision_loss)]
+            pass.set_viewport(x as f32, y as f32, width as f32, height as f32, 0.0, 1.0);
+            pass.set_scissor_rect(x, y, scissor_width, scissor_height);
+            // The key stays opaque however faded the layout over it is.
+            self.execute(
+                pass,
+                &self.paint_pipelines,
+                &self.opaque_draw_params_bind_group,
+                &self.prepared_key,
+            );
+            self.execute(
+                pass,
+                &self.paint_pipelines,
+                &self.paint_draw_params_bind_group,
+                commands,
+            );
+        }
+    }
 
-            queue.submit(std::iter::once(encoder.finish()));
-            self.bottom_layer_dirty = false;
+    /// Replay recorded draw commands into a render pass, each multiplied by
+    /// the [`DrawParams`] bound by `draw_params`.
+    fn execute(
+        &self,
+        pass: &mut wgpu::RenderPass<'_>,
+        pipelines: &Pipelines,
+        draw_params: &wgpu::BindGroup,
+        commands: &[DrawCommand],
+    ) {
+        for command in commands {
+            match command {
+                DrawCommand::Path {
+                    uniform_bind_group,
+                    uniform_offset,
+                    vertex_buffer,
+                    vertices,
+                    index_buffer,
+                    indices,
+                    index_count,
+                } => {
+                    pass.set_pipeline(&pipelines.path);
+                    pass.set_bind_group(0, uniform_bind_group, &[*uniform_offset]);
+                    pass.set_bind_group(1, draw_params, &[]);
+                    pass.set_vertex_buffer(0, vertex_buffer.slice(vertices.clone()));
+                    pass.set_index_buffer(
+                        index_buffer.slice(indices.clone()),
+                        wgpu::IndexFormat::Uint32,
+                    );
+                    pass.draw_indexed(0..*index_count, 0, 0..1);
+                }
+                DrawCommand::TexturedRect {
+                    uniform_bind_group,
+                    uniform_offset,
+                    texture_bind_group,
+                } => {
+                    // Initialized by draw_textured_rect when the command was
+                    // recorded.
+                    let rect_bufs = self
+                        .rect_buffers
+                        .get()
+                        .expect("rect buffers not initialized");
+                    pass.set_pipeline(&pipelines.image);
+                    pass.set_bind_group(0, uniform_bind_group, &[*uniform_offset]);
+                    pass.set_bind_group(1, &**texture_bind_group, &[]);
+                    pass.set_bind_group(2, draw_params, &[]);
+                    pass.set_vertex_buffer(0, rect_bufs.vertex.slice(..));
+                    pass.set_index_buffer(rect_bufs.index.slice(..), wgpu::IndexFormat::Uint32);
+                    pass.draw_indexed(0..rect_bufs.index_count, 0, 0..1);
+                }
+                DrawCommand::Glyph {
+                    uniform_bind_group,
+                    uniform_offset,
+                } => {
+                    // Initialized by draw_atlas_glyph when the
+                    // command was recorded.
+                    let rect_bufs = self
+                        .rect_buffers
+                        .get()
+                        .expect("rect buffers not initialized");
+                    pass.set_pipeline(&pipelines.text);
+                    pass.set_bind_group(0, uniform_bind_group, &[*uniform_offset]);
+                    pass.set_bind_group(1, &self.atlas_bind_group, &[]);
+                    pass.set_bind_group(2, draw_params, &[]);
+                    pass.set_vertex_buffer(0, rect_bufs.vertex.slice(..));
+                    pass.set_index_buffer(rect_bufs.index.slice(..), wgpu::IndexFormat::Uint32);
+                    pass.draw_indexed(0..rect_bufs.index_count, 0, 0..1);
+                }
+                DrawCommand::Shadow {
+                    uniform_bind_group,
+                    uniform_offset,
+                    texture_bind_group,
+                } => {
+                    // Initialized by draw_blurred_shadow when the command
+                    // was recorded.
+                    let rect_bufs = self
+                        .rect_buffers
+                        .get()
+                        .expect("rect buffers not initialized");
+                    pass.set_pipeline(&pipelines.text);
+                    pass.set_bind_group(0, uniform_bind_group, &[*uniform_offset]);
+                    pass.set_bind_group(1, texture_bind_group, &[]);
+                    pass.set_bind_group(2, draw_params, &[]);
+                    pass.set_vertex_buffer(0, rect_bufs.vertex.slice(..));
+                    pass.set_index_buffer(rect_bufs.index.slice(..), wgpu::IndexFormat::Uint32);
+                    pass.draw_indexed(0..rect_bufs.index_count, 0, 0..1);
+                }
+            }
         }
+    }
 
-        // Composite: blit cached bottom layer + render top layer into MSAA,
-        // resolving to the output.
-        let mut encoder =
-            device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    /// Record the passes that draw and blur the queued text shadows into
+    /// the shadow textures, then empty the queue.
+    ///
+    /// Must be recorded before the pass that replays the shadows' draws,
+    /// and again before the shadow textures are reused for the next layer.
+    fn flush_shadows(&self, encoder: &mut wgpu::CommandEncoder) {
+        let mut batch = self.shadow_batch.borrow_mut();
+        if batch.blurs.is_empty() {
+            return;
+        }
+        let targets = self
+            .shadow_targets
+            .as_ref()
+            .expect("shadow textures not initialized");
 
         {
             let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("composite_pass"),
+                label: Some("shadow_mask_pass"),
                 color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: msaa_view,
-                    resolve_target: Some(output_view),
+                    view: &targets.views[0],
+                    resolve_target: None,
                     ops: wgpu::Operations {
                         load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
                         store: wgpu::StoreOp::Store,
@@ -447,19 +2074,54 @@ impl WgpuRenderer {
                 occlusion_query_set: None,
                 multiview_mask: None,
             });
+            self.execute(
+                &mut pass,
+                &self.shadow_pipelines,
+                &self.opaque_draw_params_bind_group,
+                &batch.masks,
+            );
+        }
 
-            // Blit cached bottom layer.
-            self.blit_fbo(device, queue, &mut pass, resolution);
-
-            // Render top layer.
-            for entity in scene.top_layer() {
-                self.render_entity(device, queue, &mut pass, entity, resolution);
+        // Horizontally from the masks into the second texture, then
+        // vertically back into the first. Each shadow only touches its own
+        // slot, so the rest of the first texture can be kept.
+        for (pass_index, (source, target)) in [(0, 1), (1, 0)].into_iter().enumerate() {
+            let load = if target == 1 {
+                wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT)
+            } else {
+                wgpu::LoadOp::Load
+            };
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("shadow_blur_pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &targets.views[target],
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load,
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+                multiview_mask: None,
+            });
+            pass.set_pipeline(&self.shadow_blur_pipeline);
+            pass.set_bind_group(0, &targets.bind_groups[source], &[]);
+            for blur in &batch.blurs {
+                let [x, y, width, height] = blur.slot;
+                pass.set_scissor_rect(x, y, width, height);
+                pass.set_bind_group(
+                    1,
+                    &blur.uniform_bind_group,
+                    &[blur.uniform_offsets[pass_index]],
+                );
+                pass.draw(0..3, 0..1);
             }
         }
 
-        queue.submit(std::iter::once(encoder.finish()));
-
-        new_resolution
+        *batch = ShadowBatch::new(self.fbo_size);
     }
 
     /// Pre-compute the blurred background texture if needed.
@@ -497,8 +2159,6 @@ impl WgpuRenderer {
 
         let blurred_rgba = blurred.to_rgba8();
 
-        // Image source data is sRGB RGBA8, so Rgba8UnormSrgb is the correct
-        // format regardless of the output surface format.
         let texture = device.create_texture(&wgpu::TextureDescriptor {
             label: Some("blur_texture"),
             size: wgpu::Extent3d {
@@ -509,7 +2169,7 @@ impl WgpuRenderer {
             mip_level_count: 1,
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            format: self.image_format(),
             usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
             view_formats: &[],
         });
@@ -559,51 +2219,93 @@ impl WgpuRenderer {
         });
     }
 
-    /// Render a single scene entity.
-    fn render_entity(
+    /// Record the draws of a display list, falling back to hard-edged
+    /// shadows when no shadow slot is free.
+    fn record_display_list(
         &self,
         device: &wgpu::Device,
         queue: &wgpu::Queue,
-        pass: &mut wgpu::RenderPass<'_>,
-        entity: &Entity<Option<WgpuPath>, WgpuImage, WgpuLabel>,
+        commands: &mut Vec<DrawCommand>,
+        list: &DisplayList<'_, WgpuImage>,
         resolution: [f32; 2],
     ) {
-        match entity {
-            Entity::FillPath(path, shader, transform) => {
-                if let Some(path) = path.as_ref() {
-                    self.draw_path(device, queue, pass, path, shader, transform, resolution);
-                }
-            }
-            Entity::StrokePath(path, stroke_width, color, transform) => {
-                if let Some(path) = path.as_ref() {
-                    if let Some(stroked) = tessellate_stroke(path, *stroke_width) {
-                        let shader = FillShader::SolidColor(*color);
-                        self.draw_path(
-                            device, queue, pass, &stroked, &shader, transform, resolution,
-                        );
-                    }
+        for item in list.items() {
+            match item {
+                DisplayItem::CachedLayer => {
+                    self.blit_fbo(device, queue, commands, resolution);
                 }
-            }
-            Entity::Image(image, transform) => {
-                self.draw_image(device, queue, pass, image, transform, resolution);
-            }
-            Entity::Label(label, shader, text_shadow, transform) => {
-                self.draw_label(
+                DisplayItem::Path {
+                    path,
+                    fill,
+                    transform,
+                    dilation,
+                } => self.draw_path(
+                    device, queue, commands, path, fill, transform, resolution, *dilation,
+                ),
+                DisplayItem::Glyph {
+                    path,
+                    fill,
+                    transform,
+                    effects,
+                } => self.draw_glyph(
                     device,
                     queue,
-                    pass,
-                    label,
-                    shader,
-                    text_shadow.as_ref(),
+                    commands,
+                    path,
+                    fill,
                     transform,
                     resolution,
-                );
+                    effects.as_ref(),
+                ),
+                DisplayItem::Shadow(shadow) => {
+                    if !self.draw_blurred_shadow(device, queue, commands, shadow, resolution) {
+                        let fill = Fill::solid(shadow.color);
+                        for (path, transform) in &shadow.masks {
+                            self.draw_path(
+                                device,
+                                queue,
+                                commands,
+                                path,
+                                &fill,
+                                transform,
+                                resolution,
+                                shadow.spread,
+                            );
+                        }
+                    }
+                }
+                DisplayItem::Image(image) => {
+                    self.draw_image(device, queue, commands, image, resolution);
+                }
             }
         }
+        let mut stats = self.frame_stats.get();
+        stats.add(list.stats());
+        self.frame_stats.set(stats);
     }
 
-    /// Lazily create or refresh the cached path and image uniform bind
-    /// groups. Both bind the entire pool uniform buffer at offset 0 with
+    /// The settings display lists are built with, for a viewport of
+    /// `resolution` pixels and a layout of `layout_resolution` units.
+    fn display_settings(
+        &self,
+        resolution: [f32; 2],
+        layout_resolution: [f32; 2],
+    ) -> DisplaySettings<'_> {
+        DisplaySettings {
+            resolution,
+            layout_resolution,
+            layout_transform: &self.layout_transform,
+            scale_factor: self.scale_factor,
+            stroke_style: &self.stroke_style,
+            text_shadow: &self.text_shadow,
+            text_outline: self.text_outline,
+            text_rendering: self.text_rendering,
+            encoding: self.color_encoding(),
+        }
+    }
+
+    /// Lazily create or refresh the cached path, image, and text uniform
+    /// bind groups. All bind the entire pool uniform buffer at offset 0 with
     /// `has_dynamic_offset: true`, so the per-draw offset is passed via
     /// `set_bind_group`'s dynamic offset array.
     ///
@@ -648,10 +2350,49 @@ impl WgpuRenderer {
                 }],
             }));
 
+        *self.text_uniform_bind_group.borrow_mut() =
+            Some(device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("text_uniform_bind_group"),
+                layout: &self.text_bind_group_layout,
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                        buffer: &pool.uniform_buffer,
+                        offset: 0,
+                        size: std::num::NonZeroU64::new(
+                            std::mem::size_of::<TextUniformData>() as u64
+                        ),
+                    }),
+                }],
+            }));
+
+        *self.shadow_blur_uniform_bind_group.borrow_mut() =
+            Some(device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("shadow_blur_uniform_bind_group"),
+                layout: &self.shadow_blur_bind_group_layout,
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                        buffer: &pool.uniform_buffer,
+                        offset: 0,
+                        size: std::num::NonZeroU64::new(
+                            std::mem::size_of::<ShadowBlurParams>() as u64
+                        ),
+                    }),
+                }],
+            }));
+
         *self.cached_uniform_generation.borrow_mut() = gen;
     }
 
-    /// Draw a filled path with the given shader and transform.
+    /// The fill of `path` tessellated finely enough for the size it is
+    /// drawn at with `transform` (see [`curve_tolerance`]).
+    fn fill_for(&self, path: &WgpuPath, transform: &Transform) -> WgpuPath {
+        fill_at_tolerance(path, curve_tolerance(transform, &self.layout_transform))
+    }
+
+    /// Draw a tessellated path with the given fill and transform, grown by
+    /// `dilation` pixels along its edge normals.
     ///
     /// Uniform, vertex, and index data are sub-allocated from the per-frame
     /// buffer pool, avoiding per-draw-call GPU buffer creation.
@@ -660,209 +2401,472 @@ impl WgpuRenderer {
         &self,
         device: &wgpu::Device,
         queue: &wgpu::Queue,
-        pass: &mut wgpu::RenderPass<'_>,
-        path: &WgpuPath,
-        shader: &FillShader,
-        transform: &Transform,
+        commands: &mut Vec<DrawCommand>,
+        path: &WgpuPath,
+        fill: &Fill,
+        transform: &Transform,
+        resolution: [f32; 2],
+        dilation: f32,
+    ) {
+        if path.vertices.is_empty() || path.indices.is_empty() {
+            return;
+        }
+
+        let uniform_data = PathUniformData {
+            dilation,
+            ..self.build_path_uniforms(fill, transform, resolution)
+        };
+        self.push_path(device, queue, commands, path, &uniform_data);
+    }
+
+    /// Record a path draw with prepared uniforms.
+    fn push_path(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        commands: &mut Vec<DrawCommand>,
+        path: &WgpuPath,
+        uniform_data: &PathUniformData,
+    ) {
+        // The coverage fringe is drawn with the same pipeline, right after
+        // the shape itself.
+        let (vertices, indices) =
+            path.draw_geometry(uniform_data.fringe_width > 0.0, uniform_data.dilation > 0.0);
+
+        let (uniform_alloc, vertex_alloc, index_alloc) = {
+            let mut pool = self.buffer_pool.borrow_mut();
+            let u = pool.alloc_uniform(device, queue, bytemuck::bytes_of(uniform_data));
+            let v = pool.alloc_vertex(device, queue, bytemuck::cast_slice(vertices));
+            let i = pool.alloc_index(device, queue, bytemuck::cast_slice(indices));
+            (u, v, i)
+        };
+
+        self.ensure_uniform_bind_groups(device);
+        let pool = self.buffer_pool.borrow();
+        let bg = self.path_uniform_bind_group.borrow();
+        let bind_group = bg.as_ref().expect("path bind group not initialized");
+
+        // Offsets and counts are bounded by the pool's buffer sizes, which
+        // stay far below u32::MAX.
+        #[expect(clippy::cast_possible_truncation)]
+        commands.push(DrawCommand::Path {
+            uniform_bind_group: bind_group.clone(),
+            uniform_offset: uniform_alloc.offset as u32,
+            vertex_buffer: pool.vertex_buffer.clone(),
+            vertices: vertex_alloc.offset..vertex_alloc.offset + vertex_alloc.size,
+            index_buffer: pool.index_buffer.clone(),
+            indices: index_alloc.offset..index_alloc.offset + index_alloc.size,
+            index_count: indices.len() as u32,
+        });
+    }
+
+    /// Build the uniform data for a path draw call with a resolved display
+    /// list fill.
+    fn build_path_uniforms(
+        &self,
+        fill: &Fill,
+        transform: &Transform,
+        resolution: [f32; 2],
+    ) -> PathUniformData {
+        let [transform_x, transform_y] = transform_uniform(&self.layout_transform);
+        let [color_a, color_b] = fill.colors;
+        PathUniformData {
+            scale: [transform.scale_x, transform.scale_y],
+            offset: [transform.x, transform.y],
+            resolution,
+            bounds: fill.bounds,
+            color_a,
+            color_b,
+            shader_type: fill.shader_type,
+            fringe_width: self.antialiasing.fringe_width(),
+            dilation: 0.0,
+            dither: self.dither_amplitude(),
+            transform_x,
+            transform_y,
+        }
+    }
+
+    /// The color matrix, output mask, layout opacity, and chroma key for the
+    /// present and downsample passes.
+    fn output_params(&self) -> OutputParams {
+        let encoding = self.color_encoding();
+        let (columns, offset) = self.color_matrix.columns_and_offset();
+        let mask = &self.output_mask;
+        #[expect(clippy::cast_precision_loss)]
+        let mask_size = if mask.is_none() {
+            [0.0; 2]
+        } else {
+            self.viewport.size().map(|extent| extent as f32)
+        };
+        let [corner_radius, border_width] = mask.pixel_extents(self.scale_factor, mask_size);
+        let (key_color, key_threshold) = self.chroma_key.map_or(([0.0; 4], 0.0), |key| {
+            (
+                key.encoded_color(encoding),
+                key.clamped_threshold().unwrap_or(0.0),
+            )
+        });
+        OutputParams {
+            columns,
+            offset,
+            peak: encoding.brightness(),
+            matrix_enabled: u32::from(!self.color_matrix.is_identity()),
+            mask_size,
+            corner_radius,
+            border_width,
+            _pad: [0; 2],
+            border_color: mask.border_color(encoding),
+            key_color,
+            key_threshold,
+            opacity: self.opacity.get(),
+            _pad_key: [0; 2],
+        }
+    }
+
+    /// How colors are written to the output format: sRGB formats encode
+    /// linear values on write, `Rgba16Float` holds linear scRGB, and the
+    /// rest store sRGB-encoded values as is.
+    fn color_encoding(&self) -> ColorEncoding {
+        if self.format.is_srgb() {
+            ColorEncoding::Linear { brightness: 1.0 }
+        } else {
+            output_format(self.format).color_encoding(self.sdr_white_level)
+        }
+    }
+
+    /// Dither amplitude for the output format, or `0.0` if disabled.
+    fn dither_amplitude(&self) -> f32 {
+        self.dithering.amplitude(output_format(self.format))
+    }
+
+    /// Texture format for image data, which is sRGB RGBA8: sampled as
+    /// linear values for targets that store linear values, and as the
+    /// encoded values otherwise.
+    fn image_format(&self) -> wgpu::TextureFormat {
+        if self.color_encoding().is_linear() {
+            wgpu::TextureFormat::Rgba8UnormSrgb
+        } else {
+            wgpu::TextureFormat::Rgba8Unorm
+        }
+    }
+
+    /// Queue a label's shadow, blurred by a gaussian of `shadow.sigma`
+    /// pixels.
+    ///
+    /// The shadow's masks, grown by its spread, are drawn into a free slot
+    /// of the shadow textures and blurred there by the next
+    /// [`flush_shadows`](Self::flush_shadows), and `commands` gets a quad
+    /// that draws the blurred slot in the shadow's color over its region.
+    /// Returns `false` if no slot was free, in which case the caller draws
+    /// a hard-edged shadow instead.
+    fn draw_blurred_shadow(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        commands: &mut Vec<DrawCommand>,
+        shadow: &ShadowItem,
         resolution: [f32; 2],
-    ) {
-        if path.vertices.is_empty() || path.indices.is_empty() {
-            return;
-        }
+    ) -> bool {
+        let Some([x, y, width, height]) =
+            shadow_region(shadow.bounds, shadow.margin(), self.fbo_size)
+        else {
+            return true;
+        };
+        let Some([slot_x, slot_y]) = self
+            .shadow_batch
+            .borrow_mut()
+            .packer
+            .allocate(width, height)
+        else {
+            return false;
+        };
 
-        let uniform_data = self.build_path_uniforms(shader, path, transform, resolution);
+        // The masks are drawn white and moved from the shadow's region into
+        // the slot.
+        // Precision loss is acceptable: texture coordinates are small
+        // relative to f32 mantissa range.
+        #[expect(clippy::cast_precision_loss)]
+        let to_slot =
+            OutputTransform::translation(slot_x as f32 - x as f32, slot_y as f32 - y as f32);
+        let [transform_x, transform_y] = transform_uniform(&self.layout_transform.then(&to_slot));
+        let mut masks = Vec::new();
+        for (path, transform) in &shadow.masks {
+            let uniform_data = PathUniformData {
+                dilation: shadow.spread,
+                transform_x,
+                transform_y,
+                ..self.build_path_uniforms(&Fill::MASK, transform, resolution)
+            };
+            self.push_path(device, queue, &mut masks, path, &uniform_data);
+        }
 
-        let (uniform_alloc, vertex_alloc, index_alloc) = {
+        // Slots are no larger than the viewport, far inside i32 range.
+        #[expect(clippy::cast_possible_wrap)]
+        let region = [
+            slot_x as i32,
+            slot_y as i32,
+            (slot_x + width) as i32,
+            (slot_y + height) as i32,
+        ];
+        let taps = i32::try_from(shadow.taps).expect("tap count exceeds i32::MAX");
+        let slot = [slot_x, slot_y, width, height];
+        let shadow_uniforms = Self::build_shadow_uniforms(shadow.color, [x, y], slot, resolution);
+
+        let (blur_offsets, shadow_alloc) = {
             let mut pool = self.buffer_pool.borrow_mut();
-            let u = pool.alloc_uniform(device, queue, bytemuck::bytes_of(&uniform_data));
-            let v = pool.alloc_vertex(device, queue, bytemuck::cast_slice(&path.vertices));
-            let i = pool.alloc_index(device, queue, bytemuck::cast_slice(&path.indices));
-            (u, v, i)
+            let blur_offsets = [[1, 0], [0, 1]].map(|direction| {
+                let params = ShadowBlurParams {
+                    region,
+                    direction,
+                    sigma: shadow.sigma,
+                    taps,
+                };
+                pool.alloc_uniform(device, queue, bytemuck::bytes_of(&params))
+                    .offset
+            });
+            let shadow_alloc =
+                pool.alloc_uniform(device, queue, bytemuck::bytes_of(&shadow_uniforms));
+            (blur_offsets, shadow_alloc)
         };
 
         self.ensure_uniform_bind_groups(device);
-        let pool = self.buffer_pool.borrow();
-        let bg = self.path_uniform_bind_group.borrow();
-        let bind_group = bg.as_ref().expect("path bind group not initialized");
+        self.ensure_rect_buffers(device);
+        let blur_bind_group = self
+            .shadow_blur_uniform_bind_group
+            .borrow()
+            .clone()
+            .expect("shadow blur bind group not initialized");
+        let text_bind_group = self
+            .text_uniform_bind_group
+            .borrow()
+            .clone()
+            .expect("text bind group not initialized");
+        let targets = self
+            .shadow_targets
+            .as_ref()
+            .expect("shadow textures not initialized");
 
-        pass.set_pipeline(&self.path_pipeline);
+        let mut batch = self.shadow_batch.borrow_mut();
+        batch.masks.append(&mut masks);
         #[expect(clippy::cast_possible_truncation)]
-        pass.set_bind_group(0, bind_group, &[uniform_alloc.offset as u32]);
-        pass.set_vertex_buffer(
-            0,
-            pool.vertex_buffer
-                .slice(vertex_alloc.offset..vertex_alloc.offset + vertex_alloc.size),
-        );
-        pass.set_index_buffer(
-            pool.index_buffer
-                .slice(index_alloc.offset..index_alloc.offset + index_alloc.size),
-            wgpu::IndexFormat::Uint32,
-        );
+        batch.blurs.push(ShadowBlur {
+            slot,
+            uniform_bind_group: blur_bind_group,
+            uniform_offsets: blur_offsets.map(|offset| offset as u32),
+        });
         #[expect(clippy::cast_possible_truncation)]
-        pass.draw_indexed(0..path.indices.len() as u32, 0, 0..1);
+        commands.push(DrawCommand::Shadow {
+            uniform_bind_group: text_bind_group,
+            uniform_offset: shadow_alloc.offset as u32,
+            texture_bind_group: targets.bind_groups[0].clone(),
+        });
+        true
     }
 
-    /// Build the uniform data for a path draw call.
-    ///
-    /// When the render target uses an sRGB format, colors from livesplit-core
-    /// (which are in sRGB space) are converted to linear so the GPU's automatic
-    /// linear→sRGB encoding reproduces the original values. Without this,
-    /// colors would be double-encoded and appear washed out.
-    fn build_path_uniforms(
-        &self,
-        shader: &FillShader,
-        path: &WgpuPath,
-        transform: &Transform,
+    /// Build the uniform data for the quad that draws a blurred shadow's
+    /// `slot` (`[x, y, width, height]` in the shadow textures) in the
+    /// resolved `color`, with its top-left corner at `origin` in viewport
+    /// pixels.
+    fn build_shadow_uniforms(
+        color: [f32; 4],
+        origin: [u32; 2],
+        slot: [u32; 4],
         resolution: [f32; 2],
-    ) -> PathUniformData {
-        let convert = |c: &[f32; 4]| -> [f32; 4] {
-            if self.format.is_srgb() {
-                [
-                    srgb_channel_to_linear(c[0]),
-                    srgb_channel_to_linear(c[1]),
-                    srgb_channel_to_linear(c[2]),
-                    c[3], // alpha is always linear
-                ]
-            } else {
-                *c
-            }
-        };
-
-        match shader {
-            FillShader::SolidColor(color) => PathUniformData {
-                scale: [transform.scale_x, transform.scale_y],
-                offset: [transform.x, transform.y],
-                resolution,
-                bounds: [0.0, 0.0],
-                color_a: convert(color),
-                color_b: [0.0; 4],
-                shader_type: 0,
-                _pad0: 0,
-                _pad1: 0,
-                _pad2: 0,
-            },
-            FillShader::VerticalGradient(top, bottom) => {
-                let [min, max] = vertex_bounds(&path.vertices, 1);
-                PathUniformData {
-                    scale: [transform.scale_x, transform.scale_y],
-                    offset: [transform.x, transform.y],
-                    resolution,
-                    bounds: [min, max],
-                    color_a: convert(top),
-                    color_b: convert(bottom),
-                    shader_type: 1,
-                    _pad0: 0,
-                    _pad1: 0,
-                    _pad2: 0,
-                }
-            }
-            FillShader::HorizontalGradient(left, right) => {
-                let [min, max] = vertex_bounds(&path.vertices, 0);
-                PathUniformData {
-                    scale: [transform.scale_x, transform.scale_y],
-                    offset: [transform.x, transform.y],
-                    resolution,
-                    bounds: [min, max],
-                    color_a: convert(left),
-                    color_b: convert(right),
-                    shader_type: 2,
-                    _pad0: 0,
-                    _pad1: 0,
-                    _pad2: 0,
-                }
-            }
+    ) -> TextUniformData {
+        // The shadow textures are the size of the viewport.
+        let [size_x, size_y] = resolution;
+        // Precision loss is acceptable: texture coordinates are small
+        // relative to f32 mantissa range.
+        #[expect(clippy::cast_precision_loss)]
+        let ([x, y], [slot_x, slot_y, width, height]) = (
+            origin.map(|value| value as f32),
+            slot.map(|value| value as f32),
+        );
+        let [transform_x, transform_y] = transform_uniform(&OutputTransform::IDENTITY);
+        TextUniformData {
+            scale: [1.0, 1.0],
+            offset: [0.0, 0.0],
+            resolution,
+            bounds: [0.0, 0.0],
+            color_a: color,
+            color_b: [0.0; 4],
+            shader_type: 0,
+            outline_width: 0.0,
+            glow_radius: 0.0,
+            coverage: 1,
+            transform_x,
+            transform_y,
+            glyph_rect: [x, y, width, height],
+            uv_rect: [
+                slot_x / size_x,
+                slot_y / size_y,
+                width / size_x,
+                height / size_y,
+            ],
+            outline_color: [0.0; 4],
+            glow_color: [0.0; 4],
         }
     }
 
-    /// Draw a text label (each glyph is a filled path).
-    ///
-    /// If `text_shadow` is set, a shadow pass is rendered first at a small
-    /// offset with the shadow color modulated by the label's alpha.
-    ///
-    /// # Panics
-    ///
-    /// Panics if the label's internal [`RwLock`](std::sync::RwLock) is poisoned.
+    /// Draw one glyph of a label from the glyph atlas: as a distance field
+    /// with `effects`, or as rasterized coverage without. Glyphs that are
+    /// not in the atlas are drawn as geometry.
     #[allow(clippy::too_many_arguments)]
-    fn draw_label(
+    fn draw_glyph(
         &self,
         device: &wgpu::Device,
         queue: &wgpu::Queue,
-        pass: &mut wgpu::RenderPass<'_>,
-        label: &LabelHandle<WgpuLabel>,
-        shader: &FillShader,
-        text_shadow: Option<&[f32; 4]>,
+        commands: &mut Vec<DrawCommand>,
+        path: &WgpuPath,
+        fill: &Fill,
         transform: &Transform,
         resolution: [f32; 2],
+        effects: Option<&TextEffects>,
     ) {
-        let label = label.read().expect("label RwLock poisoned");
-
-        // Render shadow pass first.
-        if let Some(shadow_color) = text_shadow {
-            let alpha = match shader {
-                FillShader::SolidColor([.., a]) => *a,
-                FillShader::VerticalGradient([.., a1], [.., a2])
-                | FillShader::HorizontalGradient([.., a1], [.., a2]) => 0.5 * (a1 + a2),
-            };
-            let shadow_rgba = [
-                shadow_color[0],
-                shadow_color[1],
-                shadow_color[2],
-                shadow_color[3] * alpha,
-            ];
-            let shadow_shader = FillShader::SolidColor(shadow_rgba);
-            let shadow_transform = transform.pre_translate(SHADOW_OFFSET, SHADOW_OFFSET);
-
-            for glyph in label.glyphs() {
-                if let Some(path) = &glyph.path {
-                    let t = shadow_transform
-                        .pre_translate(glyph.x, glyph.y)
-                        .pre_scale(glyph.scale, glyph.scale);
-                    self.draw_path(device, queue, pass, path, &shadow_shader, &t, resolution);
-                }
-            }
+        let uniform_data = if let Some(effects) = effects {
+            let glyph = self.glyph_atlas.borrow_mut().distance_field_glyph(path);
+            glyph.map(|glyph| {
+                self.build_text_uniforms(&glyph, fill, transform, resolution, Some(effects))
+            })
+        } else {
+            let placement = place_rasterized_glyph(transform, &self.layout_transform);
+            let glyph = self
+                .glyph_atlas
+                .borrow_mut()
+                .coverage_glyph(path, &placement);
+            glyph.map(|glyph| {
+                self.build_text_uniforms(&glyph, fill, &placement.transform, resolution, None)
+            })
+        };
+        if let Some(uniform_data) = uniform_data {
+            self.draw_atlas_glyph(device, queue, commands, &uniform_data);
+        } else {
+            let path = self.fill_for(path, transform);
+            self.draw_path(
+                device, queue, commands, &path, fill, transform, resolution, 0.0,
+            );
         }
+    }
 
-        // Render glyphs.
-        for glyph in label.glyphs() {
-            if let Some(path) = &glyph.path {
-                let t = transform
-                    .pre_translate(glyph.x, glyph.y)
-                    .pre_scale(glyph.scale, glyph.scale);
-                let glyph_shader = if let Some(color) = &glyph.color {
-                    FillShader::SolidColor(*color)
-                } else {
-                    *shader
-                };
-                self.draw_path(device, queue, pass, path, &glyph_shader, &t, resolution);
-            }
+    /// Build the uniform data for a glyph drawn from the atlas. `effects` is
+    /// `None` for rasterized coverage glyphs, and the distance field effects,
+    /// with resolved colors, otherwise.
+    fn build_text_uniforms(
+        &self,
+        glyph: &AtlasGlyph,
+        fill: &Fill,
+        transform: &Transform,
+        resolution: [f32; 2],
+        effects: Option<&TextEffects>,
+    ) -> TextUniformData {
+        let path_uniforms = self.build_path_uniforms(fill, transform, resolution);
+        let coverage = i32::from(effects.is_none());
+        let effects = effects.copied().unwrap_or_default();
+        TextUniformData {
+            scale: path_uniforms.scale,
+            offset: path_uniforms.offset,
+            resolution,
+            bounds: path_uniforms.bounds,
+            color_a: path_uniforms.color_a,
+            color_b: path_uniforms.color_b,
+            shader_type: path_uniforms.shader_type,
+            outline_width: effects.outline_width,
+            glow_radius: effects.glow_radius,
+            coverage,
+            transform_x: path_uniforms.transform_x,
+            transform_y: path_uniforms.transform_y,
+            glyph_rect: glyph.rect,
+            uv_rect: glyph.uv,
+            outline_color: effects.outline_color,
+            glow_color: effects.glow_color,
+        }
+    }
+
+    /// Upload the glyph atlas if glyphs were added to it since the last
+    /// upload.
+    fn upload_glyph_atlas(&self, queue: &wgpu::Queue) {
+        let mut atlas = self.glyph_atlas.borrow_mut();
+        if !atlas.take_dirty() {
+            return;
         }
+        queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: &self.atlas_texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            atlas.pixels(),
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(ATLAS_SIZE),
+                rows_per_image: Some(ATLAS_SIZE),
+            },
+            wgpu::Extent3d {
+                width: ATLAS_SIZE,
+                height: ATLAS_SIZE,
+                depth_or_array_layers: 1,
+            },
+        );
+    }
+
+    /// Record a glyph quad sampling the atlas. The atlas itself is uploaded
+    /// at the end of [`prepare`](Self::prepare).
+    fn draw_atlas_glyph(
+        &self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        commands: &mut Vec<DrawCommand>,
+        uniform_data: &TextUniformData,
+    ) {
+        let uniform_alloc = {
+            let mut pool = self.buffer_pool.borrow_mut();
+            pool.alloc_uniform(device, queue, bytemuck::bytes_of(uniform_data))
+        };
+
+        self.ensure_uniform_bind_groups(device);
+        let bg = self.text_uniform_bind_group.borrow();
+        let uniform_bind_group = bg.as_ref().expect("text bind group not initialized");
+        self.ensure_rect_buffers(device);
+
+        #[expect(clippy::cast_possible_truncation)]
+        commands.push(DrawCommand::Glyph {
+            uniform_bind_group: uniform_bind_group.clone(),
+            uniform_offset: uniform_alloc.offset as u32,
+        });
     }
 
-    /// Draw an image entity as a textured quad.
+    /// Draw an image or background image as a textured quad.
     fn draw_image(
         &self,
         device: &wgpu::Device,
         queue: &wgpu::Queue,
-        pass: &mut wgpu::RenderPass<'_>,
-        image: &Handle<WgpuImage>,
-        transform: &Transform,
+        commands: &mut Vec<DrawCommand>,
+        item: &ImageItem<'_, WgpuImage>,
         resolution: [f32; 2],
     ) {
-        let texture_bind_group = self.ensure_texture(device, queue, image);
+        // Blurred background images use the pre-computed blurred copy.
+        let texture_bind_group = match &self.blur_cache {
+            Some(cache) if item.blurred => Arc::clone(&cache.bind_group),
+            _ => self.ensure_texture(device, queue, item.image),
+        };
 
+        let transform = &item.transform;
+        let [transform_x, transform_y] = transform_uniform(&self.layout_transform);
         let uniform_data = ImageUniformData {
             scale: [transform.scale_x, transform.scale_y],
             offset: [transform.x, transform.y],
             resolution,
-            brightness: 1.0,
-            opacity: 1.0,
+            brightness: item.brightness,
+            opacity: item.opacity,
             flip_uv_y: 0,
             already_premultiplied: 0,
-            _pad1: 0,
+            dither: self.dither_amplitude(),
             _pad2: 0,
+            transform_x,
+            transform_y,
         };
 
-        self.draw_textured_rect(device, queue, pass, &uniform_data, &texture_bind_group);
+        self.draw_textured_rect(device, queue, commands, &uniform_data, &texture_bind_group);
     }
 
     /// Ensure an image's pixel data is uploaded as a wgpu texture, returning
@@ -891,8 +2895,6 @@ impl WgpuRenderer {
             }
         }
 
-        // Image source data is sRGB RGBA8, so Rgba8UnormSrgb is the correct
-        // format regardless of the output surface format.
         let texture = device.create_texture(&wgpu::TextureDescriptor {
             label: Some("image_texture"),
             size: wgpu::Extent3d {
@@ -903,7 +2905,7 @@ impl WgpuRenderer {
             mip_level_count: 1,
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            format: self.image_format(),
             usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
             view_formats: &[],
         });
@@ -954,72 +2956,6 @@ impl WgpuRenderer {
         bind_group
     }
 
-    /// Render the scene background (solid color, gradient, or image fill).
-    fn render_background(
-        &self,
-        device: &wgpu::Device,
-        queue: &wgpu::Queue,
-        pass: &mut wgpu::RenderPass<'_>,
-        background: &Background<WgpuImage>,
-        resolution: [f32; 2],
-    ) {
-        match background {
-            Background::Shader(shader) => {
-                // Full-screen quad using the scene rectangle.
-                let transform = Transform {
-                    scale_x: resolution[0],
-                    scale_y: resolution[1],
-                    x: 0.0,
-                    y: 0.0,
-                };
-                let scene = self.scene_manager.scene();
-                let rect = scene.rectangle();
-                if let Some(path) = rect.as_ref() {
-                    self.draw_path(device, queue, pass, path, shader, &transform, resolution);
-                }
-            }
-            Background::Image(bg_image, transform) => {
-                self.draw_background_image(device, queue, pass, bg_image, transform, resolution);
-            }
-        }
-    }
-
-    /// Draw a background image with brightness, opacity, and optional blur.
-    fn draw_background_image(
-        &self,
-        device: &wgpu::Device,
-        queue: &wgpu::Queue,
-        pass: &mut wgpu::RenderPass<'_>,
-        bg_image: &BackgroundImage<Handle<WgpuImage>>,
-        transform: &Transform,
-        resolution: [f32; 2],
-    ) {
-        // Determine which bind group to use: blurred (from pre-computed
-        // cache) or original.
-        let texture_bind_group: Arc<wgpu::BindGroup> = if bg_image.blur > 0.0 {
-            self.blur_cache.as_ref().map_or_else(
-                || self.ensure_texture(device, queue, &bg_image.image),
-                |c| Arc::clone(&c.bind_group),
-            )
-        } else {
-            self.ensure_texture(device, queue, &bg_image.image)
-        };
-
-        let uniform_data = ImageUniformData {
-            scale: [transform.scale_x, transform.scale_y],
-            offset: [transform.x, transform.y],
-            resolution,
-            brightness: bg_image.brightness,
-            opacity: bg_image.opacity,
-            flip_uv_y: 0,
-            already_premultiplied: 0,
-            _pad1: 0,
-            _pad2: 0,
-        };
-
-        self.draw_textured_rect(device, queue, pass, &uniform_data, &texture_bind_group);
-    }
-
     /// Draw the scene's unit rectangle as a textured quad with the given
     /// uniforms and texture bind group. Shared by image, background, and
     /// blit draws.
@@ -1027,9 +2963,9 @@ impl WgpuRenderer {
         &self,
         device: &wgpu::Device,
         queue: &wgpu::Queue,
-        pass: &mut wgpu::RenderPass<'_>,
+        commands: &mut Vec<DrawCommand>,
         uniform_data: &ImageUniformData,
-        texture_bind_group: &wgpu::BindGroup,
+        texture_bind_group: &Arc<wgpu::BindGroup>,
     ) {
         let uniform_alloc = {
             let mut pool = self.buffer_pool.borrow_mut();
@@ -1040,7 +2976,19 @@ impl WgpuRenderer {
         let bg = self.image_uniform_bind_group.borrow();
         let uniform_bind_group = bg.as_ref().expect("image bind group not initialized");
 
-        let rect_bufs = self.rect_buffers.get_or_init(|| {
+        self.ensure_rect_buffers(device);
+
+        #[expect(clippy::cast_possible_truncation)]
+        commands.push(DrawCommand::TexturedRect {
+            uniform_bind_group: uniform_bind_group.clone(),
+            uniform_offset: uniform_alloc.offset as u32,
+            texture_bind_group: Arc::clone(texture_bind_group),
+        });
+    }
+
+    /// Create the cached unit rectangle buffers on first use.
+    fn ensure_rect_buffers(&self, device: &wgpu::Device) {
+        self.rect_buffers.get_or_init(|| {
             let scene = self.scene_manager.scene();
             let rect = scene.rectangle();
             let path = rect.as_ref().expect("scene rectangle not initialized");
@@ -1060,45 +3008,25 @@ impl WgpuRenderer {
                 index_count,
             }
         });
-
-        pass.set_pipeline(&self.image_pipeline);
-        #[expect(clippy::cast_possible_truncation)]
-        pass.set_bind_group(0, uniform_bind_group, &[uniform_alloc.offset as u32]);
-        pass.set_bind_group(1, texture_bind_group, &[]);
-        pass.set_vertex_buffer(0, rect_bufs.vertex.slice(..));
-        pass.set_index_buffer(rect_bufs.index.slice(..), wgpu::IndexFormat::Uint32);
-        pass.draw_indexed(0..rect_bufs.index_count, 0, 0..1);
     }
 
-    /// Blit the cached bottom-layer texture to the current render pass as a
-    /// fullscreen textured quad.
+    /// Record a blit of the cached bottom-layer texture as a fullscreen
+    /// textured quad.
     fn blit_fbo(
         &self,
         device: &wgpu::Device,
         queue: &wgpu::Queue,
-        pass: &mut wgpu::RenderPass<'_>,
+        commands: &mut Vec<DrawCommand>,
         resolution: [f32; 2],
     ) {
-        let fbo_view = self
-            .fbo_texture_view
+        let texture_bind_group = self
+            .fbo_bind_group
             .as_ref()
-            .expect("FBO texture view not initialized");
-
-        let texture_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("blit_texture_bind_group"),
-            layout: &self.image_texture_bind_group_layout,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: wgpu::BindingResource::TextureView(fbo_view),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: wgpu::BindingResource::Sampler(&self.sampler),
-                },
-            ],
-        });
+            .expect("FBO bind group not initialized");
 
+        // The cached bottom layer was already drawn with the output
+        // transform applied, so it is blitted 1:1.
+        let [transform_x, transform_y] = transform_uniform(&OutputTransform::IDENTITY);
         let uniform_data = ImageUniformData {
             scale: resolution,
             offset: [0.0, 0.0],
@@ -1107,22 +3035,34 @@ impl WgpuRenderer {
             opacity: 1.0,
             flip_uv_y: 0,
             already_premultiplied: 1,
-            _pad1: 0,
+            dither: 0.0,
             _pad2: 0,
+            transform_x,
+            transform_y,
         };
 
-        self.draw_textured_rect(device, queue, pass, &uniform_data, &texture_bind_group);
+        self.draw_textured_rect(device, queue, commands, &uniform_data, texture_bind_group);
     }
 
-    /// Resize (or initially create) both the resolve texture and MSAA texture
-    /// to match the given viewport dimensions.
+    /// Resize (or initially create) the resolve, MSAA, and composite
+    /// textures to match the given viewport dimensions.
+    ///
+    /// When supersampling, the resolve and MSAA textures are enlarged by the
+    /// supersampling factor (lowered to fit the device's limits) and the
+    /// MSAA texture is single-sampled so the downsample pass can read it.
+    #[expect(clippy::too_many_lines)] // One descriptor per off-screen texture
     fn resize_fbo(&mut self, device: &wgpu::Device, width: u32, height: u32) {
+        let max_dimension = device.limits().max_texture_dimension_2d;
+        let [target_width, target_height] = self
+            .antialiasing
+            .target_size([width, height], max_dimension);
+
         // Create the resolve target (non-MSAA).
         let fbo_texture = device.create_texture(&wgpu::TextureDescriptor {
             label: Some("fbo_texture"),
             size: wgpu::Extent3d {
-                width,
-                height,
+                width: target_width,
+                height: target_height,
                 depth_or_array_layers: 1,
             },
             mip_level_count: 1,
@@ -1135,26 +3075,185 @@ impl WgpuRenderer {
         let fbo_texture_view = fbo_texture.create_view(&wgpu::TextureViewDescriptor::default());
 
         // Create the MSAA texture.
+        let msaa_usage = match downsample_filter(self.antialiasing) {
+            None => wgpu::TextureUsages::RENDER_ATTACHMENT,
+            Some(_) => {
+                wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING
+            }
+        };
         let msaa_texture = device.create_texture(&wgpu::TextureDescriptor {
             label: Some("msaa_texture"),
+            size: wgpu::Extent3d {
+                width: target_width,
+                height: target_height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: sample_count(self.antialiasing),
+            dimension: wgpu::TextureDimension::D2,
+            format: self.format,
+            usage: msaa_usage,
+            view_formats: &[],
+        });
+        let msaa_texture_view = msaa_texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        // Create the downsample bindings for the supersampled frame.
+        let (ssaa_bind_group, downsample_params_bind_group) =
+            if let Some(filter) = downsample_filter(self.antialiasing) {
+                let params = DownsampleParams {
+                    factor: self
+                        .antialiasing
+                        .fitted_supersample_factor([width, height], max_dimension),
+                    filter_type: match filter {
+                        DownsampleFilter::Box => 0,
+                        DownsampleFilter::Lanczos => 1,
+                    },
+                    dither: self.dither_amplitude(),
+                    peak: self.color_encoding().brightness(),
+                };
+                let params_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some("downsample_params_buffer"),
+                    contents: bytemuck::bytes_of(&params),
+                    usage: wgpu::BufferUsages::UNIFORM,
+                });
+                let ssaa_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: Some("ssaa_texture_bind_group"),
+                    layout: &self.image_texture_bind_group_layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: wgpu::BindingResource::TextureView(&msaa_texture_view),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: wgpu::BindingResource::Sampler(&self.sampler),
+                        },
+                    ],
+                });
+                let params_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: Some("downsample_params_bind_group"),
+                    layout: &self.downsample_params_bind_group_layout,
+                    entries: &[wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: params_buffer.as_entire_binding(),
+                    }],
+                });
+                (Some(ssaa_bind_group), Some(params_bind_group))
+            } else {
+                (None, None)
+            };
+
+        // Create the intermediate target for drawing into a sub-rectangle
+        // of the output.
+        let composite_texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("composite_texture"),
             size: wgpu::Extent3d {
                 width,
                 height,
                 depth_or_array_layers: 1,
             },
             mip_level_count: 1,
-            sample_count: MSAA_SAMPLES,
+            sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
             format: self.format,
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
             view_formats: &[],
         });
-        let msaa_texture_view = msaa_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let composite_texture_view =
+            composite_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let composite_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("composite_texture_bind_group"),
+            layout: &self.image_texture_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&composite_texture_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+            ],
+        });
+
+        // Create the single-channel targets for blurred text shadows.
+        let shadow_textures = ["shadow_texture_a", "shadow_texture_b"].map(|label| {
+            device.create_texture(&wgpu::TextureDescriptor {
+                label: Some(label),
+                size: wgpu::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::R8Unorm,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            })
+        });
+        let shadow_views = shadow_textures
+            .each_ref()
+            .map(|texture| texture.create_view(&wgpu::TextureViewDescriptor::default()));
+        let shadow_bind_groups = shadow_views.each_ref().map(|view| {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("shadow_texture_bind_group"),
+                layout: &self.image_texture_bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(&self.sampler),
+                    },
+                ],
+            })
+        });
+
+        let fbo_bind_group = Arc::new(device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("blit_texture_bind_group"),
+            layout: &self.image_texture_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&fbo_texture_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+            ],
+        }));
 
         self.fbo_texture = Some(fbo_texture);
         self.fbo_texture_view = Some(fbo_texture_view);
+        self.fbo_bind_group = Some(fbo_bind_group);
         self.msaa_texture = Some(msaa_texture);
         self.msaa_texture_view = Some(msaa_texture_view);
+        self.ssaa_bind_group = ssaa_bind_group;
+        self.downsample_params_bind_group = downsample_params_bind_group;
+        self.composite_texture = Some(composite_texture);
+        self.composite_texture_view = Some(composite_texture_view);
+        self.composite_bind_group = Some(composite_bind_group);
+        self.shadow_targets = Some(ShadowTargets {
+            textures: shadow_textures,
+            views: shadow_views,
+            bind_groups: shadow_bind_groups,
+        });
+        *self.shadow_batch.get_mut() = ShadowBatch::new([width, height]);
+        if let Some(post_process) = &mut self.post_process {
+            post_process.resize(
+                device,
+                self.format,
+                [width, height],
+                &self.sampler,
+                &self.image_texture_bind_group_layout,
+            );
+        }
         self.fbo_size = [width, height];
     }
 }
@@ -1174,6 +3273,11 @@ mod tests {
         assert_eq!(std::mem::offset_of!(PathUniformData, color_a), 32);
         assert_eq!(std::mem::offset_of!(PathUniformData, color_b), 48);
         assert_eq!(std::mem::offset_of!(PathUniformData, shader_type), 64);
+        assert_eq!(std::mem::offset_of!(PathUniformData, fringe_width), 68);
+        assert_eq!(std::mem::offset_of!(PathUniformData, dilation), 72);
+        assert_eq!(std::mem::offset_of!(PathUniformData, dither), 76);
+        assert_eq!(std::mem::offset_of!(PathUniformData, transform_x), 80);
+        assert_eq!(std::mem::offset_of!(PathUniformData, transform_y), 96);
     }
 
     #[test]
@@ -1189,5 +3293,81 @@ mod tests {
             std::mem::offset_of!(ImageUniformData, already_premultiplied),
             36
         );
+        assert_eq!(std::mem::offset_of!(ImageUniformData, transform_x), 48);
+        assert_eq!(std::mem::offset_of!(ImageUniformData, transform_y), 64);
+    }
+
+    #[test]
+    fn text_uniform_data_layout() {
+        // Verify field offsets match the WGSL TextUniforms struct.
+        assert_eq!(std::mem::offset_of!(TextUniformData, color_a), 32);
+        assert_eq!(std::mem::offset_of!(TextUniformData, shader_type), 64);
+        assert_eq!(std::mem::offset_of!(TextUniformData, outline_width), 68);
+        assert_eq!(std::mem::offset_of!(TextUniformData, glow_radius), 72);
+        assert_eq!(std::mem::offset_of!(TextUniformData, coverage), 76);
+        assert_eq!(std::mem::offset_of!(TextUniformData, transform_x), 80);
+        assert_eq!(std::mem::offset_of!(TextUniformData, glyph_rect), 112);
+        assert_eq!(std::mem::offset_of!(TextUniformData, uv_rect), 128);
+        assert_eq!(std::mem::offset_of!(TextUniformData, outline_color), 144);
+        assert_eq!(std::mem::offset_of!(TextUniformData, glow_color), 160);
+    }
+
+    #[test]
+    fn downsample_params_layout() {
+        // Verify field offsets match the WGSL DownsampleParams struct.
+        assert_eq!(std::mem::offset_of!(DownsampleParams, factor), 0);
+        assert_eq!(std::mem::offset_of!(DownsampleParams, filter_type), 4);
+        assert_eq!(std::mem::offset_of!(DownsampleParams, dither), 8);
+        assert_eq!(std::mem::offset_of!(DownsampleParams, peak), 12);
+    }
+
+    #[test]
+    fn output_params_layout() {
+        // Verify field offsets match the WGSL OutputParams struct.
+        assert_eq!(std::mem::offset_of!(OutputParams, offset), 64);
+        assert_eq!(std::mem::offset_of!(OutputParams, peak), 80);
+        assert_eq!(std::mem::offset_of!(OutputParams, mask_size), 88);
+        assert_eq!(std::mem::offset_of!(OutputParams, border_color), 112);
+        assert_eq!(std::mem::offset_of!(OutputParams, key_color), 128);
+        assert_eq!(std::mem::offset_of!(OutputParams, key_threshold), 144);
+        assert_eq!(std::mem::offset_of!(OutputParams, opacity), 148);
+    }
+
+    #[test]
+    fn shadow_blur_params_layout() {
+        // Verify field offsets match the WGSL ShadowBlurParams struct.
+        assert_eq!(std::mem::offset_of!(ShadowBlurParams, region), 0);
+        assert_eq!(std::mem::offset_of!(ShadowBlurParams, direction), 16);
+        assert_eq!(std::mem::offset_of!(ShadowBlurParams, sigma), 24);
+        assert_eq!(std::mem::offset_of!(ShadowBlurParams, taps), 28);
+    }
+
+    #[test]
+    fn sample_count_matches_antialiasing() {
+        assert_eq!(sample_count(Antialiasing::Msaa), MSAA_SAMPLES);
+        let ssaa = Antialiasing::Supersample {
+            factor: 2,
+            filter: DownsampleFilter::Lanczos,
+        };
+        assert_eq!(sample_count(ssaa), 1);
+        assert_eq!(sample_count(Antialiasing::Analytic), 1);
+    }
+
+    #[test]
+    fn only_msaa_is_resolved() {
+        assert_eq!(downsample_filter(Antialiasing::Msaa), None);
+        assert_eq!(
+            downsample_filter(Antialiasing::Analytic),
+            Some(DownsampleFilter::Box)
+        );
+    }
+
+    #[test]
+    fn transform_uniform_pads_rows() {
+        let transform = OutputTransform::from_rows([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        assert_eq!(
+            transform_uniform(&transform),
+            [[1.0, 2.0, 3.0, 0.0], [4.0, 5.0, 6.0, 0.0]]
+        );
     }
 }
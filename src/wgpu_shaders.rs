@@ -2,11 +2,165 @@
 //!
 //! All shaders use WGSL, targeting wgpu's shader model.
 
+/// WGSL `bayer8` and `dither` functions shared by the path, image, and
+/// downsample shaders, the counterpart of the GLSL dither snippet.
+/// `dither(color, amplitude, position)` takes the fragment's clip position.
+macro_rules! dither_snippet {
+    () => {
+        r"
+// 8x8 ordered (Bayer) dither threshold of a pixel, in [-0.5, 0.5).
+fn bayer8(pixel: vec2u) -> f32 {
+    let x = pixel.x & 7u;
+    let xy = x ^ (pixel.y & 7u);
+    let index = ((xy & 1u) << 5u) | ((x & 1u) << 4u) | ((xy & 2u) << 2u)
+              | ((x & 2u) << 1u) | ((xy & 4u) >> 1u) | ((x & 4u) >> 2u);
+    return (f32(index) + 0.5) / 64.0 - 0.5;
+}
+
+// Dither a premultiplied color by `amplitude` (in color units) before it
+// is quantized to the target's precision, keeping it premultiplied.
+fn dither(color: vec4f, amplitude: f32, position: vec4f) -> vec4f {
+    let noise = bayer8(vec2u(position.xy)) * amplitude * color.a;
+    return vec4f(clamp(color.rgb + noise, vec3f(0.0), vec3f(color.a)), color.a);
+}
+"
+    };
+}
+
+/// WGSL `DrawParams` uniform struct shared by the path, image, and text
+/// shaders: an `opacity: f32` multiplier of every draw, `1.0` except when
+/// [`WgpuRenderer::paint`](crate::WgpuRenderer::paint) fades the layout.
+macro_rules! draw_params_snippet {
+    () => {
+        r"
+struct DrawParams {
+    opacity: f32,
+}
+"
+    };
+}
+
+/// WGSL vertex stage shared by the present, downsample, and post-process
+/// shaders: the `VertexOutput` struct and a `vs_main` that draws one
+/// triangle covering the whole viewport, with `uv` running from `(0, 0)` at
+/// its top-left corner to `(1, 1)` at its bottom-right.
+macro_rules! fullscreen_vertex_snippet {
+    () => {
+        r"
+struct VertexOutput {
+    @builtin(position) clip_position: vec4f,
+    @location(0) uv: vec2f,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    // UVs (0,0), (2,0), (0,2): one triangle that covers the whole viewport.
+    let uv = vec2f(f32((index << 1u) & 2u), f32(index & 2u));
+    var out: VertexOutput;
+    out.uv = uv;
+    out.clip_position = vec4f(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, 0.0, 1.0);
+    return out;
+}
+"
+    };
+}
+
+/// WGSL output stage shared by the present and downsample shaders: the
+/// `OutputParams` uniform struct and the `apply_output`, `apply_mask`,
+/// `apply_key`, `apply_color_matrix`, and `unpremultiply` functions. The
+/// including shader binds the struct as `output_params`.
+macro_rules! output_snippet {
+    () => {
+        r"
+struct OutputParams {
+    matrix: mat4x4f,
+    offset: vec4f,
+    peak: f32,
+    matrix_enabled: u32,
+    mask_size: vec2f,
+    corner_radius: f32,
+    border_width: f32,
+    border_color: vec4f,
+    key_color: vec4f,
+    key_threshold: f32,
+    opacity: f32,
+}
+
+// Apply the output mask, if any, and then the color matrix, if enabled, to
+// a premultiplied color at `uv` in the viewport, and fade it by the
+// layout's opacity.
+fn apply_output(color: vec4f, uv: vec2f) -> vec4f {
+    var masked = color;
+    if output_params.mask_size.x > 0.0 {
+        masked = apply_mask(color, uv * output_params.mask_size);
+    }
+    return apply_color_matrix(masked) * output_params.opacity;
+}
+
+// Cut a premultiplied color to the rounded rectangle of the mask at
+// `position` in pixels, with antialiased edges, and draw the border over
+// its inside edge.
+fn apply_mask(color: vec4f, position: vec2f) -> vec4f {
+    let size = output_params.mask_size;
+    let radius = output_params.corner_radius;
+    let q = abs(position - 0.5 * size) - (0.5 * size - radius);
+    let dist = length(max(q, vec2f(0.0))) + min(max(q.x, q.y), 0.0) - radius;
+    let outer = clamp(0.5 - dist, 0.0, 1.0);
+    let border = outer - clamp(0.5 - dist - output_params.border_width, 0.0, 1.0);
+    let border_color = output_params.border_color;
+    return border_color * border + color * outer * (1.0 - border_color.a * border);
+}
+
+// Composite a premultiplied color over the opaque key color (a zero key
+// color, for no key, leaves it as it is). With a key threshold, replace a
+// color that covers less than the threshold of its pixel with the key
+// color and make any other color opaque instead, so that no pixel mixes
+// the layout with the key.
+fn apply_key(color: vec4f) -> vec4f {
+    if output_params.key_threshold <= 0.0 {
+        return color + output_params.key_color * (1.0 - color.a);
+    }
+    if color.a < output_params.key_threshold {
+        return output_params.key_color;
+    }
+    return vec4f(color.rgb / color.a, 1.0);
+}
+
+// Apply the color matrix, if enabled, to the straight channels of a
+// premultiplied color, with the layout's white at `peak`, keeping it
+// premultiplied.
+fn apply_color_matrix(color: vec4f) -> vec4f {
+    if output_params.matrix_enabled == 0u {
+        return color;
+    }
+    var straight = vec4f(0.0);
+    if color.a > 0.0 {
+        straight = vec4f(color.rgb / color.a / output_params.peak, color.a);
+    }
+    let result = clamp(output_params.matrix * straight + output_params.offset, vec4f(0.0), vec4f(1.0));
+    return vec4f(result.rgb * output_params.peak * result.a, result.a);
+}
+
+// Divide a premultiplied color by its alpha.
+fn unpremultiply(color: vec4f) -> vec4f {
+    if color.a <= 0.0 {
+        return vec4f(0.0);
+    }
+    return vec4f(color.rgb / color.a, color.a);
+}
+"
+    };
+}
+
 /// Vertex shader for filled/stroked paths.
 ///
-/// Transforms vertices by the entity's scale+translate transform, and passes
-/// the *local-space* position to the fragment shader for gradient
-/// interpolation.
+/// Transforms vertices by the entity's scale+translate transform followed by
+/// the renderer's affine output transform, and passes the *local-space*
+/// position to the fragment shader for gradient interpolation.
+///
+/// Edge vertices are moved along their normal to open up the coverage
+/// fringe and to grow the shape by `dilation`, as in the GLSL path vertex
+/// shader.
 ///
 /// # Uniforms (via bind group 0)
 ///
@@ -19,7 +173,17 @@
 /// | `color_a`      | `vec4f`| Solid color or gradient start            |
 /// | `color_b`      | `vec4f`| Gradient end color                       |
 /// | `bounds`       | `vec2f`| [min, max] for gradient axis             |
-pub const PATH_SHADER_SRC: &str = r"
+/// | `transform_x`  | `vec4f`| First row of the output transform (xyz)  |
+/// | `transform_y`  | `vec4f`| Second row of the output transform (xyz) |
+/// | `fringe_width` | `f32`  | Coverage fringe width in pixels (or 0)   |
+/// | `dilation`     | `f32`  | Outward shift of the edges in pixels     |
+/// | `dither`       | `f32`  | Dither amplitude (0.0 = off)             |
+///
+/// As in the GLSL path fragment shader, a non-zero `dither` adds an ordered
+/// dither keyed to the pixel position. The result is multiplied by the
+/// `DrawParams` opacity bound in group 1.
+pub const PATH_SHADER_SRC: &str = concat!(
+    r"
 struct PathUniforms {
     scale: vec2f,
     offset: vec2f,
@@ -28,29 +192,53 @@ struct PathUniforms {
     color_a: vec4f,
     color_b: vec4f,
     shader_type: i32,
-    _pad0: i32,
-    _pad1: i32,
-    _pad2: i32,
+    fringe_width: f32,
+    dilation: f32,
+    dither: f32,
+    transform_x: vec4f,
+    transform_y: vec4f,
 }
 
 @group(0) @binding(0)
 var<uniform> u: PathUniforms;
+@group(1) @binding(0)
+var<uniform> draw: DrawParams;
 
 struct VertexInput {
     @location(0) position: vec2f,
+    @location(1) normal: vec2f,
+    @location(2) coverage: f32,
 }
 
 struct VertexOutput {
     @builtin(position) clip_position: vec4f,
     @location(0) local: vec2f,
+    @location(1) coverage: f32,
 }
 
 @vertex
 fn vs_main(in: VertexInput) -> VertexOutput {
     var out: VertexOutput;
     out.local = in.position;
+    out.coverage = in.coverage;
 
-    let world = u.offset + u.scale * in.position;
+    let layout_pos = vec3f(u.offset + u.scale * in.position, 1.0);
+    var world = vec2f(dot(u.transform_x.xyz, layout_pos), dot(u.transform_y.xyz, layout_pos));
+
+    // Cofactors of the entity scale, then of the output transform.
+    let n_scaled = in.normal * u.scale.yx;
+    var n = vec2f(
+        u.transform_y.y * n_scaled.x - u.transform_y.x * n_scaled.y,
+        u.transform_x.x * n_scaled.y - u.transform_x.y * n_scaled.x,
+    );
+    let det = (u.transform_x.x * u.transform_y.y - u.transform_x.y * u.transform_y.x)
+        * u.scale.x * u.scale.y;
+    let n_len = length(n);
+    if n_len > 0.0 {
+        // Keep the miter length, in pixels, that was computed in local space.
+        n *= sign(det) * length(in.normal) / n_len;
+        world += n * (u.fringe_width * (0.5 - in.coverage) + u.dilation);
+    }
 
     // Convert from [0, resolution] to [-1, 1] (flip Y for clip space)
     var ndc = (world / u.resolution) * 2.0 - 1.0;
@@ -87,9 +275,19 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4f {
 
     // Premultiply alpha for correct blending
     frag_color = vec4f(frag_color.rgb * frag_color.a, frag_color.a);
-    return frag_color;
+
+    // Fade out across the antialiasing fringe
+    frag_color *= in.coverage;
+
+    if u.dither > 0.0 {
+        frag_color = dither(frag_color, u.dither, in.clip_position);
+    }
+    return frag_color * draw.opacity;
 }
-";
+",
+    dither_snippet!(),
+    draw_params_snippet!(),
+);
 
 /// Combined vertex and fragment shader for textured quads (images).
 ///
@@ -107,7 +305,14 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4f {
 /// | `flip_uv_y`    | `i32`   | Flip V coordinate (for FBO blitting)  |
 /// | `brightness`   | `f32`   | Brightness multiplier (1.0 = normal)  |
 /// | `opacity`      | `f32`   | Opacity multiplier (1.0 = opaque)     |
-pub const IMAGE_SHADER_SRC: &str = r"
+/// | `dither`       | `f32`   | Dither amplitude (0.0 = off)          |
+/// | `transform_x`  | `vec4f` | First output transform row (xyz)      |
+/// | `transform_y`  | `vec4f` | Second output transform row (xyz)     |
+///
+/// The texture and its sampler are bound in group 1, and the `DrawParams`
+/// opacity that multiplies the result in group 2.
+pub const IMAGE_SHADER_SRC: &str = concat!(
+    r"
 struct ImageUniforms {
     scale: vec2f,
     offset: vec2f,
@@ -116,8 +321,10 @@ struct ImageUniforms {
     opacity: f32,
     flip_uv_y: i32,
     already_premultiplied: i32,
-    _pad1: i32,
+    dither: f32,
     _pad2: i32,
+    transform_x: vec4f,
+    transform_y: vec4f,
 }
 
 @group(0) @binding(0)
@@ -127,6 +334,8 @@ var<uniform> iu: ImageUniforms;
 var t_texture: texture_2d<f32>;
 @group(1) @binding(1)
 var s_sampler: sampler;
+@group(2) @binding(0)
+var<uniform> draw: DrawParams;
 
 struct VertexInput {
     @location(0) position: vec2f,
@@ -145,7 +354,8 @@ fn vs_main(in: VertexInput) -> VertexOutput {
         out.uv.y = 1.0 - out.uv.y;
     }
 
-    let world = iu.offset + iu.scale * in.position;
+    let layout_pos = vec3f(iu.offset + iu.scale * in.position, 1.0);
+    let world = vec2f(dot(iu.transform_x.xyz, layout_pos), dot(iu.transform_y.xyz, layout_pos));
     var ndc = (world / iu.resolution) * 2.0 - 1.0;
     ndc.y = -ndc.y;
 
@@ -158,23 +368,429 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4f {
     var frag_color = textureSample(t_texture, s_sampler, in.uv);
     frag_color = vec4f(frag_color.rgb * iu.brightness, frag_color.a);
     frag_color = vec4f(frag_color.rgb, frag_color.a * iu.opacity);
-    // Premultiply alpha (skip if content is already premultiplied, e.g. FBO blit)
+    // Premultiply alpha (content that is already premultiplied, e.g. an
+    // FBO blit, only needs the opacity applied to its colors too)
     if iu.already_premultiplied == 0 {
         frag_color = vec4f(frag_color.rgb * frag_color.a, frag_color.a);
+    } else {
+        frag_color = vec4f(frag_color.rgb * iu.opacity, frag_color.a);
+    }
+    if iu.dither > 0.0 {
+        frag_color = dither(frag_color, iu.dither, in.clip_position);
     }
-    return frag_color;
+    return frag_color * draw.opacity;
+}
+",
+    dither_snippet!(),
+    draw_params_snippet!(),
+);
+
+/// Combined vertex and fragment shader for text drawn from the glyph atlas.
+///
+/// Stretches the scene's unit rectangle over one glyph's quad from the glyph
+/// atlas, transformed like a path, and turns the sampled signed distance
+/// into fill, outline, and glow coverage, or uses the sampled coverage
+/// directly, as in the GLSL text shaders.
+///
+/// # Uniforms (via bind group 0)
+///
+/// | Name            | Type    | Description                                  |
+/// |-----------------|---------|----------------------------------------------|
+/// | `scale`         | `vec2f` | Glyph scale (width, height)                  |
+/// | `offset`        | `vec2f` | Glyph translation (x, y)                     |
+/// | `resolution`    | `vec2f` | Viewport size in pixels                      |
+/// | `bounds`        | `vec2f` | [min, max] for gradient axis                 |
+/// | `color_a`       | `vec4f` | Solid color or gradient start                |
+/// | `color_b`       | `vec4f` | Gradient end color                           |
+/// | `shader_type`   | `i32`   | 0 = solid, 1 = vertical gradient, 2 = horizontal |
+/// | `outline_width` | `f32`   | Outline width in pixels (or 0)               |
+/// | `glow_radius`   | `f32`   | Glow radius in pixels (or 0)                 |
+/// | `coverage`      | `i32`   | 1 if the atlas holds coverage                |
+/// | `transform_x`   | `vec4f` | First row of the output transform (xyz)      |
+/// | `transform_y`   | `vec4f` | Second row of the output transform (xyz)     |
+/// | `glyph_rect`    | `vec4f` | Quad origin and size in glyph-local space    |
+/// | `uv_rect`       | `vec4f` | Quad origin and size in the atlas            |
+/// | `outline_color` | `vec4f` | Outline color                                |
+/// | `glow_color`    | `vec4f` | Glow color                                   |
+///
+/// The atlas texture and its sampler are bound in group 1, and the
+/// `DrawParams` opacity that multiplies the result in group 2.
+pub const TEXT_SHADER_SRC: &str = concat!(
+    r"
+struct TextUniforms {
+    scale: vec2f,
+    offset: vec2f,
+    resolution: vec2f,
+    bounds: vec2f,
+    color_a: vec4f,
+    color_b: vec4f,
+    shader_type: i32,
+    outline_width: f32,
+    glow_radius: f32,
+    coverage: i32,
+    transform_x: vec4f,
+    transform_y: vec4f,
+    glyph_rect: vec4f,
+    uv_rect: vec4f,
+    outline_color: vec4f,
+    glow_color: vec4f,
+}
+
+@group(0) @binding(0)
+var<uniform> u: TextUniforms;
+
+@group(1) @binding(0)
+var t_atlas: texture_2d<f32>;
+@group(1) @binding(1)
+var s_sampler: sampler;
+@group(2) @binding(0)
+var<uniform> draw: DrawParams;
+
+struct VertexInput {
+    @location(0) position: vec2f,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4f,
+    @location(0) local: vec2f,
+    @location(1) uv: vec2f,
+}
+
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    out.local = u.glyph_rect.xy + in.position * u.glyph_rect.zw;
+    out.uv = u.uv_rect.xy + in.position * u.uv_rect.zw;
+
+    let layout_pos = vec3f(u.offset + u.scale * out.local, 1.0);
+    let world = vec2f(dot(u.transform_x.xyz, layout_pos), dot(u.transform_y.xyz, layout_pos));
+    var ndc = (world / u.resolution) * 2.0 - 1.0;
+    ndc.y = -ndc.y;
+
+    out.clip_position = vec4f(ndc, 0.0, 1.0);
+    return out;
+}
+
+fn premultiply(color: vec4f) -> vec4f {
+    return vec4f(color.rgb * color.a, color.a);
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4f {
+    // Sampled and differentiated before any branching, which WGSL requires.
+    let dist = textureSample(t_atlas, s_sampler, in.uv).r;
+    let per_pixel = length(vec2f(dpdx(dist), dpdy(dist)));
+
+    var fill: vec4f;
+    if u.shader_type == 0 {
+        fill = u.color_a;
+    } else {
+        var coord: f32;
+        if u.shader_type == 1 {
+            coord = in.local.y;
+        } else {
+            coord = in.local.x;
+        }
+        let range = u.bounds.y - u.bounds.x;
+        var t = 0.0;
+        if range > 0.0 {
+            t = clamp((coord - u.bounds.x) / range, 0.0, 1.0);
+        }
+        fill = mix(u.color_a, u.color_b, t);
+    }
+
+    if u.coverage != 0 {
+        // The atlas holds the glyph's coverage, not a distance field.
+        return premultiply(fill) * dist * draw.opacity;
+    }
+
+    // Signed distance to the outline in pixels, positive inside.
+    let px = (dist - 0.5) / max(per_pixel, 1e-6);
+
+    let fill_alpha = clamp(px + 0.5, 0.0, 1.0);
+    var outline_alpha = 0.0;
+    if u.outline_width > 0.0 {
+        outline_alpha = clamp(px + u.outline_width + 0.5, 0.0, 1.0);
+    }
+    let outside = max(-(px + u.outline_width), 0.0);
+    var glow_alpha = 0.0;
+    if u.glow_radius > 0.0 {
+        glow_alpha = 1.0 - smoothstep(0.0, u.glow_radius, outside);
+    }
+
+    var frag_color = premultiply(fill) * fill_alpha;
+    frag_color += premultiply(u.outline_color) * outline_alpha * (1.0 - frag_color.a);
+    frag_color += premultiply(u.glow_color) * glow_alpha * (1.0 - frag_color.a);
+    return frag_color * draw.opacity;
+}
+",
+    draw_params_snippet!(),
+);
+
+/// Full-screen shader that copies the composited layout into the caller's
+/// target.
+///
+/// Draws a single triangle covering the current viewport, generated from
+/// the vertex index (no vertex buffer), and samples the composited layout
+/// texture at the matching UV. Used when the layout cannot be resolved
+/// directly into the output, e.g. when drawing into a sub-rectangle of a
+/// larger target. The colors are cut to the output mask and filtered by the
+/// color matrix, if they are enabled, faded by the layout's opacity, and
+/// keyed against the chroma key, and the `fs_straight` entry point divides
+/// them by their alpha for straight-alpha output.
+///
+/// # Bindings
+///
+/// | Group | Binding | Type          | Description                  |
+/// |-------|---------|---------------|------------------------------|
+/// | `0`   | `0`     | `texture_2d`  | Composited layout texture    |
+/// | `0`   | `1`     | `sampler`     | Sampler for the texture      |
+/// | `1`   | `0`     | uniform       | `OutputParams`, see below    |
+///
+/// `OutputParams` holds the color matrix (`matrix: mat4x4f` and
+/// `offset: vec4f`, applied if `matrix_enabled: u32` is non-zero), the
+/// color value of the layout's white (`peak: f32`), the output mask
+/// (`mask_size: vec2f` in pixels, `0` for no mask, `corner_radius: f32`,
+/// `border_width: f32`, and the premultiplied `border_color: vec4f`), the
+/// chroma key (the opaque `key_color: vec4f`, `0` for no key, and
+/// `key_threshold: f32`, `0` to mix partially covered pixels with the key),
+/// and the layout's `opacity: f32`.
+pub const PRESENT_SHADER_SRC: &str = concat!(
+    r"
+@group(0) @binding(0)
+var t_texture: texture_2d<f32>;
+@group(0) @binding(1)
+var s_sampler: sampler;
+@group(1) @binding(0)
+var<uniform> output_params: OutputParams;
+",
+    fullscreen_vertex_snippet!(),
+    r"
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4f {
+    return apply_key(apply_output(textureSample(t_texture, s_sampler, in.uv), in.uv));
+}
+
+@fragment
+fn fs_straight(in: VertexOutput) -> @location(0) vec4f {
+    return unpremultiply(apply_key(apply_output(textureSample(t_texture, s_sampler, in.uv), in.uv)));
+}
+",
+    output_snippet!(),
+);
+
+/// Full-viewport shader that downsamples a supersampled frame into the
+/// caller's target.
+///
+/// Uses the same vertex stage as [`PRESENT_SHADER_SRC`]. Each output pixel
+/// covers a `factor`×`factor` block of source texels. The box filter
+/// averages that block; the Lanczos filter weights the texels within two
+/// output pixels with a two-lobe Lanczos kernel. The source is
+/// premultiplied, and the result is clamped so that Lanczos ringing cannot
+/// produce colors brighter than their alpha allows (`peak` times alpha).
+/// Like the present shader, it applies the output mask, color matrix,
+/// layout opacity, and chroma key (after dithering, so the key stays
+/// exact), and the `fs_straight` entry point outputs straight alpha.
+///
+/// # Bindings
+///
+/// | Group | Binding | Type         | Description                        |
+/// |-------|---------|--------------|------------------------------------|
+/// | `0`   | `0`     | `texture_2d` | Supersampled frame                 |
+/// | `1`   | `0`     | uniform      | `DownsampleParams`, see below      |
+/// | `2`   | `0`     | uniform      | `OutputParams`, see below          |
+///
+/// `DownsampleParams` holds the supersampling `factor: u32`, the
+/// `filter_type: u32` (`0` for box, `1` for Lanczos), the `dither: f32`
+/// amplitude, and `peak: f32`. `OutputParams` is the output mask, color
+/// matrix, layout opacity, and chroma key, as in [`PRESENT_SHADER_SRC`].
+pub const DOWNSAMPLE_SHADER_SRC: &str = concat!(
+    r"
+struct DownsampleParams {
+    factor: u32,
+    filter_type: u32,
+    dither: f32,
+    peak: f32,
+}
+
+@group(0) @binding(0)
+var t_texture: texture_2d<f32>;
+
+@group(1) @binding(0)
+var<uniform> params: DownsampleParams;
+
+@group(2) @binding(0)
+var<uniform> output_params: OutputParams;
+",
+    fullscreen_vertex_snippet!(),
+    r"
+const PI: f32 = 3.14159265;
+
+fn sinc(x: f32) -> f32 {
+    if abs(x) < 1e-5 {
+        return 1.0;
+    }
+    let px = PI * x;
+    return sin(px) / px;
+}
+
+fn lanczos2(x: f32) -> f32 {
+    if abs(x) < 2.0 {
+        return sinc(x) * sinc(x * 0.5);
+    }
+    return 0.0;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4f {
+    return downsample(in);
+}
+
+@fragment
+fn fs_straight(in: VertexOutput) -> @location(0) vec4f {
+    return unpremultiply(downsample(in));
+}
+
+// The filtered, premultiplied color of an output pixel.
+fn downsample(in: VertexOutput) -> vec4f {
+    let size = vec2i(textureDimensions(t_texture));
+    let factor = f32(params.factor);
+    // Center of this output pixel, in source texels.
+    let center = in.uv * vec2f(size);
+
+    var taps = i32(params.factor);
+    if params.filter_type == 1u {
+        taps = 4 * taps;
+    }
+    let first = vec2i(floor(center - 0.5 * f32(taps)));
+
+    var sum = vec4f(0.0);
+    var weight_sum = 0.0;
+    for (var j = 0; j < taps; j++) {
+        for (var i = 0; i < taps; i++) {
+            let texel = first + vec2i(i, j);
+            var weight = 1.0;
+            if params.filter_type == 1u {
+                let d = (vec2f(texel) + 0.5 - center) / factor;
+                weight = lanczos2(d.x) * lanczos2(d.y);
+            }
+            let color = textureLoad(t_texture, clamp(texel, vec2i(0), size - 1), 0);
+            sum += weight * color;
+            weight_sum += weight;
+        }
+    }
+
+    let alpha = clamp(sum.a / weight_sum, 0.0, 1.0);
+    let rgb = clamp(sum.rgb / weight_sum, vec3f(0.0), vec3f(alpha * params.peak));
+    var filtered = apply_output(vec4f(rgb, alpha), in.uv);
+    if params.dither > 0.0 {
+        filtered = dither(filtered, params.dither, in.clip_position);
+    }
+    return apply_key(filtered);
+}
+",
+    output_snippet!(),
+    dither_snippet!(),
+);
+
+/// Shader that blurs text shadow coverage along one axis.
+///
+/// Draws a full-viewport triangle; the caller limits it to one shadow's
+/// slot with the scissor rectangle. Each texel is the gaussian-weighted sum
+/// of the red channel `taps` texels either side along `direction`, with
+/// taps clamped to `region` so neighbouring slots never bleed in. The
+/// result is written to every channel.
+///
+/// # Bindings
+///
+/// | Group | Binding | Type         | Description                        |
+/// |-------|---------|--------------|------------------------------------|
+/// | `0`   | `0`     | `texture_2d` | Coverage to blur                   |
+/// | `1`   | `0`     | uniform      | `region: vec4i` (min xy, exclusive max zw), `direction: vec2i`, `sigma: f32`, `taps: i32` |
+pub const SHADOW_BLUR_SHADER_SRC: &str = r"
+struct ShadowBlurParams {
+    region: vec4i,
+    direction: vec2i,
+    sigma: f32,
+    taps: i32,
+}
+
+@group(0) @binding(0)
+var t_texture: texture_2d<f32>;
+
+@group(1) @binding(0)
+var<uniform> params: ShadowBlurParams;
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4f {
+    // One triangle that covers the whole viewport.
+    let uv = vec2f(f32((index << 1u) & 2u), f32(index & 2u));
+    return vec4f(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4f) -> @location(0) vec4f {
+    let texel = vec2i(position.xy);
+    var sum = 0.0;
+    var weight_sum = 0.0;
+    for (var i = -params.taps; i <= params.taps; i++) {
+        let tap = clamp(texel + i * params.direction, params.region.xy, params.region.zw - 1);
+        let weight = exp(-f32(i * i) / (2.0 * params.sigma * params.sigma));
+        sum += weight * textureLoad(t_texture, tap, 0).r;
+        weight_sum += weight;
+    }
+    return vec4f(sum / weight_sum);
 }
 ";
 
+/// Declarations prepended to every post-processing effect's WGSL source.
+///
+/// Provides a full-viewport vertex stage (`vs_main`, with the same
+/// `VertexOutput` as [`PRESENT_SHADER_SRC`]) and the effect's inputs. The
+/// effect supplies the fragment entry point `fs_main`.
+///
+/// # Bindings (via bind group 0)
+///
+/// | Binding | Type          | Description                                     |
+/// |---------|---------------|-------------------------------------------------|
+/// | `0`     | uniform       | `post`, see below                               |
+/// | `1`     | `texture_2d`  | `layout_texture`: the frame so far, premultiplied |
+/// | `2`     | `sampler`     | `layout_sampler`: linear, clamped to the edges  |
+///
+/// `post` holds the viewport size in pixels (`resolution: vec2f`) and the
+/// seconds since the effects were set (`time: f32`).
+pub const POST_PROCESS_PRELUDE: &str = concat!(
+    r"
+struct PostUniforms {
+    resolution: vec2f,
+    time: f32,
+    _pad: f32,
+}
+
+@group(0) @binding(0)
+var<uniform> post: PostUniforms;
+@group(0) @binding(1)
+var layout_texture: texture_2d<f32>;
+@group(0) @binding(2)
+var layout_sampler: sampler;
+",
+    fullscreen_vertex_snippet!(),
+);
+
 /// Create a wgpu render pipeline for path rendering.
 ///
+/// `sample_count` must match the sample count of the render pass the
+/// pipeline is used in.
+///
 /// # Panics
 ///
 /// Panics if shader compilation fails (indicates a bug in the shader source).
 pub fn create_path_pipeline(
     device: &wgpu::Device,
     format: wgpu::TextureFormat,
+    sample_count: u32,
     path_bind_group_layout: &wgpu::BindGroupLayout,
+    draw_params_bind_group_layout: &wgpu::BindGroupLayout,
 ) -> wgpu::RenderPipeline {
     let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
         label: Some("path_shader"),
@@ -183,7 +799,7 @@ pub fn create_path_pipeline(
 
     let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
         label: Some("path_pipeline_layout"),
-        bind_group_layouts: &[path_bind_group_layout],
+        bind_group_layouts: &[path_bind_group_layout, draw_params_bind_group_layout],
         immediate_size: 0,
     });
 
@@ -196,11 +812,11 @@ pub fn create_path_pipeline(
             buffers: &[wgpu::VertexBufferLayout {
                 array_stride: std::mem::size_of::<super::wgpu_types::Vertex>() as u64,
                 step_mode: wgpu::VertexStepMode::Vertex,
-                attributes: &[wgpu::VertexAttribute {
-                    format: wgpu::VertexFormat::Float32x2,
-                    offset: 0,
-                    shader_location: 0,
-                }],
+                attributes: &wgpu::vertex_attr_array![
+                    0 => Float32x2,
+                    1 => Float32x2,
+                    2 => Float32,
+                ],
             }],
             compilation_options: wgpu::PipelineCompilationOptions::default(),
         },
@@ -215,7 +831,7 @@ pub fn create_path_pipeline(
         },
         depth_stencil: None,
         multisample: wgpu::MultisampleState {
-            count: super::wgpu_render::MSAA_SAMPLES,
+            count: sample_count,
             mask: !0,
             alpha_to_coverage_enabled: false,
         },
@@ -247,14 +863,19 @@ pub fn create_path_pipeline(
 
 /// Create a wgpu render pipeline for image rendering.
 ///
+/// `sample_count` must match the sample count of the render pass the
+/// pipeline is used in.
+///
 /// # Panics
 ///
 /// Panics if shader compilation fails (indicates a bug in the shader source).
 pub fn create_image_pipeline(
     device: &wgpu::Device,
     format: wgpu::TextureFormat,
+    sample_count: u32,
     image_uniform_bind_group_layout: &wgpu::BindGroupLayout,
     image_texture_bind_group_layout: &wgpu::BindGroupLayout,
+    draw_params_bind_group_layout: &wgpu::BindGroupLayout,
 ) -> wgpu::RenderPipeline {
     let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
         label: Some("image_shader"),
@@ -266,6 +887,7 @@ pub fn create_image_pipeline(
         bind_group_layouts: &[
             image_uniform_bind_group_layout,
             image_texture_bind_group_layout,
+            draw_params_bind_group_layout,
         ],
         immediate_size: 0,
     });
@@ -298,7 +920,96 @@ pub fn create_image_pipeline(
         },
         depth_stencil: None,
         multisample: wgpu::MultisampleState {
-            count: super::wgpu_render::MSAA_SAMPLES,
+            count: sample_count,
+            mask: !0,
+            alpha_to_coverage_enabled: false,
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: &shader,
+            entry_point: Some("fs_main"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(wgpu::BlendState {
+                    color: wgpu::BlendComponent {
+                        src_factor: wgpu::BlendFactor::One,
+                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+                        operation: wgpu::BlendOperation::Add,
+                    },
+                    alpha: wgpu::BlendComponent {
+                        src_factor: wgpu::BlendFactor::One,
+                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+                        operation: wgpu::BlendOperation::Add,
+                    },
+                }),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        }),
+        multiview_mask: None,
+        cache: None,
+    })
+}
+
+/// Create a wgpu render pipeline for text drawn from the glyph atlas.
+///
+/// `sample_count` must match the sample count of the render pass the
+/// pipeline is used in.
+///
+/// # Panics
+///
+/// Panics if shader compilation fails (indicates a bug in the shader source).
+pub fn create_text_pipeline(
+    device: &wgpu::Device,
+    format: wgpu::TextureFormat,
+    sample_count: u32,
+    text_bind_group_layout: &wgpu::BindGroupLayout,
+    atlas_bind_group_layout: &wgpu::BindGroupLayout,
+    draw_params_bind_group_layout: &wgpu::BindGroupLayout,
+) -> wgpu::RenderPipeline {
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("text_shader"),
+        source: wgpu::ShaderSource::Wgsl(TEXT_SHADER_SRC.into()),
+    });
+
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("text_pipeline_layout"),
+        bind_group_layouts: &[
+            text_bind_group_layout,
+            atlas_bind_group_layout,
+            draw_params_bind_group_layout,
+        ],
+        immediate_size: 0,
+    });
+
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("text_pipeline"),
+        layout: Some(&pipeline_layout),
+        vertex: wgpu::VertexState {
+            module: &shader,
+            entry_point: Some("vs_main"),
+            buffers: &[wgpu::VertexBufferLayout {
+                array_stride: std::mem::size_of::<super::wgpu_types::Vertex>() as u64,
+                step_mode: wgpu::VertexStepMode::Vertex,
+                attributes: &[wgpu::VertexAttribute {
+                    format: wgpu::VertexFormat::Float32x2,
+                    offset: 0,
+                    shader_location: 0,
+                }],
+            }],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        },
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            strip_index_format: None,
+            front_face: wgpu::FrontFace::Ccw,
+            cull_mode: None,
+            unclipped_depth: false,
+            polygon_mode: wgpu::PolygonMode::Fill,
+            conservative: false,
+        },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState {
+            count: sample_count,
             mask: !0,
             alpha_to_coverage_enabled: false,
         },
@@ -327,3 +1038,236 @@ pub fn create_image_pipeline(
         cache: None,
     })
 }
+
+/// Create a wgpu render pipeline that copies the composited layout into the
+/// caller's (single-sampled) target.
+///
+/// With `blend` set to `None` the layout replaces the target's pixels;
+/// passing premultiplied alpha blending composites it over them instead.
+/// With `straight_alpha` set, the colors are divided by their alpha before
+/// they are written or blended.
+///
+/// # Panics
+///
+/// Panics if shader compilation fails (indicates a bug in the shader source).
+pub fn create_present_pipeline(
+    device: &wgpu::Device,
+    format: wgpu::TextureFormat,
+    texture_bind_group_layout: &wgpu::BindGroupLayout,
+    output_params_bind_group_layout: &wgpu::BindGroupLayout,
+    blend: Option<wgpu::BlendState>,
+    straight_alpha: bool,
+) -> wgpu::RenderPipeline {
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("present_shader"),
+        source: wgpu::ShaderSource::Wgsl(PRESENT_SHADER_SRC.into()),
+    });
+
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("present_pipeline_layout"),
+        bind_group_layouts: &[texture_bind_group_layout, output_params_bind_group_layout],
+        immediate_size: 0,
+    });
+
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("present_pipeline"),
+        layout: Some(&pipeline_layout),
+        vertex: wgpu::VertexState {
+            module: &shader,
+            entry_point: Some("vs_main"),
+            buffers: &[],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        },
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        fragment: Some(wgpu::FragmentState {
+            module: &shader,
+            entry_point: Some(if straight_alpha {
+                "fs_straight"
+            } else {
+                "fs_main"
+            }),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend,
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        }),
+        multiview_mask: None,
+        cache: None,
+    })
+}
+
+/// Create a wgpu render pipeline that downsamples a supersampled frame into
+/// the caller's (single-sampled) target.
+///
+/// `blend` and `straight_alpha` work as for [`create_present_pipeline`].
+///
+/// # Panics
+///
+/// Panics if shader compilation fails (indicates a bug in the shader source).
+pub fn create_downsample_pipeline(
+    device: &wgpu::Device,
+    format: wgpu::TextureFormat,
+    texture_bind_group_layout: &wgpu::BindGroupLayout,
+    params_bind_group_layout: &wgpu::BindGroupLayout,
+    output_params_bind_group_layout: &wgpu::BindGroupLayout,
+    blend: Option<wgpu::BlendState>,
+    straight_alpha: bool,
+) -> wgpu::RenderPipeline {
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("downsample_shader"),
+        source: wgpu::ShaderSource::Wgsl(DOWNSAMPLE_SHADER_SRC.into()),
+    });
+
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("downsample_pipeline_layout"),
+        bind_group_layouts: &[
+            texture_bind_group_layout,
+            params_bind_group_layout,
+            output_params_bind_group_layout,
+        ],
+        immediate_size: 0,
+    });
+
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("downsample_pipeline"),
+        layout: Some(&pipeline_layout),
+        vertex: wgpu::VertexState {
+            module: &shader,
+            entry_point: Some("vs_main"),
+            buffers: &[],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        },
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        fragment: Some(wgpu::FragmentState {
+            module: &shader,
+            entry_point: Some(if straight_alpha {
+                "fs_straight"
+            } else {
+                "fs_main"
+            }),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend,
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        }),
+        multiview_mask: None,
+        cache: None,
+    })
+}
+
+/// Create the render pipeline that blurs text shadows along one axis, for
+/// a single-sample, single-channel target.
+///
+/// # Panics
+///
+/// Panics if shader compilation fails (indicates a bug in the shader source).
+pub fn create_shadow_blur_pipeline(
+    device: &wgpu::Device,
+    texture_bind_group_layout: &wgpu::BindGroupLayout,
+    params_bind_group_layout: &wgpu::BindGroupLayout,
+) -> wgpu::RenderPipeline {
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("shadow_blur_shader"),
+        source: wgpu::ShaderSource::Wgsl(SHADOW_BLUR_SHADER_SRC.into()),
+    });
+
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("shadow_blur_pipeline_layout"),
+        bind_group_layouts: &[texture_bind_group_layout, params_bind_group_layout],
+        immediate_size: 0,
+    });
+
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("shadow_blur_pipeline"),
+        layout: Some(&pipeline_layout),
+        vertex: wgpu::VertexState {
+            module: &shader,
+            entry_point: Some("vs_main"),
+            buffers: &[],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        },
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        fragment: Some(wgpu::FragmentState {
+            module: &shader,
+            entry_point: Some("fs_main"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format: wgpu::TextureFormat::R8Unorm,
+                blend: None,
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        }),
+        multiview_mask: None,
+        cache: None,
+    })
+}
+
+/// The complete WGSL module of a post-processing effect: its `source`
+/// appended to [`POST_PROCESS_PRELUDE`].
+pub fn post_process_source(source: &str) -> String {
+    format!("{POST_PROCESS_PRELUDE}\n{source}")
+}
+
+/// Create the render pipeline for one post-processing effect, whose WGSL
+/// `source` is appended to [`POST_PROCESS_PRELUDE`].
+///
+/// # Panics
+///
+/// Invalid effect source is reported through the device's error handler,
+/// like any other wgpu validation error; by default it panics. Effects are
+/// validated with [`PostEffect::validate`](crate::PostEffect::validate)
+/// before they get here.
+pub fn create_post_process_pipeline(
+    device: &wgpu::Device,
+    format: wgpu::TextureFormat,
+    bind_group_layout: &wgpu::BindGroupLayout,
+    label: &str,
+    source: &str,
+) -> wgpu::RenderPipeline {
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some(label),
+        source: wgpu::ShaderSource::Wgsl(post_process_source(source).into()),
+    });
+
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("post_process_pipeline_layout"),
+        bind_group_layouts: &[bind_group_layout],
+        immediate_size: 0,
+    });
+
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some(label),
+        layout: Some(&pipeline_layout),
+        vertex: wgpu::VertexState {
+            module: &shader,
+            entry_point: Some("vs_main"),
+            buffers: &[],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        },
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        fragment: Some(wgpu::FragmentState {
+            module: &shader,
+            entry_point: Some("fs_main"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: None,
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        }),
+        multiview_mask: None,
+        cache: None,
+    })
+}